@@ -2,17 +2,28 @@ use std::os::raw::c_char;
 
 use crate::PLUGIN_COMMAND;
 use crate::cfg::get_preloader;
-use crate::py::callbacks::PY_CALLBACK_MANAGER;
+use crate::damage::{ATTACK_ACTION, find_attacker, lock_tracker};
+use crate::functions::player::PlayerPy;
+use crate::functions::vehicle::{VEHICLE_PART_COUNT, VEHICLE_TYRE_COUNT, VehiclePy};
+use crate::functions::{animation, attachment, player_class};
+use crate::py::callbacks::{PY_CALLBACK_MANAGER, PY_CALLBACK_STORAGE};
 use crate::py::events::{
-    VcmpEvent, checkpoint::*, object::*, pickup::*, player::*, server::*, vehicle::*,
+    VcmpEvent, VcmpEventType, checkpoint::*, object::*, pickup::*, player::*, server::*, vehicle::*,
 };
+use crate::py::streams::{chunk, compress, message};
 use crate::py::types::VectorPy;
 use vcmp_bindings::events::{checkpoint, object, pickup, player, server, vehicle};
-use vcmp_bindings::func::{PlayerMethods, PluginMethods, QueryVehicle, SetVehicle};
+use vcmp_bindings::func::{
+    PlayerMethods, PluginMethods, QueryVehicle, QueryVehicleOptions, SetVehicle, SetVehicleOptions,
+};
 use vcmp_bindings::vcmp_func;
 use vcmp_bindings::{options::VcmpEntityPool, raw::PluginCallbacks};
 
-use crate::{pool::ENTITY_POOL, py::load_script, py::reload};
+use crate::{
+    pool::{fetch_player, fetch_vehicle, read_pool, write_pool},
+    py::load_script,
+    py::reload,
+};
 use tracing::{Level, event};
 
 // use crate::py::callbacks::CALLBACK;
@@ -20,9 +31,11 @@ use tracing::{Level, event};
 #[unsafe(no_mangle)]
 pub extern "C" fn on_server_init() -> u8 {
     if !get_preloader() {
-        load_script();
+        let _ = load_script(None);
     }
 
+    player_class::load_class_file();
+
     // Call Plugin Command
     let _ = vcmp_func().send_plugin_command(PLUGIN_COMMAND, "");
 
@@ -39,6 +52,9 @@ pub extern "C" fn on_server_frame(elapsed_time: f32) {
 
     reload();
 
+    animation::tick_animations();
+    chunk::tick_transfers();
+
     // println!("[Rust] Server frame callback time: {}", elapsed_time);
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::ServerFrame(ServerFrameEvent::from(server::ServerFrameEvent::from(
@@ -82,9 +98,13 @@ pub unsafe extern "C" fn on_entity_pool_change(c_entity_type: i32, entity_id: i3
     event!(Level::TRACE, "entity id: {entity_id}");
     event!(Level::TRACE, "deleted: {deleted}");
 
-    let mut pool = ENTITY_POOL.lock().expect("pool is poisoned");
+    let mut pool = write_pool();
 
     if deleted {
+        if matches!(entity_type, VcmpEntityPool::Object) {
+            animation::forget_object(entity_id);
+        }
+        attachment::forget_entity(&entity_type, entity_id);
         pool.remove(entity_type, entity_id);
     } else {
         pool.insert(entity_type, entity_id);
@@ -139,6 +159,9 @@ pub unsafe extern "C" fn on_player_disconnect(player_id: i32, reason: i32) {
         false,
     );
 
+    lock_tracker().forget(player_id);
+    chunk::forget_player(player_id);
+
     // Update entity pool
     unsafe {
         on_entity_pool_change(VcmpEntityPool::Player as i32, player_id, 1);
@@ -151,11 +174,26 @@ pub unsafe extern "C" fn on_player_disconnect(player_id: i32, reason: i32) {
 /// - Buffer must remain valid during function execution
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_client_script_data(client_id: i32, data: *const u8, size: usize) {
-    let binding_event = player::ClientScriptDataEvent::from((client_id, data, size));
+    let mut binding_event = player::ClientScriptDataEvent::from((client_id, data, size));
+    if chunk::handle_client_data(client_id, &binding_event.data) {
+        return;
+    }
+    match compress::unpack(std::mem::take(&mut binding_event.data)) {
+        Ok(data) => binding_event.data = data,
+        Err(e) => {
+            event!(
+                Level::WARN,
+                "Dropped client script data from player {client_id}: {e}"
+            );
+            return;
+        }
+    }
+    let data = binding_event.data.clone();
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::ClientScriptData(ClientScriptDataEvent::from(binding_event)),
         false,
     );
+    message::dispatch(client_id, data);
 }
 
 /// # Safety
@@ -164,11 +202,10 @@ pub unsafe extern "C" fn on_client_script_data(client_id: i32, data: *const u8,
 pub unsafe extern "C" fn on_player_request_class(player_id: i32, class_id: i32) -> u8 {
     {
         // set loaded
-        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        let player = pool
-            .get_mut_player(player_id)
-            .expect("Failed to get mut player");
-        player.set_var_loaded(true);
+        let mut pool = write_pool();
+        if let Some(player) = pool.get_mut_player_or_insert(player_id) {
+            player.set_var_loaded(true);
+        }
     }
 
     let binding_event = player::PlayerRequestClassEvent::from((player_id, class_id));
@@ -231,6 +268,9 @@ pub unsafe extern "C" fn on_player_request_enter_vehicle(
 /// FFI callback for player spawn
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_player_spawn(player_id: i32) {
+    // 新的一条命
+    lock_tracker().clear_ledger(player_id);
+
     let binding_event = player::PlayerSpawnEvent::from(player_id);
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::PlayerSpawn(PlayerSpawnEvent::from(binding_event)),
@@ -252,6 +292,8 @@ pub unsafe extern "C" fn on_player_death(
         VcmpEvent::PlayerDeath(PlayerDeathEvent::from(binding_event)),
         false,
     );
+    // 死亡回调处理完才清掉，回调里还要用来算助攻
+    lock_tracker().clear_ledger(player_id);
 }
 
 /// # Safety
@@ -284,6 +326,10 @@ pub unsafe extern "C" fn on_player_state_change(player_id: i32, old_state: i32,
 /// FFI callback for player action change
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_player_action_change(player_id: i32, old_action: i32, new_action: i32) {
+    if new_action == ATTACK_ACTION {
+        // 挥拳不耗弹药，只能靠动作来判断空手的伤害来源
+        lock_tracker().record_attack(player_id);
+    }
     let binding_event = player::PlayerActionChangeEvent::from((player_id, old_action, new_action));
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::PlayerActionChange(PlayerActionChangeEvent::from(binding_event)),
@@ -452,67 +498,52 @@ pub unsafe extern "C" fn on_player_module_list(player_id: i32, modules: *const c
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
     {
+        // 一次读锁拿到所有 last_* 变量
+        // 只有事件被拦截的时候才需要重新读（回调里可能改了 current_*）
+        let last = fetch_player(player_id);
         // first health
         {
             // use raw vcmp_bindings
             let current_health = vcmp_func().get_player_health(player_id);
-            let last_health = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player_id)
-                    .expect("Failed to get mut player");
-                player.get_var_last_health()
-            };
+            let last_health = last.get_var_last_health();
             if current_health != last_health {
                 let event = PlayerHealthChangeEvent::from((player_id, last_health, current_health));
                 let health_res =
                     PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerHealthChange(event), true);
                 if !health_res {
-                    let _ = vcmp_func().set_player_health(player_id, {
-                        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                        let player = pool
-                            .get_mut_player(player_id)
-                            .expect("Failed to get mut player");
-                        player.get_var_last_health()
-                    });
+                    let _ = vcmp_func().set_player_health(
+                        player_id,
+                        fetch_player(player_id).get_var_last_health(),
+                    );
+                } else if current_health < last_health {
+                    let amount = last_health - current_health;
+                    let (attacker_id, weapon) = find_attacker(player_id);
+                    lock_tracker().record_damage(player_id, attacker_id, weapon, amount);
+                    let event = PlayerDamageEvent::from((player_id, attacker_id, weapon, amount));
+                    let _ = PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerDamage(event), false);
                 }
             }
         }
         // then armour
         {
             let current_armour = vcmp_func().get_player_armour(player_id);
-            let last_armour = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player_id)
-                    .expect("Failed to get mut player");
-                player.get_var_last_armour()
-            };
+            let last_armour = last.get_var_last_armour();
             if current_armour != last_armour {
                 let event = PlayerArmourChangeEvent::from((player_id, last_armour, current_armour));
                 let armour_res =
                     PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerArmourChange(event), true);
                 if !armour_res {
-                    let _ = vcmp_func().set_player_armour(player_id, {
-                        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                        let player = pool
-                            .get_mut_player(player_id)
-                            .expect("Failed to get mut player");
-                        player.get_var_last_armour()
-                    });
+                    let _ = vcmp_func().set_player_armour(
+                        player_id,
+                        fetch_player(player_id).get_var_last_armour(),
+                    );
                 }
             }
         }
         // then weapon
         {
             let current_weapon = vcmp_func().get_player_weapon(player_id);
-            let last_weapon = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player_id)
-                    .expect("Failed to get mut player");
-                player.get_var_last_weapon()
-            };
+            let last_weapon = last.get_var_last_weapon();
             if current_weapon != last_weapon {
                 let event = PlayerWeaponChangeEvent::from((player_id, last_weapon, current_weapon));
                 let weapon_res =
@@ -520,13 +551,7 @@ pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
                 if !weapon_res {
                     let _ = vcmp_func().give_player_weapon(
                         player_id,
-                        {
-                            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                            let player = pool
-                                .get_mut_player(player_id)
-                                .expect("Failed to get mut player");
-                            player.get_var_last_weapon()
-                        },
+                        fetch_player(player_id).get_var_last_weapon(),
                         0,
                     );
                 }
@@ -536,25 +561,17 @@ pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
         {
             let current_ammo = vcmp_func().get_player_weapon_ammo(player_id);
             let current_wep = vcmp_func().get_player_weapon(player_id);
-            let last_ammo = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player_id)
-                    .expect("Failed to get mut player");
-                player.get_var_last_ammo()
-            };
+            let last_ammo = last.get_var_last_ammo();
+            if current_ammo < last_ammo && current_wep == last.get_var_last_weapon() {
+                // 弹药减少就当作开了一枪，用来推测伤害来源
+                lock_tracker().record_shot(player_id, current_wep);
+            }
             if current_ammo != last_ammo {
                 let event = PlayerAmmoChangeEvent::from((player_id, last_ammo, current_ammo));
                 let res = PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerAmmoChange(event), true);
                 if !res {
                     let real_ammo = vcmp_func().get_player_weapon_ammo(player_id);
-                    let restore_ammo = {
-                        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                        let player = pool
-                            .get_mut_player(player_id)
-                            .expect("Failed to get mut player");
-                        player.get_var_last_ammo()
-                    } - real_ammo;
+                    let restore_ammo = fetch_player(player_id).get_var_last_ammo() - real_ammo;
                     let _ = vcmp_func().give_player_weapon(player_id, current_wep, restore_ammo);
                 }
             }
@@ -564,13 +581,7 @@ pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
             let current_pos = vcmp_func()
                 .get_player_position(player_id)
                 .unwrap_or_default();
-            let last_pos = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player_id)
-                    .expect("Failed to get mut player");
-                player.get_var_last_position()
-            };
+            let last_pos = last.get_var_last_position();
             if current_pos != last_pos {
                 let event = PlayerMoveEvent::from((
                     player_id,
@@ -579,16 +590,178 @@ pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
                 ));
                 let move_res = PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerMove(event), true);
                 if !move_res {
-                    let _ = vcmp_func().set_player_position(player_id, {
-                        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                        let player = pool
-                            .get_mut_player(player_id)
-                            .expect("Failed to get mut player");
-                        player.get_var_last_position()
-                    });
+                    let _ = vcmp_func().set_player_position(
+                        player_id,
+                        fetch_player(player_id).get_var_last_position(),
+                    );
                 }
             }
         }
+        // 以下是可选的追踪，只有注册了对应事件才会去查询
+        // 没注册的就把记录清掉，下次注册的时候重新开始记录，不会拿到过期的旧值
+        let (track_skin, track_team, track_score, track_world, track_cash, track_wanted_level) = {
+            let storage = PY_CALLBACK_STORAGE
+                .lock()
+                .expect("PyCallbackStorage lock failed");
+            (
+                storage.has_handlers(VcmpEventType::PlayerSkinChange),
+                storage.has_handlers(VcmpEventType::PlayerTeamChange),
+                storage.has_handlers(VcmpEventType::PlayerScoreChange),
+                storage.has_handlers(VcmpEventType::PlayerWorldChange),
+                storage.has_handlers(VcmpEventType::PlayerCashChange),
+                storage.has_handlers(VcmpEventType::PlayerWantedLevelChange),
+            )
+        };
+        // then skin
+        {
+            let last_skin = last.get_var_last_skin();
+            if track_skin {
+                let current_skin = vcmp_func().get_player_skin(player_id);
+                match last_skin {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_skin(Some(current_skin))
+                    }),
+                    Some(last_skin) if last_skin != current_skin => {
+                        let event =
+                            PlayerSkinChangeEvent::from((player_id, last_skin, current_skin));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerSkinChange(event), true);
+                        if !res && let Some(skin) = fetch_player(player_id).get_var_last_skin() {
+                            let _ = vcmp_func().set_player_skin(player_id, skin);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_skin.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_skin(None));
+            }
+        }
+        // then team
+        {
+            let last_team = last.get_var_last_team();
+            if track_team {
+                let current_team = vcmp_func().get_player_team(player_id);
+                match last_team {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_team(Some(current_team))
+                    }),
+                    Some(last_team) if last_team != current_team => {
+                        let event =
+                            PlayerTeamChangeEvent::from((player_id, last_team, current_team));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerTeamChange(event), true);
+                        if !res && let Some(team) = fetch_player(player_id).get_var_last_team() {
+                            let _ = vcmp_func().set_player_team(player_id, team);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_team.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_team(None));
+            }
+        }
+        // then score
+        {
+            let last_score = last.get_var_last_score();
+            if track_score {
+                let current_score = vcmp_func().get_player_score(player_id);
+                match last_score {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_score(Some(current_score))
+                    }),
+                    Some(last_score) if last_score != current_score => {
+                        let event =
+                            PlayerScoreChangeEvent::from((player_id, last_score, current_score));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerScoreChange(event), true);
+                        if !res && let Some(score) = fetch_player(player_id).get_var_last_score() {
+                            let _ = vcmp_func().set_player_score(player_id, score);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_score.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_score(None));
+            }
+        }
+        // then world
+        {
+            let last_world = last.get_var_last_world();
+            if track_world {
+                let current_world = vcmp_func().get_player_world(player_id);
+                match last_world {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_world(Some(current_world))
+                    }),
+                    Some(last_world) if last_world != current_world => {
+                        let event =
+                            PlayerWorldChangeEvent::from((player_id, last_world, current_world));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerWorldChange(event), true);
+                        if !res && let Some(world) = fetch_player(player_id).get_var_last_world() {
+                            let _ = vcmp_func().set_player_world(player_id, world);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_world.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_world(None));
+            }
+        }
+        // then cash
+        {
+            let last_cash = last.get_var_last_cash();
+            if track_cash {
+                let current_cash = vcmp_func().get_player_money(player_id);
+                match last_cash {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_cash(Some(current_cash))
+                    }),
+                    Some(last_cash) if last_cash != current_cash => {
+                        let event =
+                            PlayerCashChangeEvent::from((player_id, last_cash, current_cash));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::PlayerCashChange(event), true);
+                        if !res && let Some(cash) = fetch_player(player_id).get_var_last_cash() {
+                            let _ = vcmp_func().set_player_money(player_id, cash);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_cash.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_cash(None));
+            }
+        }
+        // then wanted level
+        {
+            let last_wanted_level = last.get_var_last_wanted_level();
+            if track_wanted_level {
+                let current_wanted_level = vcmp_func().get_player_wanted_level(player_id);
+                match last_wanted_level {
+                    None => update_player_var(player_id, |player| {
+                        player.set_var_last_wanted_level(Some(current_wanted_level))
+                    }),
+                    Some(last_wanted_level) if last_wanted_level != current_wanted_level => {
+                        let event = PlayerWantedLevelChangeEvent::from((
+                            player_id,
+                            last_wanted_level,
+                            current_wanted_level,
+                        ));
+                        let res = PY_CALLBACK_MANAGER
+                            .handle(VcmpEvent::PlayerWantedLevelChange(event), true);
+                        if !res
+                            && let Some(wanted_level) =
+                                fetch_player(player_id).get_var_last_wanted_level()
+                        {
+                            let _ = vcmp_func().set_player_wanted_level(player_id, wanted_level);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_wanted_level.is_some() {
+                update_player_var(player_id, |player| player.set_var_last_wanted_level(None));
+            }
+        }
     }
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::PlayerUpdate(PlayerUpdateEvent::from(player::PlayerUpdateEvent::from((
@@ -596,59 +769,64 @@ pub unsafe extern "C" fn on_player_update(player_id: i32, state: i32) {
         )))),
         true,
     );
+
+    attachment::update_player_attachments(player_id);
+}
+
+/// 在写锁里修改玩家的 last_* 变量
+fn update_player_var(player_id: i32, f: impl FnOnce(&mut PlayerPy)) {
+    let mut pool = write_pool();
+    if let Some(player) = pool.get_mut_player_or_insert(player_id) {
+        f(player);
+    }
+}
+
+/// 在写锁里修改载具的 last_* 变量
+fn update_vehicle_var(vehicle_id: i32, f: impl FnOnce(&mut VehiclePy)) {
+    let mut pool = write_pool();
+    if let Some(vehicle) = pool.get_mut_vehicle_or_insert(vehicle_id) {
+        f(vehicle);
+    }
 }
 
 /// # Safety
 /// FFI callback for vehicle update
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_vehicle_update(vehicle_id: i32, update_type: i32) {
-    {
-        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        let vehicle = pool
-            .get_mut_vehicle(vehicle_id)
-            .expect("Failed to get mut vehicle");
+    // 检查 updating 的同时拿一份 last_* 变量
+    let last = {
+        let mut pool = write_pool();
+        let Some(vehicle) = pool.get_mut_vehicle_or_insert(vehicle_id) else {
+            return;
+        };
         if vehicle.get_var_updating() {
             return;
         }
         vehicle.set_var_updating(true);
-    }
+        *vehicle
+    };
     {
         {
             // health change
             let current_health = vcmp_func().get_vehicle_health(vehicle_id);
-            let last_health = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let vehicle = pool
-                    .get_mut_vehicle(vehicle_id)
-                    .expect("Failed to get mut vehicle");
-                vehicle.get_var_last_health()
-            };
+            let last_health = last.get_var_last_health();
             if current_health != last_health {
                 let event =
                     VehicleHealthChangeEvent::from((vehicle_id, last_health, current_health));
                 let health_res =
                     PY_CALLBACK_MANAGER.handle(VcmpEvent::VehicleHealthChange(event), true);
                 if !health_res {
-                    let _ = vcmp_func().set_vehicle_health(vehicle_id, {
-                        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                        let vehicle = pool
-                            .get_mut_vehicle(vehicle_id)
-                            .expect("Failed to get mut vehicle");
-                        vehicle.get_var_last_health()
-                    });
+                    let _ = vcmp_func().set_vehicle_health(
+                        vehicle_id,
+                        fetch_vehicle(vehicle_id).get_var_last_health(),
+                    );
                 }
             }
         }
         {
             // move change
             let current_pos = vcmp_func().get_vehicle_position(vehicle_id);
-            let last_pos = {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let vehicle = pool
-                    .get_mut_vehicle(vehicle_id)
-                    .expect("Failed to get mut vehicle");
-                vehicle.get_var_last_position()
-            };
+            let last_pos = last.get_var_last_position();
             if current_pos != last_pos {
                 let event = VehicleMoveEvent::from((
                     vehicle_id,
@@ -659,19 +837,258 @@ pub unsafe extern "C" fn on_vehicle_update(vehicle_id: i32, update_type: i32) {
                 if !move_res {
                     let _ = vcmp_func().set_vehicle_position(
                         vehicle_id,
-                        {
-                            // fix object clone bug
-                            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                            let vehicle = pool
-                                .get_mut_vehicle(vehicle_id)
-                                .expect("Failed to get mut vehicle");
-                            vehicle.get_var_last_position()
-                        },
+                        // fix object clone bug
+                        fetch_vehicle(vehicle_id).get_var_last_position(),
                         Some(false),
                     );
                 }
             }
         }
+        // 以下是可选的追踪，只有注册了对应事件才会去查询，跟玩家的一样
+        let (track_damage, track_tyres, track_parts, track_lights, track_siren, track_locked) = {
+            let storage = PY_CALLBACK_STORAGE
+                .lock()
+                .expect("PyCallbackStorage lock failed");
+            (
+                storage.has_handlers(VcmpEventType::VehicleDamageChange),
+                storage.has_handlers(VcmpEventType::VehicleTyrePop),
+                storage.has_handlers(VcmpEventType::VehiclePartStatusChange),
+                storage.has_handlers(VcmpEventType::VehicleLightsDataChange),
+                storage.has_handlers(VcmpEventType::VehicleSirenChange),
+                storage.has_handlers(VcmpEventType::VehicleDoorsLockedChange),
+            )
+        };
+        {
+            // damage change
+            let last_damage = last.get_var_last_damage();
+            if track_damage {
+                let current_damage = vcmp_func().get_vehicle_damage_data(vehicle_id);
+                match last_damage {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_damage(Some(current_damage))
+                    }),
+                    Some(last_damage) if last_damage != current_damage => {
+                        let event = VehicleDamageChangeEvent::from((
+                            vehicle_id,
+                            last_damage,
+                            current_damage,
+                            update_type,
+                        ));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::VehicleDamageChange(event), true);
+                        if !res
+                            && let Some(damage) = fetch_vehicle(vehicle_id).get_var_last_damage()
+                        {
+                            let _ = vcmp_func().set_vehicle_damage_data(vehicle_id, damage);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_damage.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| vehicle.set_var_last_damage(None));
+            }
+        }
+        {
+            // tyre pop
+            // 只有爆胎才触发事件，修好的轮胎直接记录
+            let last_tyres = last.get_var_last_tyre_status();
+            if track_tyres {
+                let current_tyres = (0..VEHICLE_TYRE_COUNT).fold(0u8, |tyres, tyre| {
+                    if vcmp_func().get_vehicle_tyre_status(vehicle_id, tyre) {
+                        tyres | 1 << tyre
+                    } else {
+                        tyres
+                    }
+                });
+                match last_tyres {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_tyre_status(Some(current_tyres))
+                    }),
+                    Some(last_tyres) if last_tyres != current_tyres => {
+                        let mut tyres = last_tyres;
+                        for tyre in 0..VEHICLE_TYRE_COUNT {
+                            let mask = 1u8 << tyre;
+                            if (last_tyres ^ current_tyres) & mask == 0 {
+                                continue;
+                            }
+                            if current_tyres & mask != 0 {
+                                let event =
+                                    VehicleTyrePopEvent::from((vehicle_id, tyre, update_type));
+                                let res = PY_CALLBACK_MANAGER
+                                    .handle(VcmpEvent::VehicleTyrePop(event), true);
+                                if !res {
+                                    let _ =
+                                        vcmp_func().set_vehicle_tyre_status(vehicle_id, tyre, 0);
+                                    continue;
+                                }
+                            }
+                            tyres ^= mask;
+                        }
+                        update_vehicle_var(vehicle_id, |vehicle| {
+                            vehicle.set_var_last_tyre_status(Some(tyres))
+                        });
+                    }
+                    _ => {}
+                }
+            } else if last_tyres.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| vehicle.set_var_last_tyre_status(None));
+            }
+        }
+        {
+            // part status change
+            let last_parts = last.get_var_last_part_status();
+            if track_parts {
+                let current_parts = (0..VEHICLE_PART_COUNT).fold(0u8, |parts, part| {
+                    if vcmp_func().get_vehicle_part_status(vehicle_id, part) {
+                        parts | 1 << part
+                    } else {
+                        parts
+                    }
+                });
+                match last_parts {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_part_status(Some(current_parts))
+                    }),
+                    Some(last_parts) if last_parts != current_parts => {
+                        let mut parts = last_parts;
+                        for part in 0..VEHICLE_PART_COUNT {
+                            let mask = 1u8 << part;
+                            if (last_parts ^ current_parts) & mask == 0 {
+                                continue;
+                            }
+                            let old_status = last_parts & mask != 0;
+                            let event = VehiclePartStatusChangeEvent::from((
+                                vehicle_id,
+                                part,
+                                old_status,
+                                !old_status,
+                                update_type,
+                            ));
+                            let res = PY_CALLBACK_MANAGER
+                                .handle(VcmpEvent::VehiclePartStatusChange(event), true);
+                            if !res {
+                                let _ = vcmp_func().set_vehicle_part_status(
+                                    vehicle_id,
+                                    part,
+                                    old_status as i32,
+                                );
+                                continue;
+                            }
+                            parts ^= mask;
+                        }
+                        update_vehicle_var(vehicle_id, |vehicle| {
+                            vehicle.set_var_last_part_status(Some(parts))
+                        });
+                    }
+                    _ => {}
+                }
+            } else if last_parts.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| vehicle.set_var_last_part_status(None));
+            }
+        }
+        {
+            // lights data change
+            let last_lights_data = last.get_var_last_lights_data();
+            if track_lights {
+                let current_lights_data = vcmp_func().get_vehicle_lights_data(vehicle_id);
+                match last_lights_data {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_lights_data(Some(current_lights_data))
+                    }),
+                    Some(last_lights_data) if last_lights_data != current_lights_data => {
+                        let event = VehicleLightsDataChangeEvent::from((
+                            vehicle_id,
+                            last_lights_data,
+                            current_lights_data,
+                            update_type,
+                        ));
+                        let res = PY_CALLBACK_MANAGER
+                            .handle(VcmpEvent::VehicleLightsDataChange(event), true);
+                        if !res
+                            && let Some(lights_data) =
+                                fetch_vehicle(vehicle_id).get_var_last_lights_data()
+                        {
+                            let _ = vcmp_func().set_vehicle_lights_data(vehicle_id, lights_data);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_lights_data.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| vehicle.set_var_last_lights_data(None));
+            }
+        }
+        {
+            // siren change
+            let last_siren = last.get_var_last_siren();
+            if track_siren {
+                let current_siren = vcmp_func().get_vehicle_option_siren(vehicle_id);
+                match last_siren {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_siren(Some(current_siren))
+                    }),
+                    Some(last_siren) if last_siren != current_siren => {
+                        let event = VehicleSirenChangeEvent::from((
+                            vehicle_id,
+                            last_siren,
+                            current_siren,
+                            update_type,
+                        ));
+                        let res =
+                            PY_CALLBACK_MANAGER.handle(VcmpEvent::VehicleSirenChange(event), true);
+                        if !res {
+                            update_vehicle_var(vehicle_id, |vehicle| {
+                                vehicle.set_var_last_siren(Some(last_siren))
+                            });
+                        }
+                        if let Some(siren) = fetch_vehicle(vehicle_id).get_var_last_siren()
+                            && siren != current_siren
+                        {
+                            let _ = vcmp_func().set_vehicle_option_siren(vehicle_id, siren);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_siren.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| vehicle.set_var_last_siren(None));
+            }
+        }
+        {
+            // doors locked change
+            let last_locked = last.get_var_last_doors_locked();
+            if track_locked {
+                let current_locked = vcmp_func().get_vehicle_option_doors_locked(vehicle_id);
+                match last_locked {
+                    None => update_vehicle_var(vehicle_id, |vehicle| {
+                        vehicle.set_var_last_doors_locked(Some(current_locked))
+                    }),
+                    Some(last_locked) if last_locked != current_locked => {
+                        let event = VehicleDoorsLockedChangeEvent::from((
+                            vehicle_id,
+                            last_locked,
+                            current_locked,
+                            update_type,
+                        ));
+                        let res = PY_CALLBACK_MANAGER
+                            .handle(VcmpEvent::VehicleDoorsLockedChange(event), true);
+                        if !res {
+                            update_vehicle_var(vehicle_id, |vehicle| {
+                                vehicle.set_var_last_doors_locked(Some(last_locked))
+                            });
+                        }
+                        if let Some(locked) = fetch_vehicle(vehicle_id).get_var_last_doors_locked()
+                            && locked != current_locked
+                        {
+                            let _ = vcmp_func().set_vehicle_option_doors_locked(vehicle_id, locked);
+                        }
+                    }
+                    _ => {}
+                }
+            } else if last_locked.is_some() {
+                update_vehicle_var(vehicle_id, |vehicle| {
+                    vehicle.set_var_last_doors_locked(None)
+                });
+            }
+        }
     }
     let binding_event = vehicle::VehicleUpdateEvent::from((vehicle_id, update_type));
     let _ = PY_CALLBACK_MANAGER.handle(
@@ -679,12 +1096,13 @@ pub unsafe extern "C" fn on_vehicle_update(vehicle_id: i32, update_type: i32) {
         false,
     );
 
+    attachment::update_vehicle_attachments(vehicle_id);
+
     {
-        let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        let vehicle = pool
-            .get_mut_vehicle(vehicle_id)
-            .expect("Failed to get mut vehicle");
-        vehicle.set_var_updating(false);
+        let mut pool = write_pool();
+        if let Some(vehicle) = pool.get_mut_vehicle(vehicle_id) {
+            vehicle.set_var_updating(false);
+        }
     }
 }
 
@@ -703,6 +1121,7 @@ pub unsafe extern "C" fn on_vehicle_explode(vehicle_id: i32) {
 /// FFI callback for vehicle respawn
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn on_vehicle_respawn(vehicle_id: i32) {
+    update_vehicle_var(vehicle_id, VehiclePy::reset_var_last_status);
     let binding_event = vehicle::VehicleRespawnEvent::from(vehicle_id);
     let _ = PY_CALLBACK_MANAGER.handle(
         VcmpEvent::VehicleRespawn(VehicleRespawnEvent::from(binding_event)),
@@ -1,15 +1,52 @@
-use std::{collections::HashMap, path::Path, sync::OnceLock};
+use std::{collections::HashMap, path::Path, sync::OnceLock, time::Duration};
 
 use serde::Deserialize;
 use tracing::Level;
 use vcmp_bindings::encodes::decode_gbk;
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct ScriptConfig {
     pub script_path: String,
+    /// 在 script_path 之后按顺序加载的其他入口
+    #[serde(default)]
+    pub scripts: Vec<String>,
+    /// 只收这些 world 的事件的入口，key 跟 script_path / scripts 里的写法一样
+    #[serde(default)]
+    pub worlds: HashMap<String, Vec<i32>>,
     pub virtual_env: String,
     #[serde(default)]
     pub preloader: bool,
+    /// 启动的时候加载的 class 列表（toml）
+    #[serde(default)]
+    pub class_file: String,
+    /// 脚本目录有改动就自动重载
+    #[serde(default)]
+    pub watch: bool,
+    /// 改动停下来多久之后才重载（毫秒）
+    #[serde(default = "default_watch_debounce")]
+    pub watch_debounce: u64,
+    /// 重载之前先把新脚本 import 一遍，失败就不重载
+    #[serde(default)]
+    pub reload_dry_run: bool,
+}
+fn default_watch_debounce() -> u64 {
+    500
+}
+
+impl Default for ScriptConfig {
+    fn default() -> Self {
+        Self {
+            script_path: String::new(),
+            scripts: Vec::new(),
+            worlds: HashMap::new(),
+            virtual_env: String::new(),
+            preloader: false,
+            class_file: String::new(),
+            watch: false,
+            watch_debounce: default_watch_debounce(),
+            reload_dry_run: false,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -146,11 +183,48 @@ fn init_config_from_cfg() -> Config {
         .get("python_script_path")
         .unwrap_or(&"".to_string())
         .to_string();
+    config.script.scripts = dictionary
+        .get("python_scripts")
+        .unwrap_or(&"".to_string())
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    // race.py=2,deathmatch=3 4
+    config.script.worlds = dictionary
+        .get("python_script_worlds")
+        .unwrap_or(&"".to_string())
+        .split(',')
+        .filter_map(|s| {
+            let (entry, worlds) = s.rsplit_once('=')?;
+            let worlds = worlds
+                .split_whitespace()
+                .filter_map(|w| w.parse().ok())
+                .collect();
+            Some((entry.trim().to_string(), worlds))
+        })
+        .collect();
     config.script.virtual_env = dictionary
         .get("python_virtual_env")
         .unwrap_or(&"".to_string())
         .to_string();
 
+    config.script.class_file = dictionary
+        .get("python_class_file")
+        .unwrap_or(&"".to_string())
+        .to_string();
+
+    config.script.watch = parse_bool(dictionary.get("python_watch").unwrap_or(&"0".to_string()));
+    config.script.watch_debounce = dictionary
+        .get("python_watch_debounce")
+        .and_then(|s| s.trim().parse().ok())
+        .unwrap_or(default_watch_debounce());
+    config.script.reload_dry_run = parse_bool(
+        dictionary
+            .get("python_reload_dry_run")
+            .unwrap_or(&"0".to_string()),
+    );
+
     config.logger.log_level = LogLevel::from_str(
         dictionary
             .get("python_log_level")
@@ -193,12 +267,41 @@ pub fn get_config() -> &'static Config {
 pub fn get_script_path() -> String {
     get_config().script.script_path.clone()
 }
+/// 所有脚本入口，script_path 在最前面
+pub fn get_script_entries() -> Vec<String> {
+    let script = &get_config().script;
+    std::iter::once(&script.script_path)
+        .chain(script.scripts.iter())
+        .filter(|s| !s.trim().is_empty())
+        .cloned()
+        .collect()
+}
+/// 每个入口限定的 world，跟 get_script_entries 一一对应，没配置的是 None
+pub fn get_script_worlds() -> Vec<Option<Vec<i32>>> {
+    let worlds = &get_config().script.worlds;
+    get_script_entries()
+        .iter()
+        .map(|entry| worlds.get(entry.trim()).cloned())
+        .collect()
+}
 pub fn get_virtual_env() -> String {
     get_config().script.virtual_env.clone()
 }
 pub fn get_preloader() -> bool {
     get_config().script.preloader
 }
+pub fn get_class_file() -> String {
+    get_config().script.class_file.clone()
+}
+pub fn get_watch() -> bool {
+    get_config().script.watch
+}
+pub fn get_watch_debounce() -> Duration {
+    Duration::from_millis(get_config().script.watch_debounce)
+}
+pub fn get_reload_dry_run() -> bool {
+    get_config().script.reload_dry_run
+}
 pub fn get_log_level() -> Level {
     get_config().logger.log_level.as_level()
 }
@@ -0,0 +1,551 @@
+//! 伤害归属
+//!
+//! 服务器只会在死亡的时候告诉我们凶手，这里用 player update 的数据
+//! 推测每次掉血是谁打的，并且给每条命记一本伤害账，死亡回调里可以拿来算助攻
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use pyo3::{pyclass, pymethods};
+use vcmp_bindings::{
+    func::{PlayerMethods, QueryVehicle},
+    utils::Vectorf32,
+    vcmp_func,
+};
+
+use crate::{
+    functions::player::PlayerPy,
+    pool::{fetch_player, read_pool},
+};
+
+/// 找不到凶器的时候用的武器 id
+pub const UNKNOWN_WEAPON: i32 = -1;
+/// 车辆撞击
+pub const VEHICLE_WEAPON: i32 = 39;
+/// 空手
+const FISTS_WEAPON: i32 = 0;
+/// 玩家动作里的攻击（挥拳也是这个）
+pub const ATTACK_ACTION: i32 = 16;
+
+/// 开枪之后多久内掉的血算到这一枪上
+const SHOT_WINDOW: Duration = Duration::from_millis(1500);
+/// 爆炸物飞行 + 引信时间比较长
+const EXPLOSIVE_WINDOW: Duration = Duration::from_secs(5);
+/// 枪械的最大归属距离
+const SHOT_RANGE: f32 = 150.0;
+/// 近战的最大归属距离
+const MELEE_RANGE: f32 = 3.0;
+/// 车辆撞人的最大归属距离
+const VEHICLE_HIT_RANGE: f32 = 6.0;
+
+/// 手雷、燃烧瓶、火箭筒之类的
+fn is_explosive(weapon: i32) -> bool {
+    matches!(weapon, 12 | 13 | 15 | 16 | 30 | 34 | 35)
+}
+
+/// 指虎到电锯，空手要看有没有出拳，见 [`Suspect::is_melee`]
+fn is_melee(weapon: i32) -> bool {
+    (1..=11).contains(&weapon)
+}
+
+fn distance(a: Vectorf32, b: Vectorf32) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+fn unix_time() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+/// 伤害账里的一条记录
+#[pyclass]
+#[pyo3(name = "DamageRecord")]
+#[derive(Debug, Clone, Copy)]
+pub struct DamageRecord {
+    pub attacker_id: Option<i32>,
+    pub weapon: i32,
+    pub amount: f32,
+    /// unix 时间戳（秒）
+    pub time: f64,
+}
+
+#[pymethods]
+impl DamageRecord {
+    #[getter]
+    fn attacker(&self) -> Option<PlayerPy> {
+        let pool = read_pool();
+        self.attacker_id.and_then(|id| pool.get_player(id).copied())
+    }
+
+    #[getter]
+    fn weapon(&self) -> i32 {
+        self.weapon
+    }
+
+    #[getter]
+    fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    #[getter]
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DamageRecord(attacker={}, weapon={}, amount={}, time={})",
+            self.attacker()
+                .map(|p| format!("{p}"))
+                .unwrap_or("None".to_string()),
+            self.weapon,
+            self.amount,
+            self.time
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ShotRecord {
+    weapon: i32,
+    time: Instant,
+}
+
+/// 可能的凶手，在锁里拷一份出来，锁外面再去查位置武器
+#[derive(Debug, Clone, Copy)]
+struct Suspect {
+    player_id: i32,
+    shot: Option<ShotRecord>,
+    last_attack: Option<Instant>,
+}
+
+impl Suspect {
+    /// 空手只有刚出过拳才算近战，不然站在旁边的人都会被当成凶手
+    fn is_melee(&self, weapon: i32, action: i32, now: Instant) -> bool {
+        if weapon == FISTS_WEAPON {
+            action == ATTACK_ACTION
+                || self
+                    .last_attack
+                    .is_some_and(|time| now.duration_since(time) <= SHOT_WINDOW)
+        } else {
+            is_melee(weapon)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DamageTracker {
+    /// 每个玩家最近一次开枪（弹药减少）的记录
+    shots: HashMap<i32, ShotRecord>,
+    /// 每个玩家最近一次做出攻击动作的时间
+    attacks: HashMap<i32, Instant>,
+    /// 每条命的伤害账，key 是受害者
+    ledger: HashMap<i32, Vec<DamageRecord>>,
+}
+
+impl DamageTracker {
+    pub fn record_shot(&mut self, player_id: i32, weapon: i32) {
+        self.shots.insert(
+            player_id,
+            ShotRecord {
+                weapon,
+                time: Instant::now(),
+            },
+        );
+    }
+
+    pub fn record_attack(&mut self, player_id: i32) {
+        self.attacks.insert(player_id, Instant::now());
+    }
+
+    pub fn record_damage(
+        &mut self,
+        victim_id: i32,
+        attacker_id: Option<i32>,
+        weapon: i32,
+        amount: f32,
+    ) {
+        self.ledger
+            .entry(victim_id)
+            .or_default()
+            .push(DamageRecord {
+                attacker_id,
+                weapon,
+                amount,
+                time: unix_time(),
+            });
+    }
+
+    pub fn get_ledger(&self, victim_id: i32) -> Vec<DamageRecord> {
+        self.ledger.get(&victim_id).cloned().unwrap_or_default()
+    }
+
+    /// 新的一条命，清掉之前的账
+    pub fn clear_ledger(&mut self, victim_id: i32) {
+        self.ledger.remove(&victim_id);
+    }
+
+    /// 玩家离开服务器
+    pub fn forget(&mut self, player_id: i32) {
+        self.shots.remove(&player_id);
+        self.attacks.remove(&player_id);
+        self.ledger.remove(&player_id);
+    }
+
+    /// 按伤害总量从高到低列出助攻者，不包括凶手和受害者自己
+    pub fn get_assists(&self, victim_id: i32, killer_id: Option<i32>, min_amount: f32) -> Vec<i32> {
+        let mut totals: Vec<(i32, f32)> = Vec::new();
+        for record in self.ledger.get(&victim_id).into_iter().flatten() {
+            let Some(attacker_id) = record.attacker_id else {
+                continue;
+            };
+            if attacker_id == victim_id || Some(attacker_id) == killer_id {
+                continue;
+            }
+            match totals.iter_mut().find(|(id, _)| *id == attacker_id) {
+                Some((_, total)) => *total += record.amount,
+                None => totals.push((attacker_id, record.amount)),
+            }
+        }
+        totals.retain(|(_, total)| *total >= min_amount);
+        totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+        totals.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn suspects(&self, player_ids: &[i32]) -> Vec<Suspect> {
+        player_ids
+            .iter()
+            .map(|&player_id| Suspect {
+                player_id,
+                shot: self.shots.get(&player_id).copied(),
+                last_attack: self.attacks.get(&player_id).copied(),
+            })
+            .collect()
+    }
+}
+
+pub static DAMAGE_TRACKER: LazyLock<Mutex<DamageTracker>> =
+    LazyLock::new(|| Mutex::new(DamageTracker::default()));
+
+/// 锁被 panic 污染了也照样用，服务器回调里不能再 panic
+pub fn lock_tracker() -> MutexGuard<'static, DamageTracker> {
+    DAMAGE_TRACKER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 一个可能的凶手查到的位置和武器，查完再交给 [`pick_attacker`]
+#[derive(Debug, Clone, Copy)]
+struct Sighting {
+    suspect: Suspect,
+    /// 开着的车离受害者多远，没在开车（或者跟受害者同一辆车）就是 None
+    vehicle_distance: Option<f32>,
+    /// 离受害者多远，查不到位置就是 None
+    distance: Option<f32>,
+    /// 贴身的时候才查：手上的武器和动作
+    hand: Option<(i32, i32)>,
+}
+
+/// 推测是谁造成了这次掉血
+///
+/// 优先级：车辆撞击 > 最近开过枪的玩家 > 贴身拿着近战武器的玩家
+///
+/// 查询位置武器的时候不拿着实体池和 DAMAGE_TRACKER 的锁
+pub fn find_attacker(victim_id: i32) -> (Option<i32>, i32) {
+    let player_ids = read_pool()
+        .players()
+        .map(|player| player.get_id())
+        .filter(|&player_id| player_id != victim_id)
+        .collect::<Vec<_>>();
+    let suspects = lock_tracker().suspects(&player_ids);
+
+    let Ok(victim_pos) = vcmp_func().get_player_position(victim_id) else {
+        return (None, UNKNOWN_WEAPON);
+    };
+    let victim_vehicle = vcmp_func().get_player_vehicle_id(victim_id);
+
+    let sightings = suspects
+        .into_iter()
+        .map(|suspect| {
+            let player_id = suspect.player_id;
+            let vehicle_id = vcmp_func().get_player_vehicle_id(player_id);
+            let vehicle_distance = (vehicle_id > 0
+                && vehicle_id != victim_vehicle
+                && vcmp_func().get_player_in_vehicle_slot(player_id) == 0)
+                .then(|| distance(vcmp_func().get_vehicle_position(vehicle_id), victim_pos));
+            let distance = vcmp_func()
+                .get_player_position(player_id)
+                .ok()
+                .map(|pos| distance(pos, victim_pos));
+            let hand = distance.filter(|dist| *dist <= MELEE_RANGE).map(|_| {
+                (
+                    vcmp_func().get_player_weapon(player_id),
+                    vcmp_func().get_player_action(player_id),
+                )
+            });
+            Sighting {
+                suspect,
+                vehicle_distance,
+                distance,
+                hand,
+            }
+        })
+        .collect::<Vec<_>>();
+    pick_attacker(&sightings, Instant::now())
+}
+
+/// [`find_attacker`] 查完之后按优先级挑一个，每一类里挑离得最近的
+fn pick_attacker(sightings: &[Sighting], now: Instant) -> (Option<i32>, i32) {
+    let mut vehicle_hit: Option<(i32, f32)> = None;
+    let mut shooter: Option<(i32, i32, f32)> = None;
+    let mut melee: Option<(i32, i32, f32)> = None;
+
+    for sighting in sightings {
+        let suspect = sighting.suspect;
+        let player_id = suspect.player_id;
+
+        if let Some(dist) = sighting.vehicle_distance
+            && dist <= VEHICLE_HIT_RANGE
+            && vehicle_hit.is_none_or(|(_, d)| dist < d)
+        {
+            vehicle_hit = Some((player_id, dist));
+        }
+
+        let Some(dist) = sighting.distance else {
+            continue;
+        };
+
+        if let Some(shot) = suspect.shot {
+            let window = if is_explosive(shot.weapon) {
+                EXPLOSIVE_WINDOW
+            } else {
+                SHOT_WINDOW
+            };
+            if now.duration_since(shot.time) <= window
+                && dist <= SHOT_RANGE
+                && shooter.is_none_or(|(_, _, d)| dist < d)
+            {
+                shooter = Some((player_id, shot.weapon, dist));
+            }
+        }
+
+        if dist > MELEE_RANGE || melee.is_some_and(|(_, _, d)| dist >= d) {
+            continue;
+        }
+        if let Some((weapon, action)) = sighting.hand
+            && suspect.is_melee(weapon, action, now)
+        {
+            melee = Some((player_id, weapon, dist));
+        }
+    }
+
+    if let Some((player_id, _)) = vehicle_hit {
+        (Some(player_id), VEHICLE_WEAPON)
+    } else if let Some((player_id, weapon, _)) = shooter.or(melee) {
+        (Some(player_id), weapon)
+    } else {
+        (None, UNKNOWN_WEAPON)
+    }
+}
+
+/// 给 python 用的，受害者当前这条命的伤害账
+pub fn get_damage_ledger(victim_id: i32) -> Vec<DamageRecord> {
+    lock_tracker().get_ledger(victim_id)
+}
+
+/// 给 python 用的，助攻者列表
+pub fn get_assist_players(
+    victim_id: i32,
+    killer_id: Option<i32>,
+    min_amount: f32,
+) -> Vec<PlayerPy> {
+    let assists = lock_tracker().get_assists(victim_id, killer_id, min_amount);
+    assists.into_iter().map(fetch_player).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suspect(player_id: i32) -> Suspect {
+        Suspect {
+            player_id,
+            shot: None,
+            last_attack: None,
+        }
+    }
+
+    fn sighting(suspect: Suspect, distance: f32) -> Sighting {
+        Sighting {
+            suspect,
+            vehicle_distance: None,
+            distance: Some(distance),
+            hand: None,
+        }
+    }
+
+    fn shot(weapon: i32, time: Instant) -> Option<ShotRecord> {
+        Some(ShotRecord { weapon, time })
+    }
+
+    #[test]
+    fn nobody_nearby_is_unknown() {
+        let now = Instant::now();
+        assert_eq!(pick_attacker(&[], now), (None, UNKNOWN_WEAPON));
+        let far = sighting(suspect(1), MELEE_RANGE + 1.0);
+        assert_eq!(pick_attacker(&[far], now), (None, UNKNOWN_WEAPON));
+    }
+
+    #[test]
+    fn vehicle_hit_beats_shooter() {
+        let now = Instant::now();
+        let shooter = sighting(
+            Suspect {
+                shot: shot(26, now),
+                ..suspect(1)
+            },
+            10.0,
+        );
+        let driver = Sighting {
+            vehicle_distance: Some(4.0),
+            ..sighting(suspect(2), 4.0)
+        };
+        assert_eq!(
+            pick_attacker(&[shooter, driver], now),
+            (Some(2), VEHICLE_WEAPON)
+        );
+
+        // 车离得太远不算撞的
+        let driver = Sighting {
+            vehicle_distance: Some(VEHICLE_HIT_RANGE + 1.0),
+            ..driver
+        };
+        assert_eq!(pick_attacker(&[shooter, driver], now), (Some(1), 26));
+    }
+
+    #[test]
+    fn nearest_recent_shooter_wins() {
+        let start = Instant::now();
+        let now = start + SHOT_WINDOW;
+        let near = sighting(
+            Suspect {
+                shot: shot(26, start),
+                ..suspect(1)
+            },
+            20.0,
+        );
+        let nearer = sighting(
+            Suspect {
+                shot: shot(19, start),
+                ..suspect(2)
+            },
+            5.0,
+        );
+        let out_of_range = sighting(
+            Suspect {
+                shot: shot(28, start),
+                ..suspect(3)
+            },
+            SHOT_RANGE + 1.0,
+        );
+        assert_eq!(
+            pick_attacker(&[near, nearer, out_of_range], now),
+            (Some(2), 19)
+        );
+
+        // 开枪太久以前了，爆炸物的时间窗口长一些
+        let later = start + SHOT_WINDOW * 2;
+        let grenade = sighting(
+            Suspect {
+                shot: shot(12, start),
+                ..suspect(4)
+            },
+            30.0,
+        );
+        assert_eq!(
+            pick_attacker(&[near, nearer, grenade], later),
+            (Some(4), 12)
+        );
+        assert_eq!(
+            pick_attacker(&[near, nearer], later),
+            (None, UNKNOWN_WEAPON)
+        );
+    }
+
+    #[test]
+    fn fists_only_count_after_an_attack() {
+        let start = Instant::now();
+        let bystander = Sighting {
+            hand: Some((FISTS_WEAPON, 0)),
+            ..sighting(suspect(1), 1.0)
+        };
+        assert_eq!(pick_attacker(&[bystander], start), (None, UNKNOWN_WEAPON));
+
+        let punching = Sighting {
+            hand: Some((FISTS_WEAPON, ATTACK_ACTION)),
+            ..bystander
+        };
+        assert_eq!(pick_attacker(&[punching], start), (Some(1), FISTS_WEAPON));
+
+        let punched = Sighting {
+            suspect: Suspect {
+                last_attack: Some(start),
+                ..suspect(1)
+            },
+            ..bystander
+        };
+        assert_eq!(pick_attacker(&[punched], start), (Some(1), FISTS_WEAPON));
+        let now = start + SHOT_WINDOW * 2;
+        assert_eq!(pick_attacker(&[punched], now), (None, UNKNOWN_WEAPON));
+    }
+
+    #[test]
+    fn shooter_beats_nearest_melee() {
+        let now = Instant::now();
+        let knife = Sighting {
+            hand: Some((5, 0)),
+            ..sighting(suspect(1), 1.0)
+        };
+        let bat = Sighting {
+            hand: Some((6, 0)),
+            ..sighting(suspect(2), 2.0)
+        };
+        assert_eq!(pick_attacker(&[bat, knife], now), (Some(1), 5));
+
+        let shooter = sighting(
+            Suspect {
+                shot: shot(17, now),
+                ..suspect(3)
+            },
+            15.0,
+        );
+        assert_eq!(pick_attacker(&[bat, knife, shooter], now), (Some(3), 17));
+    }
+
+    #[test]
+    fn assists_are_summed_and_sorted() {
+        let mut tracker = DamageTracker::default();
+        tracker.record_damage(1, Some(2), 26, 20.0);
+        tracker.record_damage(1, Some(3), 19, 15.0);
+        tracker.record_damage(1, Some(2), 26, 10.0);
+        tracker.record_damage(1, Some(4), 17, 40.0);
+        tracker.record_damage(1, Some(5), 17, 5.0);
+        tracker.record_damage(1, None, UNKNOWN_WEAPON, 50.0);
+        tracker.record_damage(1, Some(1), 15, 30.0);
+        tracker.record_damage(6, Some(3), 19, 100.0);
+
+        // 4 是凶手，1 是自己，5 不够
+        assert_eq!(tracker.get_assists(1, Some(4), 10.0), vec![2, 3]);
+        assert_eq!(tracker.get_assists(1, None, 0.0), vec![4, 2, 3, 5]);
+        assert!(tracker.get_assists(7, None, 0.0).is_empty());
+
+        tracker.clear_ledger(1);
+        assert!(tracker.get_assists(1, None, 0.0).is_empty());
+        assert_eq!(tracker.get_assists(6, None, 0.0), vec![3]);
+    }
+}
@@ -4,16 +4,21 @@ use pyo3::{Bound, PyResult, Python};
 
 use crate::py::fix_module_name;
 
+pub mod animation;
+pub mod attachment;
 pub mod checkpoint;
 pub mod environment;
+pub mod handling;
 pub mod keybind;
 pub mod marker;
 pub mod misc;
 pub mod object;
 pub mod pickup;
 pub mod player;
+pub mod player_class;
 pub mod server;
 pub mod vehicle;
+pub mod weapon;
 
 pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     let server_module = PyModule::new(py, "server")?;
@@ -31,6 +36,11 @@ pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     fix_module_name(py, &vehicle_module, "functions.vehicle");
     m.add_submodule(&vehicle_module)?;
 
+    let handling_module = PyModule::new(py, "handling")?;
+    handling::module_define(py, &handling_module)?;
+    fix_module_name(py, &handling_module, "functions.handling");
+    m.add_submodule(&handling_module)?;
+
     let environment_module = PyModule::new(py, "environment")?;
     environment::module_define(py, &environment_module)?;
     fix_module_name(py, &environment_module, "functions.environment");
@@ -61,6 +71,16 @@ pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     fix_module_name(py, &keybind_module, "functions.keybind");
     m.add_submodule(&keybind_module)?;
 
+    let animation_module = PyModule::new(py, "animation")?;
+    animation::module_define(py, &animation_module)?;
+    fix_module_name(py, &animation_module, "functions.animation");
+    m.add_submodule(&animation_module)?;
+
+    let weapon_module = PyModule::new(py, "weapon")?;
+    weapon::module_define(py, &weapon_module)?;
+    fix_module_name(py, &weapon_module, "functions.weapon");
+    m.add_submodule(&weapon_module)?;
+
     let misc_module = PyModule::new(py, "misc")?;
     misc::module_define(py, &misc_module)?;
     fix_module_name(py, &misc_module, "functions.misc");
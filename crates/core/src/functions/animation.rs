@@ -0,0 +1,620 @@
+//! 物体动画
+//!
+//! 每个物体一个动画队列，在 server frame 里推进。
+//! 非线性的缓动会被切成很多小段交给 move_object_to / rotate_object_to，
+//! 客户端在每一小段里自己插值，所以不需要每帧都发包。
+
+use std::{
+    collections::{HashMap, VecDeque},
+    f32::consts::PI,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use pyo3::{
+    Bound, PyRefMut, PyResult, Python, pyclass, pymethods,
+    types::{PyModule, PyModuleMethods},
+};
+use vcmp_bindings::{
+    func::ObjectMethods,
+    utils::{Quaternionf32, Vectorf32},
+    vcmp_func,
+};
+
+use crate::py::{
+    callbacks::PY_CALLBACK_MANAGER,
+    events::{VcmpEvent, object::ObjectAnimationFinishedEvent},
+    types::{QuaternionPy, VectorPy, quat_from_euler, quat_mul, quat_normalize, quat_slerp},
+};
+
+/// 缓动曲线每一小段的时长
+const SEGMENT: Duration = Duration::from_millis(100);
+
+#[pyclass(eq, eq_int)]
+#[pyo3(name = "Easing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    BackOut,
+    BounceOut,
+    ElasticOut,
+}
+
+impl Easing {
+    /// t 在 0 到 1 之间
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut => (t * PI / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::BackOut => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+            Easing::BounceOut => {
+                let n1 = 7.5625;
+                let d1 = 2.75;
+                if t < 1.0 / d1 {
+                    n1 * t * t
+                } else if t < 2.0 / d1 {
+                    let t = t - 1.5 / d1;
+                    n1 * t * t + 0.75
+                } else if t < 2.5 / d1 {
+                    let t = t - 2.25 / d1;
+                    n1 * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / d1;
+                    n1 * t * t + 0.984375
+                }
+            }
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Easing {
+    /// 给 python 用的，方便自己算曲线
+    #[pyo3(name = "apply")]
+    fn py_apply(&self, t: f32) -> f32 {
+        self.apply(t)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Target<T> {
+    To(T),
+    By(T),
+}
+
+/// 一个关键帧，位置和旋转都没有的就是等待
+#[derive(Debug, Clone, Copy)]
+struct AnimationStep {
+    position: Option<Target<Vectorf32>>,
+    rotation: Option<Target<Quaternionf32>>,
+    duration: Duration,
+    easing: Easing,
+}
+
+#[pyclass]
+#[pyo3(name = "ObjectAnimation")]
+#[derive(Debug, Clone, Default)]
+pub struct ObjectAnimationPy {
+    steps: Vec<AnimationStep>,
+    /// 0 表示无限循环
+    loops: u32,
+    /// 播完之后倒着播回去，一来一回算一次
+    ping_pong: bool,
+    tag: Option<String>,
+}
+
+impl ObjectAnimationPy {
+    fn push(
+        &mut self,
+        position: Option<Target<Vectorf32>>,
+        rotation: Option<Target<Quaternionf32>>,
+        duration: u32,
+        easing: Easing,
+    ) {
+        self.steps.push(AnimationStep {
+            position,
+            rotation,
+            duration: Duration::from_millis(duration as u64),
+            easing,
+        });
+    }
+}
+
+#[pymethods]
+impl ObjectAnimationPy {
+    #[new]
+    #[pyo3(signature = (loops = 1, ping_pong = false, tag = None))]
+    fn new(loops: u32, ping_pong: bool, tag: Option<String>) -> Self {
+        Self {
+            steps: Vec::new(),
+            loops,
+            ping_pong,
+            tag,
+        }
+    }
+
+    #[getter]
+    fn get_loops(&self) -> u32 {
+        self.loops
+    }
+
+    #[getter]
+    fn get_ping_pong(&self) -> bool {
+        self.ping_pong
+    }
+
+    #[getter]
+    fn get_tag(&self) -> Option<String> {
+        self.tag.clone()
+    }
+
+    /// 一次循环的总时长（毫秒），ping pong 的话是来回的总时长
+    #[getter]
+    fn get_duration(&self) -> u128 {
+        let total: Duration = self.steps.iter().map(|step| step.duration).sum();
+        let total = total.as_millis();
+        if self.ping_pong { total * 2 } else { total }
+    }
+
+    fn __len__(&self) -> usize {
+        self.steps.len()
+    }
+
+    #[pyo3(signature = (position, duration, easing = Easing::Linear))]
+    fn move_to(
+        mut slf: PyRefMut<'_, Self>,
+        position: VectorPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            Some(Target::To(position.get_entity_pos())),
+            None,
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    #[pyo3(signature = (offset, duration, easing = Easing::Linear))]
+    fn move_by(
+        mut slf: PyRefMut<'_, Self>,
+        offset: VectorPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            Some(Target::By(offset.get_entity_pos())),
+            None,
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    #[pyo3(signature = (rotation, duration, easing = Easing::Linear))]
+    fn rotate_to(
+        mut slf: PyRefMut<'_, Self>,
+        rotation: QuaternionPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            None,
+            Some(Target::To(rotation.get_entity_quaternion())),
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    /// 相对于当前朝向再转一个 rotation
+    #[pyo3(signature = (rotation, duration, easing = Easing::Linear))]
+    fn rotate_by(
+        mut slf: PyRefMut<'_, Self>,
+        rotation: QuaternionPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            None,
+            Some(Target::By(rotation.get_entity_quaternion())),
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    #[pyo3(signature = (euler, duration, easing = Easing::Linear))]
+    fn rotate_to_euler(
+        mut slf: PyRefMut<'_, Self>,
+        euler: VectorPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            None,
+            Some(Target::To(quat_from_euler(euler.get_entity_pos()))),
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    #[pyo3(signature = (euler, duration, easing = Easing::Linear))]
+    fn rotate_by_euler(
+        mut slf: PyRefMut<'_, Self>,
+        euler: VectorPy,
+        duration: u32,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            None,
+            Some(Target::By(quat_from_euler(euler.get_entity_pos()))),
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    /// 同时移动和旋转到指定的位置
+    #[pyo3(signature = (duration, position = None, rotation = None, easing = Easing::Linear))]
+    fn keyframe(
+        mut slf: PyRefMut<'_, Self>,
+        duration: u32,
+        position: Option<VectorPy>,
+        rotation: Option<QuaternionPy>,
+        easing: Easing,
+    ) -> PyRefMut<'_, Self> {
+        slf.push(
+            position.map(|p| Target::To(p.get_entity_pos())),
+            rotation.map(|r| Target::To(r.get_entity_quaternion())),
+            duration,
+            easing,
+        );
+        slf
+    }
+
+    /// 原地等待
+    fn wait(mut slf: PyRefMut<'_, Self>, duration: u32) -> PyRefMut<'_, Self> {
+        slf.push(None, None, duration, Easing::Linear);
+        slf
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ObjectAnimation(steps={}, loops={}, ping_pong={}, tag={:?})",
+            self.steps.len(),
+            self.loops,
+            self.ping_pong,
+            self.tag
+        )
+    }
+}
+
+/// 已经算好起点和终点的一步
+#[derive(Debug, Clone, Copy)]
+struct ResolvedStep {
+    from_pos: Vectorf32,
+    to_pos: Vectorf32,
+    from_rot: Quaternionf32,
+    to_rot: Quaternionf32,
+    moves: bool,
+    rotates: bool,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl ResolvedStep {
+    fn reversed(&self) -> Self {
+        Self {
+            from_pos: self.to_pos,
+            to_pos: self.from_pos,
+            from_rot: self.to_rot,
+            to_rot: self.from_rot,
+            ..*self
+        }
+    }
+
+    /// t 时刻的位置和旋转，走到头直接用终点，免得浮点误差让物体停在终点旁边
+    fn sample(&self, segment_end: Duration) -> (Vectorf32, Quaternionf32) {
+        if segment_end >= self.duration {
+            return (self.to_pos, self.to_rot);
+        }
+        let t = self
+            .easing
+            .apply(segment_end.as_secs_f32() / self.duration.as_secs_f32());
+        let pos = Vectorf32::new(
+            self.from_pos.x + (self.to_pos.x - self.from_pos.x) * t,
+            self.from_pos.y + (self.to_pos.y - self.from_pos.y) * t,
+            self.from_pos.z + (self.to_pos.z - self.from_pos.z) * t,
+        );
+        (pos, quat_slerp(self.from_rot, self.to_rot, t))
+    }
+
+    /// 发出从 elapsed 到 segment_end 的这一小段
+    fn send_segment(&self, object_id: i32, elapsed: Duration, segment_end: Duration) {
+        let (pos, rot) = self.sample(segment_end);
+        let time = segment_end.saturating_sub(elapsed).as_millis() as u32;
+        if self.moves {
+            let _ = vcmp_func().move_object_to(object_id, pos, time);
+        }
+        if self.rotates {
+            let _ = vcmp_func().rotate_object_to(object_id, rot, time);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ObjectAnimator {
+    animation: ObjectAnimationPy,
+    /// 当前这一趟要走的步骤
+    pass: Vec<ResolvedStep>,
+    /// 正向那一趟，ping pong 回来的时候用
+    forward: Vec<ResolvedStep>,
+    reversing: bool,
+    index: usize,
+    step_started: Instant,
+    /// 当前这一小段结束的时间（相对于 step_started）
+    segment_end: Option<Duration>,
+    loops_done: u32,
+}
+
+impl ObjectAnimator {
+    fn new(object_id: i32, animation: ObjectAnimationPy, now: Instant) -> Self {
+        let forward = resolve(object_id, &animation.steps);
+        Self {
+            animation,
+            pass: forward.clone(),
+            forward,
+            reversing: false,
+            index: 0,
+            step_started: now,
+            segment_end: None,
+            loops_done: 0,
+        }
+    }
+
+    /// 开始下一趟，返回 false 表示整个动画播完了
+    fn next_pass(&mut self, object_id: i32) -> bool {
+        if self.animation.ping_pong && !self.reversing {
+            self.reversing = true;
+            self.pass = self.forward.iter().rev().map(|s| s.reversed()).collect();
+        } else {
+            self.loops_done += 1;
+            if self.animation.loops != 0 && self.loops_done >= self.animation.loops {
+                return false;
+            }
+            self.reversing = false;
+            // 每一趟重新算，move_by / rotate_by 会在上一趟的基础上继续叠加
+            self.forward = resolve(object_id, &self.animation.steps);
+            self.pass = self.forward.clone();
+        }
+        self.index = 0;
+        self.segment_end = None;
+        true
+    }
+
+    /// 推进动画，返回 true 表示播完了
+    fn tick(&mut self, object_id: i32, now: Instant) -> bool {
+        // 防止全是 0 时长的无限循环卡死
+        let mut budget = self.forward.len() * 2 + 2;
+        loop {
+            let Some(step) = self.pass.get(self.index).copied() else {
+                if !self.next_pass(object_id) {
+                    return true;
+                }
+                if self.pass.is_empty() || budget == 0 {
+                    return false;
+                }
+                budget -= 1;
+                continue;
+            };
+            let elapsed = now.saturating_duration_since(self.step_started);
+
+            if self.segment_end.is_none_or(|end| elapsed >= end) && elapsed < step.duration {
+                // 线性的直接一段走完，交给客户端插值
+                let segment_end = if step.easing == Easing::Linear {
+                    step.duration
+                } else {
+                    (elapsed + SEGMENT).min(step.duration)
+                };
+                step.send_segment(object_id, elapsed, segment_end);
+                self.segment_end = Some(segment_end);
+            }
+
+            if elapsed < step.duration {
+                return false;
+            }
+            // 不管之前发了几段，结束的时候都把物体放到准确的终点
+            step.send_segment(object_id, elapsed, elapsed);
+            self.step_started += step.duration;
+            self.index += 1;
+            self.segment_end = None;
+            if budget == 0 {
+                return false;
+            }
+            budget -= 1;
+        }
+    }
+}
+
+/// 从物体当前的位置开始，把 to / by 都换成绝对的起点终点
+fn resolve(object_id: i32, steps: &[AnimationStep]) -> Vec<ResolvedStep> {
+    let mut pos = vcmp_func()
+        .get_object_position(object_id)
+        .unwrap_or_default();
+    let mut rot = vcmp_func()
+        .get_object_rotation(object_id)
+        .unwrap_or_default();
+    steps
+        .iter()
+        .map(|step| {
+            let from_pos = pos;
+            let from_rot = rot;
+            match step.position {
+                Some(Target::To(target)) => pos = target,
+                Some(Target::By(offset)) => {
+                    pos = Vectorf32::new(pos.x + offset.x, pos.y + offset.y, pos.z + offset.z)
+                }
+                None => {}
+            }
+            match step.rotation {
+                Some(Target::To(target)) => rot = quat_normalize(target),
+                Some(Target::By(delta)) => rot = quat_normalize(quat_mul(rot, delta)),
+                None => {}
+            }
+            ResolvedStep {
+                from_pos,
+                to_pos: pos,
+                from_rot,
+                to_rot: rot,
+                moves: step.position.is_some(),
+                rotates: step.rotation.is_some(),
+                duration: step.duration,
+                easing: step.easing,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct AnimationSlot {
+    current: Option<ObjectAnimator>,
+    queue: VecDeque<ObjectAnimationPy>,
+}
+
+static OBJECT_ANIMATIONS: LazyLock<Mutex<HashMap<i32, AnimationSlot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 播放动画，queue 为 true 的时候排在当前动画后面
+pub fn play_animation(object_id: i32, animation: ObjectAnimationPy, queue: bool) {
+    let mut animations = OBJECT_ANIMATIONS
+        .lock()
+        .expect("Failed to lock object animations");
+    let slot = animations.entry(object_id).or_default();
+    if queue && slot.current.is_some() {
+        slot.queue.push_back(animation);
+    } else {
+        slot.queue.clear();
+        slot.current = Some(ObjectAnimator::new(object_id, animation, Instant::now()));
+    }
+}
+
+/// 停止动画并清空队列，物体停在当前位置（不会触发结束事件）
+pub fn stop_animation(object_id: i32) -> bool {
+    let removed = OBJECT_ANIMATIONS
+        .lock()
+        .expect("Failed to lock object animations")
+        .remove(&object_id);
+    let Some(slot) = removed else {
+        return false;
+    };
+    if slot.current.is_some() {
+        if let Ok(pos) = vcmp_func().get_object_position(object_id) {
+            let _ = vcmp_func().move_object_to(object_id, pos, 0);
+        }
+        if let Ok(rot) = vcmp_func().get_object_rotation(object_id) {
+            let _ = vcmp_func().rotate_object_to(object_id, rot, 0);
+        }
+    }
+    true
+}
+
+pub fn is_animating(object_id: i32) -> bool {
+    OBJECT_ANIMATIONS
+        .lock()
+        .expect("Failed to lock object animations")
+        .get(&object_id)
+        .is_some_and(|slot| slot.current.is_some())
+}
+
+/// 物体被删除的时候清理
+pub fn forget_object(object_id: i32) {
+    OBJECT_ANIMATIONS
+        .lock()
+        .expect("Failed to lock object animations")
+        .remove(&object_id);
+}
+
+/// 在 server frame 里调用
+pub fn tick_animations() {
+    let now = Instant::now();
+    let mut finished = Vec::new();
+    {
+        let mut animations = OBJECT_ANIMATIONS
+            .lock()
+            .expect("Failed to lock object animations");
+        if animations.is_empty() {
+            return;
+        }
+        for (&object_id, slot) in animations.iter_mut() {
+            let Some(animator) = slot.current.as_mut() else {
+                continue;
+            };
+            if !animator.tick(object_id, now) {
+                continue;
+            }
+            finished.push((object_id, animator.animation.tag.clone()));
+            // 接着播队列里的下一个
+            slot.current = slot
+                .queue
+                .pop_front()
+                .map(|next| ObjectAnimator::new(object_id, next, now));
+        }
+        animations.retain(|_, slot| slot.current.is_some());
+    }
+    // 锁放掉之后再回调，回调里可能会继续加动画
+    for (object_id, tag) in finished {
+        let event = ObjectAnimationFinishedEvent::from((object_id, tag));
+        let _ = PY_CALLBACK_MANAGER.handle(VcmpEvent::ObjectAnimationFinished(event), false);
+    }
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Easing>()?;
+    m.add_class::<ObjectAnimationPy>()?;
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! 物体附着
+//!
+//! 服务器没有原生的附着，这里在玩家和载具的 update 里
+//! 按相对偏移把物体挪过去
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use pyo3::{FromPyObject, IntoPyObject};
+use vcmp_bindings::{
+    func::{ObjectMethods, PlayerMethods, QueryVehicle},
+    options::VcmpEntityPool,
+    utils::{Quaternionf32, Vectorf32},
+    vcmp_func,
+};
+
+use crate::{
+    functions::{player::PlayerPy, vehicle::VehiclePy},
+    pool::{fetch_player, fetch_vehicle},
+    py::types::{quat_from_euler, quat_mul, quat_rotate},
+};
+
+/// 可以被附着的实体
+#[derive(Debug, Clone, Copy, FromPyObject, IntoPyObject)]
+pub enum AttachTarget {
+    Player(PlayerPy),
+    Vehicle(VehiclePy),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetId {
+    Player(i32),
+    Vehicle(i32),
+}
+
+impl From<AttachTarget> for TargetId {
+    fn from(value: AttachTarget) -> Self {
+        match value {
+            AttachTarget::Player(player) => TargetId::Player(player.get_id()),
+            AttachTarget::Vehicle(vehicle) => TargetId::Vehicle(vehicle.get_id()),
+        }
+    }
+}
+
+impl TargetId {
+    /// 实体当前的位置和朝向，玩家只有 heading
+    fn transform(self) -> Option<(Vectorf32, Quaternionf32)> {
+        match self {
+            TargetId::Player(id) => {
+                let pos = vcmp_func().get_player_position(id).ok()?;
+                let angle = vcmp_func().get_player_angle(id);
+                Some((pos, quat_from_euler(Vectorf32::new(0.0, 0.0, angle))))
+            }
+            TargetId::Vehicle(id) => Some((
+                vcmp_func().get_vehicle_position(id),
+                vcmp_func().get_vehicle_rotation(id),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Attachment {
+    target: TargetId,
+    offset: Vectorf32,
+    rotation: Quaternionf32,
+}
+
+impl Attachment {
+    fn apply(&self, object_id: i32, pos: Vectorf32, rot: Quaternionf32) {
+        let offset = quat_rotate(rot, self.offset);
+        let _ = vcmp_func().set_object_position(
+            object_id,
+            Vectorf32::new(pos.x + offset.x, pos.y + offset.y, pos.z + offset.z),
+        );
+        let _ = vcmp_func().rotate_object_to(object_id, quat_mul(rot, self.rotation), 0);
+    }
+}
+
+/// key 是物体 id
+static ATTACHMENTS: LazyLock<Mutex<HashMap<i32, Attachment>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 附着到实体上，offset 和 rotation 都是相对于实体的
+pub fn attach(object_id: i32, target: AttachTarget, offset: Vectorf32, rotation: Quaternionf32) {
+    let attachment = Attachment {
+        target: target.into(),
+        offset,
+        rotation,
+    };
+    // 先摆到位置上，不用等下一次 update
+    if let Some((pos, rot)) = attachment.target.transform() {
+        attachment.apply(object_id, pos, rot);
+    }
+    ATTACHMENTS
+        .lock()
+        .expect("Failed to lock object attachments")
+        .insert(object_id, attachment);
+}
+
+pub fn detach(object_id: i32) -> bool {
+    ATTACHMENTS
+        .lock()
+        .expect("Failed to lock object attachments")
+        .remove(&object_id)
+        .is_some()
+}
+
+pub fn get_attached_to(object_id: i32) -> Option<AttachTarget> {
+    let target = ATTACHMENTS
+        .lock()
+        .expect("Failed to lock object attachments")
+        .get(&object_id)?
+        .target;
+    Some(match target {
+        TargetId::Player(id) => AttachTarget::Player(fetch_player(id)),
+        TargetId::Vehicle(id) => AttachTarget::Vehicle(fetch_vehicle(id)),
+    })
+}
+
+fn update_target(target: TargetId) {
+    let attachments = ATTACHMENTS
+        .lock()
+        .expect("Failed to lock object attachments");
+    if !attachments.values().any(|a| a.target == target) {
+        return;
+    }
+    let Some((pos, rot)) = target.transform() else {
+        return;
+    };
+    for (&object_id, attachment) in attachments.iter() {
+        if attachment.target == target {
+            attachment.apply(object_id, pos, rot);
+        }
+    }
+}
+
+/// 在 player update 里调用
+pub fn update_player_attachments(player_id: i32) {
+    update_target(TargetId::Player(player_id));
+}
+
+/// 在 vehicle update 里调用
+pub fn update_vehicle_attachments(vehicle_id: i32) {
+    update_target(TargetId::Vehicle(vehicle_id));
+}
+
+/// 实体被删除的时候清理，物体被删就去掉它的附着，玩家或载具被删就把上面的物体都放下
+pub fn forget_entity(entity_type: &VcmpEntityPool, entity_id: i32) {
+    let mut attachments = ATTACHMENTS
+        .lock()
+        .expect("Failed to lock object attachments");
+    match entity_type {
+        VcmpEntityPool::Object => {
+            attachments.remove(&entity_id);
+        }
+        VcmpEntityPool::Vehicle => {
+            attachments.retain(|_, a| a.target != TargetId::Vehicle(entity_id));
+        }
+        VcmpEntityPool::Player => {
+            attachments.retain(|_, a| a.target != TargetId::Player(entity_id));
+        }
+        _ => {}
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Display, ops::Add};
+use std::fmt::Display;
 
 use pyo3::{
     Bound, PyResult, Python, pyclass, pyfunction, pymethods,
@@ -9,7 +9,7 @@ use vcmp_bindings::{func::CheckPointMethods, vcmp_func};
 
 use crate::{
     functions::player::PlayerPy,
-    pool::{ENTITY_POOL, EntityPoolTrait},
+    pool::{EntityPoolTrait, read_pool},
     py::types::{EntityVectorType, RGBPy, VectorPy},
 };
 
@@ -69,8 +69,8 @@ impl CheckPointPy {
     }
 
     fn add_position(&self, pos: VectorPy) {
-        let origin = self._position();
-        let _ = origin.add(pos);
+        let mut origin = self._position();
+        origin.add_to_entity(pos);
     }
 
     #[getter]
@@ -103,7 +103,7 @@ impl CheckPointPy {
     #[getter]
     fn get_owner(&self) -> Option<PlayerPy> {
         let id = vcmp_func().get_checkpoint_owner(self.id);
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+        let pool = read_pool();
         pool.get_player(id).copied()
     }
 
@@ -166,7 +166,7 @@ pub fn create_checkpoint(
         radius,
     );
 
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
 
     pool.get_checkpoint(id)
         .copied()
@@ -12,7 +12,10 @@ use vcmp_bindings::{
     vcmp_func,
 };
 
-use crate::py::types::{RGBPy, VectorPy, WastedSettingsPy};
+use crate::{
+    functions::player_class,
+    py::types::{VectorPy, WastedSettingsPy},
+};
 
 #[pyfunction]
 pub fn set_taxi_boost_jump(toggle: bool) {
@@ -334,33 +337,6 @@ pub fn set_vehicles_forced_respawn_height(height: f32) {
     vcmp_func().set_vehicles_forced_respawn_height(height);
 }
 
-#[pyfunction]
-#[allow(clippy::too_many_arguments)]
-pub fn add_player_class(
-    team: i32,
-    color: RGBPy,
-    skin: i32,
-    pos: VectorPy,
-    angle: f32,
-    weapon: Option<i32>,
-    ammo: Option<i32>,
-    weapon1: Option<i32>,
-    ammo1: Option<i32>,
-    weapon2: Option<i32>,
-    ammo2: Option<i32>,
-) {
-    vcmp_func().add_player_class(
-        team,
-        color.into(),
-        skin,
-        pos.into(),
-        angle,
-        Some((weapon.unwrap_or_default(), ammo.unwrap_or_default())),
-        Some((weapon1.unwrap_or_default(), ammo1.unwrap_or_default())),
-        Some((weapon2.unwrap_or_default(), ammo2.unwrap_or_default())),
-    );
-}
-
 #[pyfunction]
 pub fn set_spawn_player_position(position: VectorPy) {
     vcmp_func().set_spawn_player_position(position.into());
@@ -410,7 +386,7 @@ pub fn set_wasted_settings(settings: WastedSettingsPy) {
     vcmp_func().set_wasted_settings(settings.into());
 }
 
-pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(set_taxi_boost_jump, m)?)?;
     m.add_function(wrap_pyfunction!(get_taxi_boost_jump, m)?)?;
     m.add_function(wrap_pyfunction!(set_drive_on_water, m)?)?;
@@ -475,7 +451,6 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(enable_kill_command, m)?)?;
     m.add_function(wrap_pyfunction!(get_vehicles_forced_respawn_height, m)?)?;
     m.add_function(wrap_pyfunction!(set_vehicles_forced_respawn_height, m)?)?;
-    m.add_function(wrap_pyfunction!(add_player_class, m)?)?;
     m.add_function(wrap_pyfunction!(set_spawn_player_position, m)?)?;
     m.add_function(wrap_pyfunction!(set_spawn_camera_position, m)?)?;
     m.add_function(wrap_pyfunction!(set_spawn_camera_look_at, m)?)?;
@@ -484,5 +459,6 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(get_fall_timer, m)?)?;
     m.add_function(wrap_pyfunction!(get_wasted_settings, m)?)?;
     m.add_function(wrap_pyfunction!(set_wasted_settings, m)?)?;
+    player_class::module_define(py, m)?;
     Ok(())
 }
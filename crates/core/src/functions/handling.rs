@@ -0,0 +1,357 @@
+//! 载具操控
+//!
+//! 车型级别的操控规则，以及整张操控表的 toml / json 导入导出，
+//! 方便给不同的模式准备调校预设
+
+use std::collections::BTreeMap;
+
+use pyo3::{
+    Bound, FromPyObject, IntoPyObject, PyResult, Python,
+    exceptions::{PyIOError, PyValueError},
+    pyclass, pyfunction, pymethods,
+    types::{PyAnyMethods, PyModule, PyModuleMethods},
+    wrap_pyfunction,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use vcmp_bindings::{func::VehicleHandlingMethods, vcmp_func};
+
+use crate::py::{types::VehicleModelPy, util::VEHICLE_NAMES};
+
+/// 一张操控表，key 是 HandlingRule 的 key
+pub type HandlingTable = BTreeMap<String, f64>;
+
+#[pyclass(eq, eq_int, module = "vcmp.functions.handling")]
+#[pyo3(name = "HandlingRule")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandlingRule {
+    Mass = 1,
+    TurnMass = 2,
+    DragMultiplier = 3,
+    CentreOfMassX = 4,
+    CentreOfMassY = 5,
+    CentreOfMassZ = 6,
+    PercentSubmerged = 7,
+    TractionMultiplier = 8,
+    TractionLoss = 9,
+    TractionBias = 10,
+    NumberOfGears = 11,
+    MaxSpeed = 12,
+    Acceleration = 13,
+    DriveType = 14,
+    EngineType = 15,
+    BrakeDeceleration = 16,
+    BrakeBias = 17,
+    SteeringLock = 18,
+    SuspensionForceLevel = 19,
+    SuspensionDampening = 20,
+    SeatOffset = 21,
+    DamageMultiplier = 22,
+    SuspensionUpperLimit = 23,
+    SuspensionLowerLimit = 24,
+    SuspensionBias = 25,
+    SuspensionAntiDive = 26,
+    Flags = 27,
+    FrontLights = 28,
+    RearLights = 29,
+}
+
+impl HandlingRule {
+    pub const ALL: [HandlingRule; 29] = [
+        HandlingRule::Mass,
+        HandlingRule::TurnMass,
+        HandlingRule::DragMultiplier,
+        HandlingRule::CentreOfMassX,
+        HandlingRule::CentreOfMassY,
+        HandlingRule::CentreOfMassZ,
+        HandlingRule::PercentSubmerged,
+        HandlingRule::TractionMultiplier,
+        HandlingRule::TractionLoss,
+        HandlingRule::TractionBias,
+        HandlingRule::NumberOfGears,
+        HandlingRule::MaxSpeed,
+        HandlingRule::Acceleration,
+        HandlingRule::DriveType,
+        HandlingRule::EngineType,
+        HandlingRule::BrakeDeceleration,
+        HandlingRule::BrakeBias,
+        HandlingRule::SteeringLock,
+        HandlingRule::SuspensionForceLevel,
+        HandlingRule::SuspensionDampening,
+        HandlingRule::SeatOffset,
+        HandlingRule::DamageMultiplier,
+        HandlingRule::SuspensionUpperLimit,
+        HandlingRule::SuspensionLowerLimit,
+        HandlingRule::SuspensionBias,
+        HandlingRule::SuspensionAntiDive,
+        HandlingRule::Flags,
+        HandlingRule::FrontLights,
+        HandlingRule::RearLights,
+    ];
+
+    /// 导入导出时用的名字
+    pub fn key(self) -> &'static str {
+        match self {
+            HandlingRule::Mass => "mass",
+            HandlingRule::TurnMass => "turn_mass",
+            HandlingRule::DragMultiplier => "drag_multiplier",
+            HandlingRule::CentreOfMassX => "centre_of_mass_x",
+            HandlingRule::CentreOfMassY => "centre_of_mass_y",
+            HandlingRule::CentreOfMassZ => "centre_of_mass_z",
+            HandlingRule::PercentSubmerged => "percent_submerged",
+            HandlingRule::TractionMultiplier => "traction_multiplier",
+            HandlingRule::TractionLoss => "traction_loss",
+            HandlingRule::TractionBias => "traction_bias",
+            HandlingRule::NumberOfGears => "number_of_gears",
+            HandlingRule::MaxSpeed => "max_speed",
+            HandlingRule::Acceleration => "acceleration",
+            HandlingRule::DriveType => "drive_type",
+            HandlingRule::EngineType => "engine_type",
+            HandlingRule::BrakeDeceleration => "brake_deceleration",
+            HandlingRule::BrakeBias => "brake_bias",
+            HandlingRule::SteeringLock => "steering_lock",
+            HandlingRule::SuspensionForceLevel => "suspension_force_level",
+            HandlingRule::SuspensionDampening => "suspension_dampening",
+            HandlingRule::SeatOffset => "seat_offset",
+            HandlingRule::DamageMultiplier => "damage_multiplier",
+            HandlingRule::SuspensionUpperLimit => "suspension_upper_limit",
+            HandlingRule::SuspensionLowerLimit => "suspension_lower_limit",
+            HandlingRule::SuspensionBias => "suspension_bias",
+            HandlingRule::SuspensionAntiDive => "suspension_anti_dive",
+            HandlingRule::Flags => "flags",
+            HandlingRule::FrontLights => "front_lights",
+            HandlingRule::RearLights => "rear_lights",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.key() == key)
+    }
+}
+
+#[pymethods]
+impl HandlingRule {
+    #[getter]
+    fn get_key(&self) -> &'static str {
+        self.key()
+    }
+
+    fn __index__(&self) -> i32 {
+        *self as i32
+    }
+}
+
+/// 把 key 换成规则，遇到不认识的 key 直接报错
+pub fn resolve_table(table: &HandlingTable) -> PyResult<Vec<(HandlingRule, f64)>> {
+    table
+        .iter()
+        .map(|(key, value)| {
+            HandlingRule::from_key(key)
+                .map(|rule| (rule, *value))
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown handling rule: {key}")))
+        })
+        .collect()
+}
+
+/// 读一张操控表，overridden_only 的话只要被改过的规则
+pub fn collect_table(
+    overridden_only: bool,
+    exists: impl Fn(i32) -> bool,
+    get: impl Fn(i32) -> f64,
+) -> HandlingTable {
+    HandlingRule::ALL
+        .into_iter()
+        .filter(|rule| !overridden_only || exists(*rule as i32))
+        .map(|rule| (rule.key().to_string(), get(rule as i32)))
+        .collect()
+}
+
+/// format 是 "toml" 或者 "json"
+pub fn dumps<'py, T>(py: Python<'py>, value: T, format: &str) -> PyResult<String>
+where
+    T: Serialize + IntoPyObject<'py>,
+{
+    match format {
+        "toml" => toml::to_string_pretty(&value).map_err(|e| PyValueError::new_err(e.to_string())),
+        "json" => py
+            .import("json")?
+            .call_method1("dumps", (value,))?
+            .extract::<String>(),
+        _ => Err(PyValueError::new_err(format!("Unknown format: {format}"))),
+    }
+}
+
+pub fn loads<T>(py: Python<'_>, content: &str, format: &str) -> PyResult<T>
+where
+    T: DeserializeOwned + for<'py> FromPyObject<'py>,
+{
+    match format {
+        "toml" => toml::from_str::<T>(content).map_err(|e| PyValueError::new_err(e.to_string())),
+        "json" => py
+            .import("json")?
+            .call_method1("loads", (content,))?
+            .extract::<T>(),
+        _ => Err(PyValueError::new_err(format!("Unknown format: {format}"))),
+    }
+}
+
+fn parse_model_key(key: &str) -> PyResult<i32> {
+    match key.parse::<i32>() {
+        Ok(id) => Ok(id),
+        Err(_) => VehicleModelPy::from_name(key).map(i32::from),
+    }
+}
+
+#[pyfunction]
+pub fn exists_model_handling_rule(model: i32, rule: i32) -> bool {
+    vcmp_func().exists_handling_rule(model, rule)
+}
+
+#[pyfunction]
+pub fn get_model_handling_rule(model: i32, rule: i32) -> f64 {
+    vcmp_func().get_handling_rule(model, rule)
+}
+
+#[pyfunction]
+pub fn set_model_handling_rule(model: i32, rule: i32, value: f64) {
+    let _ = vcmp_func().set_handling_rule(model, rule, value);
+}
+
+#[pyfunction]
+pub fn reset_model_handling_rule(model: i32, rule: i32) {
+    let _ = vcmp_func().reset_handling_rule(model, rule);
+}
+
+#[pyfunction]
+pub fn reset_model_handling(model: i32) {
+    let _ = vcmp_func().reset_handling(model);
+}
+
+#[pyfunction]
+pub fn reset_all_handling() {
+    let _ = vcmp_func().reset_all_vehicle_handlings();
+}
+
+#[pyfunction]
+#[pyo3(signature = (model, overridden_only = false))]
+pub fn get_model_handling(model: i32, overridden_only: bool) -> HandlingTable {
+    collect_table(
+        overridden_only,
+        |rule| vcmp_func().exists_handling_rule(model, rule),
+        |rule| vcmp_func().get_handling_rule(model, rule),
+    )
+}
+
+#[pyfunction]
+#[pyo3(signature = (model, table, reset = false))]
+pub fn set_model_handling(model: i32, table: HandlingTable, reset: bool) -> PyResult<()> {
+    let rules = resolve_table(&table)?;
+    if reset {
+        reset_model_handling(model);
+    }
+    for (rule, value) in rules {
+        set_model_handling_rule(model, rule as i32, value);
+    }
+    Ok(())
+}
+
+/// 导出车型的操控预设，不传 models 的话导出所有被改过的车型
+///
+/// ```toml
+/// [models.Infernus]
+/// max_speed = 260.0
+/// acceleration = 40.0
+/// ```
+#[pyfunction]
+#[pyo3(signature = (models = None, path = None, format = "toml", overridden_only = true))]
+pub fn export_handling_preset(
+    py: Python<'_>,
+    models: Option<Vec<i32>>,
+    path: Option<String>,
+    format: &str,
+    overridden_only: bool,
+) -> PyResult<String> {
+    let models = models.unwrap_or_else(|| VEHICLE_NAMES.iter().map(|(model, _)| *model).collect());
+    let mut preset: BTreeMap<String, HandlingTable> = BTreeMap::new();
+    for model in models {
+        let table = get_model_handling(model, overridden_only);
+        if table.is_empty() {
+            continue;
+        }
+        let key = VehicleModelPy::try_from(model)
+            .map(|model| model.get_name().to_string())
+            .unwrap_or(model.to_string());
+        preset.insert(key, table);
+    }
+    let mut root = BTreeMap::new();
+    root.insert("models".to_string(), preset);
+    let content = dumps(py, root, format)?;
+    if let Some(path) = path {
+        std::fs::write(&path, &content).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    }
+    Ok(content)
+}
+
+/// 从字符串导入车型的操控预设，返回改了多少条规则
+#[pyfunction]
+#[pyo3(signature = (content, format = "toml", reset = false))]
+pub fn load_handling_preset(
+    py: Python<'_>,
+    content: &str,
+    format: &str,
+    reset: bool,
+) -> PyResult<usize> {
+    let root = loads::<BTreeMap<String, BTreeMap<String, HandlingTable>>>(py, content, format)?;
+    let preset = root.get("models").cloned().unwrap_or_default();
+
+    // 先全部检查一遍，不要导入到一半才报错
+    let mut changes = Vec::new();
+    for (key, table) in &preset {
+        changes.push((parse_model_key(key)?, resolve_table(table)?));
+    }
+
+    if reset {
+        reset_all_handling();
+    }
+    let mut count = 0;
+    for (model, rules) in changes {
+        for (rule, value) in rules {
+            set_model_handling_rule(model, rule as i32, value);
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// 从文件导入，format 不传的话按后缀名判断
+#[pyfunction]
+#[pyo3(signature = (path, format = None, reset = false))]
+pub fn import_handling_preset(
+    py: Python<'_>,
+    path: &str,
+    format: Option<&str>,
+    reset: bool,
+) -> PyResult<usize> {
+    let content = std::fs::read_to_string(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let format = format.unwrap_or(if path.ends_with(".json") {
+        "json"
+    } else {
+        "toml"
+    });
+    load_handling_preset(py, &content, format, reset)
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<HandlingRule>()?;
+    m.add_function(wrap_pyfunction!(exists_model_handling_rule, m)?)?;
+    m.add_function(wrap_pyfunction!(get_model_handling_rule, m)?)?;
+    m.add_function(wrap_pyfunction!(set_model_handling_rule, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_model_handling_rule, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_model_handling, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_all_handling, m)?)?;
+    m.add_function(wrap_pyfunction!(get_model_handling, m)?)?;
+    m.add_function(wrap_pyfunction!(set_model_handling, m)?)?;
+    m.add_function(wrap_pyfunction!(export_handling_preset, m)?)?;
+    m.add_function(wrap_pyfunction!(load_handling_preset, m)?)?;
+    m.add_function(wrap_pyfunction!(import_handling_preset, m)?)?;
+    Ok(())
+}
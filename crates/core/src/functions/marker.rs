@@ -8,7 +8,7 @@ use pyo3::{
 use vcmp_bindings::{func::MarkerMethods, vcmp_func};
 
 use crate::{
-    pool::{ENTITY_POOL, EntityPoolTrait},
+    pool::{EntityPoolTrait, read_pool},
     py::types::{EntityVectorType, RGBPy, VectorPy},
 };
 
@@ -116,7 +116,7 @@ pub fn create_marker(
 ) -> MarkerPy {
     let id = vcmp_func().create_marker(world, position.into(), scale, color.into(), model, None);
 
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
 
     pool.get_marker(id).copied().unwrap_or(MarkerPy::new(id))
 }
@@ -1,15 +1,19 @@
-use std::{fmt::Display, ops::Add};
+use std::fmt::Display;
 
 use pyo3::{
     Bound, PyResult, Python, pyclass, pyfunction, pymethods,
     types::{PyModule, PyModuleMethods},
     wrap_pyfunction,
 };
-use vcmp_bindings::{func::ObjectMethods, vcmp_func};
+use vcmp_bindings::{func::ObjectMethods, utils::Quaternionf32, vcmp_func};
 
 use crate::{
-    functions::player::PlayerPy,
-    pool::{ENTITY_POOL, EntityPoolTrait},
+    functions::{
+        animation::{self, ObjectAnimationPy},
+        attachment::{self, AttachTarget},
+        player::PlayerPy,
+    },
+    pool::{EntityPoolTrait, read_pool},
     py::types::{EntityQuaternionType, EntityVectorType, QuaternionPy, VectorPy},
 };
 
@@ -77,8 +81,8 @@ impl ObjectPy {
     }
 
     fn add_position(&self, vector: VectorPy) {
-        let origin = self._position();
-        let _ = origin.add(vector);
+        let mut origin = self._position();
+        origin.add_to_entity(vector);
     }
 
     #[getter]
@@ -166,6 +170,52 @@ impl ObjectPy {
         );
     }
 
+    /// 播放动画，queue 为 True 的时候排在当前动画后面
+    #[pyo3(signature = (animation, queue = false))]
+    fn animate(&self, animation: ObjectAnimationPy, queue: bool) {
+        attachment::detach(self.id);
+        animation::play_animation(self.id, animation, queue);
+    }
+
+    /// 停止当前动画并清空队列
+    fn stop_animation(&self) -> bool {
+        animation::stop_animation(self.id)
+    }
+
+    #[getter]
+    fn is_animating(&self) -> bool {
+        animation::is_animating(self.id)
+    }
+
+    /// 附着到玩家或者载具上，offset 和 rotation 是相对于实体的
+    #[pyo3(signature = (entity, offset = None, rotation = None))]
+    fn attach_to(
+        &self,
+        entity: AttachTarget,
+        offset: Option<VectorPy>,
+        rotation: Option<QuaternionPy>,
+    ) {
+        animation::forget_object(self.id);
+        attachment::attach(
+            self.id,
+            entity,
+            offset.map(|v| v.get_entity_pos()).unwrap_or_default(),
+            rotation
+                .map(|q| q.get_entity_quaternion())
+                .unwrap_or(Quaternionf32::new(0.0, 0.0, 0.0, 1.0)),
+        );
+    }
+
+    /// 取消附着，物体停在当前位置
+    fn detach(&self) -> bool {
+        attachment::detach(self.id)
+    }
+
+    #[getter]
+    fn get_attached_to(&self) -> Option<AttachTarget> {
+        attachment::get_attached_to(self.id)
+    }
+
     #[getter]
     fn get_shot_report(&self) -> bool {
         vcmp_func().is_object_shot_report_enabled(self.id)
@@ -225,7 +275,7 @@ impl ObjectPy {
 pub fn create_object(model: i32, world: i32, pos: VectorPy, alpha: i32) -> ObjectPy {
     let id = vcmp_func().create_object(model, world, pos.into(), alpha);
 
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
 
     pool.get_object(id).copied().unwrap_or(ObjectPy::new(id))
 }
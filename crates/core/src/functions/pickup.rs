@@ -1,4 +1,4 @@
-use std::{fmt::Display, ops::Add};
+use std::fmt::Display;
 
 use pyo3::{
     Bound, PyResult, Python, pyclass, pyfunction, pymethods,
@@ -9,7 +9,7 @@ use vcmp_bindings::{func::PickupMethods, vcmp_func};
 
 use crate::{
     functions::player::PlayerPy,
-    pool::{ENTITY_POOL, EntityPoolTrait},
+    pool::{EntityPoolTrait, read_pool},
     py::types::{EntityVectorType, VectorPy},
 };
 
@@ -73,8 +73,8 @@ impl PickupPy {
     }
 
     fn add_position(&self, position: VectorPy) {
-        let origin = self._position();
-        let _ = origin.add(position);
+        let mut origin = self._position();
+        origin.add_to_entity(position);
     }
 
     #[getter]
@@ -185,7 +185,7 @@ pub fn create_pickup(
         is_automatic,
     );
 
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
 
     pool.get_pickup(id).copied().unwrap_or(PickupPy::new(id))
 }
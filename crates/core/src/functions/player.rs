@@ -1,7 +1,7 @@
-use std::{fmt::Display, ops::Add};
+use std::fmt::Display;
 
 use pyo3::{
-    Bound, PyResult, Python, pyclass, pymethods,
+    Bound, PyAny, PyResult, Python, pyclass, pymethods,
     types::{PyModule, PyModuleMethods},
 };
 use vcmp_bindings::{
@@ -9,11 +9,12 @@ use vcmp_bindings::{
     utils::{Color, Vectorf32},
 };
 
-use crate::py::streams::WriteStream;
+use crate::py::streams::{WriteStream, chunk, compress};
 use crate::py::types::RGBPy;
+use crate::py::util::format_markup;
 use crate::{
     functions::{object::ObjectPy, vehicle::VehiclePy},
-    pool::{ENTITY_POOL, EntityPoolTrait},
+    pool::{EntityPoolTrait, read_pool},
     py::types::{EntityVectorType, VectorPy},
 };
 use vcmp_bindings::{func::PlayerMethods, options::VcmpPlayerOption, vcmp_func};
@@ -31,6 +32,13 @@ pub struct PlayerPy {
     last_position: Vectorf32,
     last_weapon: i32,
     last_ammo: i32,
+    // 以下是可选追踪的，None 表示还没有记录（没有注册对应事件）
+    last_skin: Option<i32>,
+    last_team: Option<i32>,
+    last_score: Option<i32>,
+    last_world: Option<i32>,
+    last_cash: Option<i32>,
+    last_wanted_level: Option<i32>,
     updating: bool,
     // 用于判断是否进入的
     loaded: bool,
@@ -53,6 +61,12 @@ impl PlayerPy {
             last_position: Vectorf32::default(),
             last_weapon: 0,
             last_ammo: 0,
+            last_skin: None,
+            last_team: None,
+            last_score: None,
+            last_world: None,
+            last_cash: None,
+            last_wanted_level: None,
             loaded: false,
             updating: false,
             reload_joined: true, // 默认初始化就是为 true
@@ -87,6 +101,30 @@ impl PlayerPy {
         self.last_ammo = ammo;
     }
 
+    pub fn set_var_last_skin(&mut self, skin: Option<i32>) {
+        self.last_skin = skin;
+    }
+
+    pub fn set_var_last_team(&mut self, team: Option<i32>) {
+        self.last_team = team;
+    }
+
+    pub fn set_var_last_score(&mut self, score: Option<i32>) {
+        self.last_score = score;
+    }
+
+    pub fn set_var_last_world(&mut self, world: Option<i32>) {
+        self.last_world = world;
+    }
+
+    pub fn set_var_last_cash(&mut self, cash: Option<i32>) {
+        self.last_cash = cash;
+    }
+
+    pub fn set_var_last_wanted_level(&mut self, wanted_level: Option<i32>) {
+        self.last_wanted_level = wanted_level;
+    }
+
     pub fn set_var_loaded(&mut self, loaded: bool) {
         self.loaded = loaded;
     }
@@ -111,6 +149,30 @@ impl PlayerPy {
         self.last_ammo
     }
 
+    pub fn get_var_last_skin(&self) -> Option<i32> {
+        self.last_skin
+    }
+
+    pub fn get_var_last_team(&self) -> Option<i32> {
+        self.last_team
+    }
+
+    pub fn get_var_last_score(&self) -> Option<i32> {
+        self.last_score
+    }
+
+    pub fn get_var_last_world(&self) -> Option<i32> {
+        self.last_world
+    }
+
+    pub fn get_var_last_cash(&self) -> Option<i32> {
+        self.last_cash
+    }
+
+    pub fn get_var_last_wanted_level(&self) -> Option<i32> {
+        self.last_wanted_level
+    }
+
     pub fn get_var_loaded(&self) -> bool {
         self.loaded
     }
@@ -186,15 +248,15 @@ impl PlayerPy {
 
     pub fn add_position(&mut self, py: Python<'_>, pos: VectorPy) {
         py.allow_threads(|| {
-            let origin = self.position();
-            let _ = origin.add(pos);
+            let mut origin = self.position();
+            origin.add_to_entity(pos);
         })
     }
 
     pub fn add_speed(&mut self, py: Python<'_>, speed: VectorPy) {
         py.allow_threads(|| {
-            let origin = self._speed();
-            let _ = origin.add(speed);
+            let mut origin = self._speed();
+            origin.add_to_entity(speed);
         })
     }
 
@@ -669,12 +731,30 @@ impl PlayerPy {
         });
     }
 
-    pub fn send_data(&self, py: Python<'_>, data: WriteStream) {
+    /// compress 为 True 时压缩后发送，客户端要用 compress.nut 解压
+    #[pyo3(signature = (data, compress = false))]
+    pub fn send_data(&self, py: Python<'_>, data: WriteStream, compress: bool) {
         py.allow_threads(|| {
-            let _ = vcmp_func().send_client_script_data(self.id, data.raw_buffer().as_slice());
+            let buffer = if compress {
+                compress::pack(data.raw_buffer())
+            } else {
+                data.raw_buffer()
+            };
+            let _ = vcmp_func().send_client_script_data(self.id, buffer.as_slice());
         });
     }
 
+    /// 数据太大的时候分片发送，客户端要用 chunk.nut 拼起来，返回 transfer id
+    #[pyo3(signature = (data, chunk_size = None))]
+    pub fn send_chunked_data(
+        &self,
+        py: Python<'_>,
+        data: &Bound<'_, PyAny>,
+        chunk_size: Option<usize>,
+    ) -> PyResult<i32> {
+        chunk::send_chunked(py, self, data, chunk_size)
+    }
+
     pub fn send_raw_message(&self, py: Python<'_>, color: RGBPy, message: &str) {
         py.allow_threads(|| {
             let _ = vcmp_func().send_client_message(self.id, color.into(), message);
@@ -685,6 +765,13 @@ impl PlayerPy {
         self.send_raw_message(py, RGBPy::from_rgb(0xFFFFFF, None), message)
     }
 
+    /// 支持 `{red}Warning{/}` 这样的颜色 markup
+    pub fn send_markup_message(&self, py: Python<'_>, markup: &str) -> PyResult<()> {
+        let message = format_markup(markup, None)?;
+        self.send_message(py, &message);
+        Ok(())
+    }
+
     pub fn send_announce(&self, py: Python<'_>, announce_type: i32, message: &str) {
         py.allow_threads(|| {
             let _ = vcmp_func().send_announce(self.id, announce_type, message);
@@ -793,7 +880,7 @@ impl PlayerPy {
     #[getter]
     pub fn get_spectate_target(&self, py: Python<'_>) -> Option<PlayerPy> {
         py.allow_threads(|| {
-            let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+            let pool = read_pool();
             let id = vcmp_func().get_player_spectate_target(self.id);
             pool.get_player(id).copied()
         })
@@ -823,7 +910,7 @@ impl PlayerPy {
     pub fn get_standing_on_object(&self, py: Python<'_>) -> Option<ObjectPy> {
         py.allow_threads(|| {
             let id = vcmp_func().get_player_standing_on_object(self.id);
-            let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+            let pool = read_pool();
             pool.get_object(id).copied()
         })
     }
@@ -832,7 +919,7 @@ impl PlayerPy {
     pub fn get_standing_vehicle(&self, py: Python<'_>) -> Option<VehiclePy> {
         py.allow_threads(|| {
             let id = vcmp_func().get_player_standing_on_vehicle(self.id);
-            let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+            let pool = read_pool();
             pool.get_vehicle(id).copied()
         })
     }
@@ -873,7 +960,7 @@ impl PlayerPy {
     pub fn get_vehicle(&self, py: Python<'_>) -> Option<VehiclePy> {
         py.allow_threads(|| {
             let vehicle_id = vcmp_func().get_player_vehicle_id(self.id);
-            let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+            let pool = read_pool();
             pool.get_vehicle(vehicle_id).copied()
         })
     }
@@ -0,0 +1,314 @@
+//! 出生职业（class）
+//!
+//! 服务器只会在添加的时候给一个 id，这里把每个 class 的数据记下来，
+//! 方便在 request class 的时候拿到完整信息。
+//! 服务器没有删除 class 的接口，`add_player_class` 每次调用都会添加一个新的，
+//! class 文件（配置里的和 `load_player_classes(dedup=True)`）在重载的时候会再加载一次，
+//! 所以只有它们会复用一模一样的 class 的 id，不会越加越多。
+
+use std::sync::{LazyLock, Mutex};
+
+use pyo3::{
+    Bound, PyResult, Python,
+    exceptions::{PyIOError, PyValueError},
+    pyclass, pyfunction, pymethods,
+    types::{PyModule, PyModuleMethods},
+    wrap_pyfunction,
+};
+use serde::Deserialize;
+use tracing::{Level, event};
+use vcmp_bindings::{
+    func::EnvironmentMethods,
+    utils::{Color, Vectorf32},
+    vcmp_func,
+};
+
+use crate::{
+    cfg::get_class_file,
+    py::types::{RGBPy, VectorPy, parse_color},
+};
+
+#[pyclass]
+#[pyo3(name = "PlayerClass")]
+#[derive(Debug, Clone)]
+pub struct ClassPy {
+    id: i32,
+    team: i32,
+    color: Color,
+    skin: i32,
+    position: Vectorf32,
+    angle: f32,
+    /// (武器, 弹药)，最多三把
+    weapons: Vec<(i32, i32)>,
+    name: Option<String>,
+}
+
+impl ClassPy {
+    /// 除了 id 以外全都一样
+    fn same_as(&self, other: &ClassPy) -> bool {
+        self.team == other.team
+            && self.color.as_rgba() == other.color.as_rgba()
+            && self.skin == other.skin
+            && self.position.x == other.position.x
+            && self.position.y == other.position.y
+            && self.position.z == other.position.z
+            && self.angle == other.angle
+            && self.weapons == other.weapons
+            && self.name == other.name
+    }
+
+    fn weapon(&self, slot: usize) -> Option<(i32, i32)> {
+        self.weapons.get(slot).copied()
+    }
+}
+
+#[pymethods]
+impl ClassPy {
+    #[getter]
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    #[getter]
+    fn get_team(&self) -> i32 {
+        self.team
+    }
+
+    #[getter]
+    fn get_color(&self) -> RGBPy {
+        RGBPy::from(self.color)
+    }
+
+    #[getter]
+    fn get_skin(&self) -> i32 {
+        self.skin
+    }
+
+    #[getter]
+    fn get_position(&self) -> VectorPy {
+        VectorPy::from(self.position)
+    }
+
+    #[getter]
+    fn get_angle(&self) -> f32 {
+        self.angle
+    }
+
+    #[getter]
+    fn get_weapons(&self) -> Vec<(i32, i32)> {
+        self.weapons.clone()
+    }
+
+    #[getter]
+    fn get_name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn __eq__(&self, other: &ClassPy) -> bool {
+        self.id == other.id
+    }
+
+    fn __hash__(&self) -> i32 {
+        self.id
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerClass(id={}, team={}, skin={}, name={:?})",
+            self.id, self.team, self.skin, self.name
+        )
+    }
+}
+
+static PLAYER_CLASSES: LazyLock<Mutex<Vec<ClassPy>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// 添加 class，dedup 为 true 的时候已经有一样的就直接返回原来的
+fn register_class(mut class: ClassPy, dedup: bool) -> ClassPy {
+    let mut classes = PLAYER_CLASSES
+        .lock()
+        .expect("Failed to lock player classes");
+    if dedup && let Some(existing) = classes.iter().find(|c| c.same_as(&class)) {
+        return existing.clone();
+    }
+    class.id = vcmp_func().add_player_class(
+        class.team,
+        class.color,
+        class.skin,
+        class.position,
+        class.angle,
+        class.weapon(0),
+        class.weapon(1),
+        class.weapon(2),
+    );
+    classes.push(class.clone());
+    class
+}
+
+pub fn get_class(class_id: i32) -> Option<ClassPy> {
+    PLAYER_CLASSES
+        .lock()
+        .expect("Failed to lock player classes")
+        .iter()
+        .find(|c| c.id == class_id)
+        .cloned()
+}
+
+#[derive(Debug, Deserialize)]
+struct ClassEntry {
+    team: i32,
+    /// `#RRGGBB` 或者颜色名字
+    color: String,
+    skin: i32,
+    position: [f32; 3],
+    #[serde(default)]
+    angle: f32,
+    #[serde(default)]
+    weapons: Vec<(i32, i32)>,
+    name: Option<String>,
+}
+
+/// ```toml
+/// [[class]]
+/// name = "Police"
+/// team = 1
+/// color = "#3366FF"
+/// skin = 1
+/// position = [-657.0, 762.0, 11.6]
+/// angle = 0.0
+/// weapons = [[17, 200], [19, 50]]
+/// ```
+#[derive(Debug, Deserialize)]
+struct ClassFile {
+    #[serde(default, rename = "class")]
+    classes: Vec<ClassEntry>,
+}
+
+fn load_classes(content: &str, dedup: bool) -> Result<Vec<ClassPy>, String> {
+    let file = toml::from_str::<ClassFile>(content).map_err(|e| e.to_string())?;
+    // 先全部检查一遍，不要添加到一半才报错
+    let classes = file
+        .classes
+        .into_iter()
+        .map(|entry| {
+            if entry.weapons.len() > 3 {
+                return Err(format!("Class has more than 3 weapons: {:?}", entry.name));
+            }
+            Ok(ClassPy {
+                id: -1,
+                team: entry.team,
+                color: parse_color(&entry.color)
+                    .ok_or(format!("Invalid color: {}", entry.color))?,
+                skin: entry.skin,
+                position: Vectorf32::new(entry.position[0], entry.position[1], entry.position[2]),
+                angle: entry.angle,
+                weapons: entry.weapons,
+                name: entry.name,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(classes
+        .into_iter()
+        .map(|class| register_class(class, dedup))
+        .collect())
+}
+
+/// 加载配置里的 class 文件，server init 和重载脚本的时候都会调用
+pub fn load_class_file() {
+    let path = get_class_file();
+    if path.is_empty() {
+        return;
+    }
+    let result = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| load_classes(&content, true));
+    match result {
+        Ok(classes) => event!(
+            Level::INFO,
+            "Loaded {} class(es) from {path}",
+            classes.len()
+        ),
+        Err(e) => event!(Level::ERROR, "Failed to load class file {path}: {e}"),
+    }
+}
+
+/// 添加一个 class，返回注册好的 class（带服务器给的 id）
+#[pyfunction]
+#[pyo3(signature = (team, color, skin, pos, angle, weapon = None, ammo = None, weapon1 = None, ammo1 = None, weapon2 = None, ammo2 = None, name = None))]
+#[allow(clippy::too_many_arguments)]
+pub fn add_player_class(
+    team: i32,
+    color: RGBPy,
+    skin: i32,
+    pos: VectorPy,
+    angle: f32,
+    weapon: Option<i32>,
+    ammo: Option<i32>,
+    weapon1: Option<i32>,
+    ammo1: Option<i32>,
+    weapon2: Option<i32>,
+    ammo2: Option<i32>,
+    name: Option<String>,
+) -> ClassPy {
+    // 没给武器的槽位不传给服务器，不是 (0, 0)
+    let weapons = [(weapon, ammo), (weapon1, ammo1), (weapon2, ammo2)]
+        .into_iter()
+        .filter_map(|(weapon, ammo)| weapon.map(|weapon| (weapon, ammo.unwrap_or_default())))
+        .collect();
+    register_class(
+        ClassPy {
+            id: -1,
+            team,
+            color: color.into(),
+            skin,
+            position: pos.get_entity_pos(),
+            angle,
+            weapons,
+            name,
+        },
+        false,
+    )
+}
+
+#[pyfunction]
+pub fn get_player_class(class_id: i32) -> Option<ClassPy> {
+    get_class(class_id)
+}
+
+#[pyfunction]
+pub fn get_player_classes() -> Vec<ClassPy> {
+    PLAYER_CLASSES
+        .lock()
+        .expect("Failed to lock player classes")
+        .clone()
+}
+
+#[pyfunction]
+pub fn get_team_classes(team: i32) -> Vec<ClassPy> {
+    PLAYER_CLASSES
+        .lock()
+        .expect("Failed to lock player classes")
+        .iter()
+        .filter(|c| c.team == team)
+        .cloned()
+        .collect()
+}
+
+/// 从 toml 文件加载 class 列表，返回加载到的 class
+///
+/// 重载的时候会再调用一次的话传 dedup=True，一样的 class 复用原来的 id
+#[pyfunction]
+#[pyo3(signature = (path, dedup = false))]
+pub fn load_player_classes(path: &str, dedup: bool) -> PyResult<Vec<ClassPy>> {
+    let content = std::fs::read_to_string(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    load_classes(&content, dedup).map_err(PyValueError::new_err)
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ClassPy>()?;
+    m.add_function(wrap_pyfunction!(add_player_class, m)?)?;
+    m.add_function(wrap_pyfunction!(get_player_class, m)?)?;
+    m.add_function(wrap_pyfunction!(get_player_classes, m)?)?;
+    m.add_function(wrap_pyfunction!(get_team_classes, m)?)?;
+    m.add_function(wrap_pyfunction!(load_player_classes, m)?)?;
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use std::ops::Add as _;
 use std::{collections::HashMap, fmt::Display};
 
 use pyo3::{
@@ -16,11 +15,31 @@ use vcmp_bindings::{
 };
 
 use crate::{
-    functions::player::PlayerPy,
-    pool::{ENTITY_POOL, EntityPoolTrait},
-    py::types::{EntityQuaternionType, EntityVectorType, QuaternionPy, VectorPy},
+    functions::{
+        handling::{self, HandlingRule, HandlingTable},
+        player::PlayerPy,
+    },
+    pool::{EntityPoolTrait, read_pool},
+    py::{
+        callbacks::{PY_CALLBACK_MANAGER, PY_CALLBACK_STORAGE},
+        events::{VcmpEvent, VcmpEventType, vehicle::VehicleHandlingChangeEvent},
+        types::{EntityQuaternionType, EntityVectorType, QuaternionPy, VectorPy},
+    },
 };
 
+/// 没注册 VehicleHandlingChangeEvent 就不用多查一次旧值
+fn handling_tracked() -> bool {
+    PY_CALLBACK_STORAGE
+        .lock()
+        .expect("PyCallbackStorage lock failed")
+        .has_handlers(VcmpEventType::VehicleHandlingChange)
+}
+
+/// 轮胎数量，用于 on_vehicle_update 里的轮胎追踪
+pub const VEHICLE_TYRE_COUNT: i32 = 4;
+/// 部件数量（引擎盖、后备箱和四个车门）
+pub const VEHICLE_PART_COUNT: i32 = 6;
+
 #[pyclass]
 #[derive(Debug, Clone, Copy)]
 #[pyo3(name = "Vehicle")]
@@ -30,6 +49,14 @@ pub struct VehiclePy {
     // storage var
     last_pos: Vectorf32,
     last_health: f32,
+    // 以下只有注册了对应事件才会记录，None 表示还没开始记录
+    last_damage: Option<u32>,
+    last_lights_data: Option<u32>,
+    // 按位存储，第 n 位对应第 n 个轮胎 / 部件
+    last_tyre_status: Option<u8>,
+    last_part_status: Option<u8>,
+    last_siren: Option<bool>,
+    last_doors_locked: Option<bool>,
     updating: bool,
 }
 
@@ -45,6 +72,12 @@ impl VehiclePy {
             id,
             last_pos: Vectorf32::default(),
             last_health: 1000.0,
+            last_damage: None,
+            last_lights_data: None,
+            last_tyre_status: None,
+            last_part_status: None,
+            last_siren: None,
+            last_doors_locked: None,
             updating: false,
         }
     }
@@ -73,6 +106,84 @@ impl VehiclePy {
         self.last_health
     }
 
+    pub fn set_var_last_damage(&mut self, damage: Option<u32>) {
+        self.last_damage = damage;
+    }
+
+    pub fn get_var_last_damage(&self) -> Option<u32> {
+        self.last_damage
+    }
+
+    pub fn set_var_last_lights_data(&mut self, lights_data: Option<u32>) {
+        self.last_lights_data = lights_data;
+    }
+
+    pub fn get_var_last_lights_data(&self) -> Option<u32> {
+        self.last_lights_data
+    }
+
+    pub fn set_var_last_tyre_status(&mut self, tyre_status: Option<u8>) {
+        self.last_tyre_status = tyre_status;
+    }
+
+    pub fn get_var_last_tyre_status(&self) -> Option<u8> {
+        self.last_tyre_status
+    }
+
+    pub fn set_var_last_part_status(&mut self, part_status: Option<u8>) {
+        self.last_part_status = part_status;
+    }
+
+    pub fn get_var_last_part_status(&self) -> Option<u8> {
+        self.last_part_status
+    }
+
+    pub fn set_var_last_siren(&mut self, siren: Option<bool>) {
+        self.last_siren = siren;
+    }
+
+    pub fn get_var_last_siren(&self) -> Option<bool> {
+        self.last_siren
+    }
+
+    pub fn set_var_last_doors_locked(&mut self, doors_locked: Option<bool>) {
+        self.last_doors_locked = doors_locked;
+    }
+
+    pub fn get_var_last_doors_locked(&self) -> Option<bool> {
+        self.last_doors_locked
+    }
+
+    /// 重生之后载具状态都重置了，重新开始记录，免得第一次 update 触发一堆事件
+    pub fn reset_var_last_status(&mut self) {
+        self.last_damage = None;
+        self.last_lights_data = None;
+        self.last_tyre_status = None;
+        self.last_part_status = None;
+        self.last_siren = None;
+        self.last_doors_locked = None;
+    }
+
+    /// 改一条操控规则，值真的变了才触发 VehicleHandlingChangeEvent
+    fn change_handling_rule(&self, rule_index: i32, change: impl FnOnce()) {
+        if !handling_tracked() {
+            change();
+            return;
+        }
+        let old_value = vcmp_func().get_inst_handling_rule(self.id, rule_index);
+        change();
+        self.fire_handling_change(rule_index, old_value);
+    }
+
+    fn fire_handling_change(&self, rule_index: i32, old_value: f64) {
+        let new_value = vcmp_func().get_inst_handling_rule(self.id, rule_index);
+        if old_value != new_value {
+            let event =
+                VehicleHandlingChangeEvent::from((self.id, rule_index, old_value, new_value));
+            let _ = PY_CALLBACK_MANAGER.handle(VcmpEvent::VehicleHandlingChange(event), false);
+        }
+    }
+
     pub fn _position(&self) -> VectorPy {
         VectorPy::from((EntityVectorType::VehiclePosition, self.id))
     }
@@ -149,53 +260,53 @@ impl VehiclePy {
     }
 
     fn add_position(&mut self, pos: VectorPy) {
-        let origin = self._position();
-        let _ = origin.add(pos);
+        let mut origin = self._position();
+        origin.add_to_entity(pos);
     }
 
     fn add_relative_speed(&mut self, speed: VectorPy) {
-        let origin = self._rel_speed();
-        let _ = origin.add(speed);
+        let mut origin = self._rel_speed();
+        origin.add_to_entity(speed);
     }
 
     fn add_relative_turn_speed(&mut self, speed: VectorPy) {
-        let origin = self._rel_turn_speed();
-        let _ = origin.add(speed);
+        let mut origin = self._rel_turn_speed();
+        origin.add_to_entity(speed);
     }
 
     fn add_rotation_euler(&mut self, rotation: VectorPy) {
-        let origin = self._rotation_euler();
-        let _ = origin.add(rotation);
+        let mut origin = self._rotation_euler();
+        origin.add_to_entity(rotation);
     }
 
     fn add_rotation(&mut self, rotation: QuaternionPy) {
-        let origin = self._rotation();
-        let _ = origin.add(rotation);
+        let mut origin = self._rotation();
+        origin.add_to_entity(rotation);
     }
 
     fn add_spawn_position(&mut self, pos: VectorPy) {
-        let origin = self._spawn_position();
-        let _ = origin.add(pos);
+        let mut origin = self._spawn_position();
+        origin.add_to_entity(pos);
     }
 
     fn add_spawn_rotation_euler(&mut self, rotation: VectorPy) {
-        let origin = self._spawn_rotation_euler();
-        let _ = origin.add(rotation);
+        let mut origin = self._spawn_rotation_euler();
+        origin.add_to_entity(rotation);
     }
 
     fn add_spawn_rotation(&mut self, rotation: QuaternionPy) {
-        let origin = self._spawn_rotation();
-        let _ = origin.add(rotation);
+        let mut origin = self._spawn_rotation();
+        origin.add_to_entity(rotation);
     }
 
     fn add_speed(&mut self, speed: VectorPy) {
-        let origin = self._speed();
-        let _ = origin.add(speed);
+        let mut origin = self._speed();
+        origin.add_to_entity(speed);
     }
 
     fn add_turn_speed(&mut self, speed: VectorPy) {
-        let origin = self._turn_speed();
-        let _ = origin.add(speed);
+        let mut origin = self._turn_speed();
+        origin.add_to_entity(speed);
     }
 
     #[getter]
@@ -432,11 +543,72 @@ impl VehiclePy {
     }
 
     fn reset_handling(&self) {
+        if !handling_tracked() {
+            let _ = vcmp_func().reset_inst_handling(self.id);
+            return;
+        }
+        // 重置之前先记下被改过的规则，重置之后逐条比较
+        let overridden = HandlingRule::ALL
+            .into_iter()
+            .map(|rule| rule as i32)
+            .filter(|rule| vcmp_func().exists_inst_handling_rule(self.id, *rule))
+            .map(|rule| (rule, vcmp_func().get_inst_handling_rule(self.id, rule)))
+            .collect::<Vec<_>>();
         let _ = vcmp_func().reset_inst_handling(self.id);
+        for (rule, old_value) in overridden {
+            self.fire_handling_change(rule, old_value);
+        }
     }
 
     fn reset_handling_rule(&self, rule_index: i32) {
-        let _ = vcmp_func().reset_inst_handling_rule(self.id, rule_index);
+        self.change_handling_rule(rule_index, || {
+            let _ = vcmp_func().reset_inst_handling_rule(self.id, rule_index);
+        });
+    }
+
+    /// 这辆车的整张操控表，overridden_only 的话只要被改过的规则
+    #[pyo3(signature = (overridden_only = false))]
+    fn get_handling(&self, overridden_only: bool) -> HandlingTable {
+        handling::collect_table(
+            overridden_only,
+            |rule| vcmp_func().exists_inst_handling_rule(self.id, rule),
+            |rule| vcmp_func().get_inst_handling_rule(self.id, rule),
+        )
+    }
+
+    #[pyo3(signature = (table, reset = false))]
+    fn set_handling(&self, table: HandlingTable, reset: bool) -> PyResult<()> {
+        let rules = handling::resolve_table(&table)?;
+        if reset {
+            self.reset_handling();
+        }
+        for (rule, value) in rules {
+            self.set_handling_rule(rule as i32, value);
+        }
+        Ok(())
+    }
+
+    /// 导出成 toml 或者 json
+    #[pyo3(signature = (format = "toml", overridden_only = true))]
+    fn export_handling(
+        &self,
+        py: Python<'_>,
+        format: &str,
+        overridden_only: bool,
+    ) -> PyResult<String> {
+        handling::dumps(py, self.get_handling(overridden_only), format)
+    }
+
+    #[pyo3(signature = (content, format = "toml", reset = false))]
+    fn import_handling(
+        &self,
+        py: Python<'_>,
+        content: &str,
+        format: &str,
+        reset: bool,
+    ) -> PyResult<()> {
+        let table = handling::loads::<HandlingTable>(py, content, format)?;
+        self.set_handling(table, reset)
     }
 
     fn respawn(&self) {
@@ -464,7 +636,9 @@ impl VehiclePy {
     }
 
     fn set_handling_rule(&self, rule_index: i32, rule_value: f64) {
-        let _ = vcmp_func().set_inst_handling_rule(self.id, rule_index, rule_value);
+        self.change_handling_rule(rule_index, || {
+            let _ = vcmp_func().set_inst_handling_rule(self.id, rule_index, rule_value);
+        });
     }
 
     fn set_part_status(&self, part_index: i32, status: i32) {
@@ -572,18 +746,20 @@ impl VehiclePy {
 
     #[getter]
     fn get_driver(&self) -> Option<PlayerPy> {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        pool.get_players().into_iter().find(|&player| {
-            vcmp_func().get_player_vehicle_id(player.get_id()) == self.id
-                && vcmp_func().get_player_in_vehicle_slot(player.get_id()) == 0
-        })
+        let pool = read_pool();
+        pool.players()
+            .find(|player| {
+                vcmp_func().get_player_vehicle_id(player.get_id()) == self.id
+                    && vcmp_func().get_player_in_vehicle_slot(player.get_id()) == 0
+            })
+            .copied()
     }
 
     #[getter]
     fn get_passengers(&self) -> Vec<PlayerPy> {
         let mut passenger_seats: HashMap<i32, Vec<PlayerPy>> = HashMap::new();
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        for player in pool.get_players() {
+        let pool = read_pool();
+        for &player in pool.players() {
             if vcmp_func().get_player_vehicle_id(player.get_id()) == self.id {
                 let slot = vcmp_func().get_player_in_vehicle_slot(player.get_id());
                 if let std::collections::hash_map::Entry::Vacant(e) = passenger_seats.entry(slot) {
@@ -640,7 +816,7 @@ pub fn create_vehicle(
         secondary_colour,
     );
 
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_vehicle(id).copied().unwrap_or(VehiclePy::new(id))
 }
 
@@ -0,0 +1,457 @@
+//! 武器数据
+//!
+//! 对应 weapon.dat 里的字段，可以在运行时调整武器平衡，
+//! 也可以把整套数值导出成 toml / json 预设
+
+use std::collections::BTreeMap;
+
+use pyo3::{
+    Bound, PyResult, Python,
+    exceptions::{PyIOError, PyValueError},
+    pyclass, pyfunction, pymethods,
+    types::{PyModule, PyModuleMethods},
+    wrap_pyfunction,
+};
+use vcmp_bindings::{func::WeaponMethods, vcmp_func};
+
+use crate::functions::handling::{dumps, loads};
+use crate::py::{types::WeaponPy, util::WEAPON_NAMES};
+
+/// 有武器数据的最大武器 id
+pub const MAX_WEAPON_DATA_ID: i32 = 36;
+
+#[pyclass(eq, eq_int, module = "vcmp.functions.weapon")]
+#[pyo3(name = "WeaponField")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponField {
+    FireType = 1,
+    Range = 2,
+    FiringRate = 3,
+    Reload = 4,
+    ClipSize = 5,
+    Damage = 6,
+    Speed = 7,
+    Radius = 8,
+    LifeSpan = 9,
+    Spread = 10,
+    FireOffsetX = 11,
+    FireOffsetY = 12,
+    FireOffsetZ = 13,
+    AnimGroup = 14,
+    AnimLoopStart = 15,
+    AnimLoopEnd = 16,
+    AnimFirePos = 17,
+    AnimTwoLoopStart = 18,
+    AnimTwoLoopEnd = 19,
+    AnimTwoFirePos = 20,
+    AnimBreakoutPos = 21,
+    ModelId = 22,
+    Model2Id = 23,
+    Flags = 24,
+    Slot = 25,
+}
+
+impl WeaponField {
+    pub const ALL: [WeaponField; 25] = [
+        WeaponField::FireType,
+        WeaponField::Range,
+        WeaponField::FiringRate,
+        WeaponField::Reload,
+        WeaponField::ClipSize,
+        WeaponField::Damage,
+        WeaponField::Speed,
+        WeaponField::Radius,
+        WeaponField::LifeSpan,
+        WeaponField::Spread,
+        WeaponField::FireOffsetX,
+        WeaponField::FireOffsetY,
+        WeaponField::FireOffsetZ,
+        WeaponField::AnimGroup,
+        WeaponField::AnimLoopStart,
+        WeaponField::AnimLoopEnd,
+        WeaponField::AnimFirePos,
+        WeaponField::AnimTwoLoopStart,
+        WeaponField::AnimTwoLoopEnd,
+        WeaponField::AnimTwoFirePos,
+        WeaponField::AnimBreakoutPos,
+        WeaponField::ModelId,
+        WeaponField::Model2Id,
+        WeaponField::Flags,
+        WeaponField::Slot,
+    ];
+
+    /// 预设里用的名字
+    pub fn key(self) -> &'static str {
+        match self {
+            WeaponField::FireType => "fire_type",
+            WeaponField::Range => "range",
+            WeaponField::FiringRate => "firing_rate",
+            WeaponField::Reload => "reload",
+            WeaponField::ClipSize => "clip_size",
+            WeaponField::Damage => "damage",
+            WeaponField::Speed => "speed",
+            WeaponField::Radius => "radius",
+            WeaponField::LifeSpan => "life_span",
+            WeaponField::Spread => "spread",
+            WeaponField::FireOffsetX => "fire_offset_x",
+            WeaponField::FireOffsetY => "fire_offset_y",
+            WeaponField::FireOffsetZ => "fire_offset_z",
+            WeaponField::AnimGroup => "anim_group",
+            WeaponField::AnimLoopStart => "anim_loop_start",
+            WeaponField::AnimLoopEnd => "anim_loop_end",
+            WeaponField::AnimFirePos => "anim_fire_pos",
+            WeaponField::AnimTwoLoopStart => "anim_two_loop_start",
+            WeaponField::AnimTwoLoopEnd => "anim_two_loop_end",
+            WeaponField::AnimTwoFirePos => "anim_two_fire_pos",
+            WeaponField::AnimBreakoutPos => "anim_breakout_pos",
+            WeaponField::ModelId => "model_id",
+            WeaponField::Model2Id => "model2_id",
+            WeaponField::Flags => "flags",
+            WeaponField::Slot => "slot",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|field| field.key() == key)
+    }
+}
+
+#[pymethods]
+impl WeaponField {
+    #[getter]
+    fn get_key(&self) -> &'static str {
+        self.key()
+    }
+
+    fn __index__(&self) -> i32 {
+        *self as i32
+    }
+}
+
+#[pyclass]
+#[pyo3(name = "WeaponData")]
+#[derive(Debug, Clone, Copy)]
+pub struct WeaponDataPy {
+    weapon_id: i32,
+}
+
+impl WeaponDataPy {
+    pub fn new(weapon_id: i32) -> Self {
+        Self { weapon_id }
+    }
+
+    fn value(&self, field: WeaponField) -> f64 {
+        vcmp_func().get_weapon_data_value(self.weapon_id, field as i32)
+    }
+
+    fn set_value(&self, field: WeaponField, value: f64) {
+        let _ = vcmp_func().set_weapon_data_value(self.weapon_id, field as i32, value);
+    }
+
+    /// 所有字段，modified_only 的话只要改过的
+    fn values(&self, modified_only: bool) -> WeaponTable {
+        WeaponField::ALL
+            .into_iter()
+            .filter(|field| !modified_only || self.is_modified(Some(*field)))
+            .map(|field| (field.key().to_string(), self.value(field)))
+            .collect()
+    }
+}
+
+#[pymethods]
+impl WeaponDataPy {
+    #[getter]
+    fn get_weapon_id(&self) -> i32 {
+        self.weapon_id
+    }
+
+    #[getter]
+    fn get_weapon(&self) -> Option<WeaponPy> {
+        WeaponPy::try_from(self.weapon_id).ok()
+    }
+
+    fn get(&self, field: WeaponField) -> f64 {
+        self.value(field)
+    }
+
+    fn set(&self, field: WeaponField, value: f64) {
+        self.set_value(field, value);
+    }
+
+    /// 不传 field 的话整把武器都恢复默认
+    #[pyo3(signature = (field = None))]
+    fn reset(&self, field: Option<WeaponField>) {
+        match field {
+            Some(field) => {
+                let _ = vcmp_func().reset_weapon_data_value(self.weapon_id, field as i32);
+            }
+            None => {
+                let _ = vcmp_func().reset_weapon_data(self.weapon_id);
+            }
+        }
+    }
+
+    /// 不传 field 的话看是否有任意一个字段被改过
+    #[pyo3(signature = (field = None))]
+    fn is_modified(&self, field: Option<WeaponField>) -> bool {
+        match field {
+            Some(field) => vcmp_func().is_weapon_data_value_modified(self.weapon_id, field as i32),
+            None => WeaponField::ALL.into_iter().any(|field| {
+                vcmp_func().is_weapon_data_value_modified(self.weapon_id, field as i32)
+            }),
+        }
+    }
+
+    #[pyo3(signature = (modified_only = false))]
+    fn to_dict(&self, modified_only: bool) -> BTreeMap<String, f64> {
+        self.values(modified_only)
+    }
+
+    #[getter]
+    fn get_damage(&self) -> f64 {
+        self.value(WeaponField::Damage)
+    }
+
+    #[setter]
+    fn set_damage(&self, value: f64) {
+        self.set_value(WeaponField::Damage, value);
+    }
+
+    #[getter]
+    fn get_range(&self) -> f64 {
+        self.value(WeaponField::Range)
+    }
+
+    #[setter]
+    fn set_range(&self, value: f64) {
+        self.set_value(WeaponField::Range, value);
+    }
+
+    #[getter]
+    fn get_firing_rate(&self) -> f64 {
+        self.value(WeaponField::FiringRate)
+    }
+
+    #[setter]
+    fn set_firing_rate(&self, value: f64) {
+        self.set_value(WeaponField::FiringRate, value);
+    }
+
+    #[getter]
+    fn get_reload(&self) -> f64 {
+        self.value(WeaponField::Reload)
+    }
+
+    #[setter]
+    fn set_reload(&self, value: f64) {
+        self.set_value(WeaponField::Reload, value);
+    }
+
+    #[getter]
+    fn get_clip_size(&self) -> f64 {
+        self.value(WeaponField::ClipSize)
+    }
+
+    #[setter]
+    fn set_clip_size(&self, value: f64) {
+        self.set_value(WeaponField::ClipSize, value);
+    }
+
+    #[getter]
+    fn get_speed(&self) -> f64 {
+        self.value(WeaponField::Speed)
+    }
+
+    #[setter]
+    fn set_speed(&self, value: f64) {
+        self.set_value(WeaponField::Speed, value);
+    }
+
+    #[getter]
+    fn get_radius(&self) -> f64 {
+        self.value(WeaponField::Radius)
+    }
+
+    #[setter]
+    fn set_radius(&self, value: f64) {
+        self.set_value(WeaponField::Radius, value);
+    }
+
+    #[getter]
+    fn get_life_span(&self) -> f64 {
+        self.value(WeaponField::LifeSpan)
+    }
+
+    #[setter]
+    fn set_life_span(&self, value: f64) {
+        self.set_value(WeaponField::LifeSpan, value);
+    }
+
+    #[getter]
+    fn get_spread(&self) -> f64 {
+        self.value(WeaponField::Spread)
+    }
+
+    #[setter]
+    fn set_spread(&self, value: f64) {
+        self.set_value(WeaponField::Spread, value);
+    }
+
+    #[getter]
+    fn get_slot(&self) -> f64 {
+        self.value(WeaponField::Slot)
+    }
+
+    #[setter]
+    fn set_slot(&self, value: f64) {
+        self.set_value(WeaponField::Slot, value);
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "WeaponData(weapon_id={}, damage={}, range={}, firing_rate={}, clip_size={})",
+            self.weapon_id,
+            self.get_damage(),
+            self.get_range(),
+            self.get_firing_rate(),
+            self.get_clip_size()
+        )
+    }
+}
+
+/// 一把武器的字段，key 是 [`WeaponField::key`]
+type WeaponTable = BTreeMap<String, f64>;
+
+fn weapon_ids() -> impl Iterator<Item = i32> {
+    WEAPON_NAMES
+        .iter()
+        .map(|(id, _)| *id)
+        .filter(|id| *id <= MAX_WEAPON_DATA_ID)
+}
+
+/// 预设里的 key 可以是武器 id 也可以是武器名字，只认有武器数据的武器
+fn parse_weapon_key(key: &str) -> PyResult<i32> {
+    let weapon_id = match key.parse::<i32>() {
+        Ok(id) => id,
+        Err(_) => WeaponPy::from_name(key).map(i32::from)?,
+    };
+    if !weapon_ids().any(|id| id == weapon_id) {
+        return Err(PyValueError::new_err(format!(
+            "Weapon {key} has no weapon data"
+        )));
+    }
+    Ok(weapon_id)
+}
+
+#[pyfunction]
+pub fn get_weapon_data(weapon: i32) -> WeaponDataPy {
+    WeaponDataPy::new(weapon)
+}
+
+#[pyfunction]
+pub fn reset_weapon_data(weapon: i32) {
+    let _ = vcmp_func().reset_weapon_data(weapon);
+}
+
+#[pyfunction]
+pub fn reset_all_weapon_data() {
+    let _ = vcmp_func().reset_all_weapon_data();
+}
+
+/// 导出武器预设，传了 path 就顺便写到文件里，format 是 "toml" 或者 "json"
+///
+/// ```toml
+/// [weapons.M4]
+/// damage = 40.0
+/// range = 90.0
+/// ```
+#[pyfunction]
+#[pyo3(signature = (path = None, modified_only = true, format = "toml"))]
+pub fn export_weapon_preset(
+    py: Python<'_>,
+    path: Option<String>,
+    modified_only: bool,
+    format: &str,
+) -> PyResult<String> {
+    let mut preset: BTreeMap<String, WeaponTable> = BTreeMap::new();
+    for weapon_id in weapon_ids() {
+        let values = WeaponDataPy::new(weapon_id).values(modified_only);
+        if values.is_empty() {
+            continue;
+        }
+        let key = WeaponPy::try_from(weapon_id)
+            .map(|weapon| weapon.get_name().to_string())
+            .unwrap_or(weapon_id.to_string());
+        preset.insert(key, values);
+    }
+    let mut root = BTreeMap::new();
+    root.insert("weapons".to_string(), preset);
+    let content = dumps(py, root, format)?;
+    if let Some(path) = path {
+        std::fs::write(&path, &content).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    }
+    Ok(content)
+}
+
+/// 从字符串导入，返回改了多少个字段
+#[pyfunction]
+#[pyo3(signature = (content, reset = false, format = "toml"))]
+pub fn load_weapon_preset(
+    py: Python<'_>,
+    content: &str,
+    reset: bool,
+    format: &str,
+) -> PyResult<usize> {
+    let root = loads::<BTreeMap<String, BTreeMap<String, WeaponTable>>>(py, content, format)?;
+    let preset = root.get("weapons").cloned().unwrap_or_default();
+
+    // 先全部检查一遍，不要导入到一半才报错
+    let mut changes = Vec::new();
+    for (key, values) in &preset {
+        let weapon_id = parse_weapon_key(key)?;
+        for (field, value) in values {
+            let field = WeaponField::from_key(field).ok_or_else(|| {
+                PyValueError::new_err(format!("Unknown weapon field: {key}.{field}"))
+            })?;
+            changes.push((weapon_id, field, *value));
+        }
+    }
+
+    if reset {
+        reset_all_weapon_data();
+    }
+    for (weapon_id, field, value) in &changes {
+        WeaponDataPy::new(*weapon_id).set_value(*field, *value);
+    }
+    Ok(changes.len())
+}
+
+/// 从文件导入，format 不传的话按后缀名判断
+#[pyfunction]
+#[pyo3(signature = (path, reset = false, format = None))]
+pub fn import_weapon_preset(
+    py: Python<'_>,
+    path: &str,
+    reset: bool,
+    format: Option<&str>,
+) -> PyResult<usize> {
+    let content = std::fs::read_to_string(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let format = format.unwrap_or(if path.ends_with(".json") {
+        "json"
+    } else {
+        "toml"
+    });
+    load_weapon_preset(py, &content, reset, format)
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<WeaponField>()?;
+    m.add_class::<WeaponDataPy>()?;
+    m.add_function(wrap_pyfunction!(get_weapon_data, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_weapon_data, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_all_weapon_data, m)?)?;
+    m.add_function(wrap_pyfunction!(export_weapon_preset, m)?)?;
+    m.add_function(wrap_pyfunction!(load_weapon_preset, m)?)?;
+    m.add_function(wrap_pyfunction!(import_weapon_preset, m)?)?;
+    Ok(())
+}
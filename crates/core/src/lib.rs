@@ -11,9 +11,11 @@ pub mod consts;
 pub mod functions;
 
 pub mod cfg;
+pub mod damage;
 pub mod pool;
 pub mod py;
 pub mod update;
+pub mod watcher;
 
 /// 插件版本
 ///
@@ -31,7 +33,7 @@ pub const PLUGIN_NAME: &str = "PythonPlugin";
 
 use crate::{
     callbacks::init_callbacks,
-    cfg::{get_check_update, init_config},
+    cfg::{get_check_update, get_watch, init_config},
     py::init_py,
 };
 
@@ -124,5 +126,9 @@ extern "C" fn VcmpPluginInit(
         update::init();
     }
 
+    if get_watch() {
+        watcher::init();
+    }
+
     1
 }
@@ -1,11 +1,14 @@
 use std::{
-    collections::HashMap,
     fmt::Debug,
-    sync::{LazyLock, Mutex},
+    sync::{
+        LazyLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
+use pyo3::{PyResult, exceptions::PyValueError};
 use tracing::{Level, event};
-use vcmp_bindings::options::VcmpEntityPool;
+use vcmp_bindings::{func::PlayerMethods, options::VcmpEntityPool, vcmp_func};
 
 use crate::{
     consts::EntityId,
@@ -20,12 +23,16 @@ pub trait EntityPoolTrait: Debug + Clone {
     fn entity_id(&self) -> EntityId;
 }
 
+/// 单类实体池
+///
+/// 用 id 当下标的槽位数组，vcmp 的 id 都是从 0 开始的小整数，查找不需要 hash
 #[derive(Debug, Clone)]
 pub struct AnEntityPool<E>
 where
     E: EntityPoolTrait,
 {
-    pool: HashMap<EntityId, E>,
+    slots: Vec<Option<E>>,
+    len: usize,
 }
 
 impl<E: EntityPoolTrait> AnEntityPool<E> {
@@ -33,16 +40,38 @@ impl<E: EntityPoolTrait> AnEntityPool<E> {
         E::entity_pool_type()
     }
 
+    fn slot_index(entity_id: EntityId) -> Option<usize> {
+        usize::try_from(entity_id).ok()
+    }
+
     pub fn add_entity(&mut self, entity: E) {
-        self.pool.insert(entity.entity_id(), entity);
+        let Some(index) = Self::slot_index(entity.entity_id()) else {
+            event!(
+                Level::ERROR,
+                "Invalid entity id: {:?}({})",
+                E::entity_pool_type(),
+                entity.entity_id()
+            );
+            return;
+        };
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        if self.slots[index].replace(entity).is_none() {
+            self.len += 1;
+        }
     }
 
     pub fn remove_entity(&mut self, entity_id: EntityId) {
-        self.pool.remove(&entity_id);
+        if let Some(slot) = Self::slot_index(entity_id).and_then(|i| self.slots.get_mut(i))
+            && slot.take().is_some()
+        {
+            self.len -= 1;
+        }
     }
 
     pub fn have_entity(&self, entity_id: EntityId) -> bool {
-        self.pool.contains_key(&entity_id)
+        self.get_entity(entity_id).is_some()
     }
 
     pub fn insert_raw_entity(&mut self, entity: impl Into<E>) {
@@ -50,21 +79,52 @@ impl<E: EntityPoolTrait> AnEntityPool<E> {
     }
 
     pub fn get_entity(&self, entity_id: EntityId) -> Option<&E> {
-        self.pool.get(&entity_id)
+        Self::slot_index(entity_id)
+            .and_then(|i| self.slots.get(i))
+            .and_then(Option::as_ref)
     }
 
     pub fn get_mut_entity(&mut self, entity_id: EntityId) -> Option<&mut E> {
-        self.pool.get_mut(&entity_id)
+        Self::slot_index(entity_id)
+            .and_then(|i| self.slots.get_mut(i))
+            .and_then(Option::as_mut)
     }
 
+    /// 按 id 顺序遍历，不会 clone
     pub fn entities(&self) -> impl Iterator<Item = &E> {
-        self.pool.values()
+        self.slots.iter().flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     pub fn new() -> Self {
         Self {
-            pool: HashMap::new(),
+            slots: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<E: EntityPoolTrait + From<EntityId>> AnEntityPool<E> {
+    /// 获取实体，如果实体池里没有就补一个
+    ///
+    /// 只给服务器的回调用（服务器发来了这个 id 的事件，说明实体还在），
+    /// 正常情况下不会发生，发生了说明漏了某个 entity pool change
+    ///
+    /// 只有 id 为负数（无效）的时候才会返回 None
+    pub fn get_mut_entity_or_insert(&mut self, entity_id: EntityId) -> Option<&mut E> {
+        let index = Self::slot_index(entity_id)?;
+        if !self.have_entity(entity_id) {
+            record_desync(E::entity_pool_type(), entity_id);
+            self.insert_raw_entity(entity_id);
         }
+        self.slots.get_mut(index).and_then(Option::as_mut)
     }
 }
 
@@ -173,6 +233,14 @@ impl EntityPool {
         self.vehicles.get_mut_entity(vehicle_id)
     }
 
+    pub fn get_mut_player_or_insert(&mut self, player_id: EntityId) -> Option<&mut PlayerPy> {
+        self.players.get_mut_entity_or_insert(player_id)
+    }
+
+    pub fn get_mut_vehicle_or_insert(&mut self, vehicle_id: EntityId) -> Option<&mut VehiclePy> {
+        self.vehicles.get_mut_entity_or_insert(vehicle_id)
+    }
+
     pub fn get_object(&self, object_id: EntityId) -> Option<&ObjectPy> {
         self.objects.get_entity(object_id)
     }
@@ -189,41 +257,183 @@ impl EntityPool {
         self.checkpoints.get_entity(checkpoint_id)
     }
 
-    pub fn get_players(&self) -> Vec<PlayerPy> {
+    // 迭代器，不 clone 整个池子
+
+    /// 已加入的玩家（重载的时候会暂时排除掉）
+    pub fn players(&self) -> impl Iterator<Item = &PlayerPy> {
         self.players
             .entities()
-            .cloned()
             .filter(|p| p.get_var_reload_joined())
-            .collect()
+    }
+
+    pub fn all_players(&self) -> impl Iterator<Item = &PlayerPy> {
+        self.players.entities()
+    }
+
+    pub fn vehicles(&self) -> impl Iterator<Item = &VehiclePy> {
+        self.vehicles.entities()
+    }
+
+    pub fn objects(&self) -> impl Iterator<Item = &ObjectPy> {
+        self.objects.entities()
+    }
+
+    pub fn pickups(&self) -> impl Iterator<Item = &PickupPy> {
+        self.pickups.entities()
+    }
+
+    pub fn markers(&self) -> impl Iterator<Item = &MarkerPy> {
+        self.markers.entities()
+    }
+
+    pub fn checkpoints(&self) -> impl Iterator<Item = &CheckPointPy> {
+        self.checkpoints.entities()
+    }
+
+    // 给 python 用的，需要一份拷贝
+
+    pub fn get_players(&self) -> Vec<PlayerPy> {
+        self.players().copied().collect()
     }
 
     pub fn get_all_players(&self) -> Vec<PlayerPy> {
-        self.players.entities().cloned().collect()
+        self.all_players().copied().collect()
     }
 
     pub fn get_vehicles(&self) -> Vec<VehiclePy> {
-        self.vehicles.entities().cloned().collect()
+        self.vehicles().copied().collect()
     }
 
     pub fn get_objects(&self) -> Vec<ObjectPy> {
-        self.objects.entities().cloned().collect()
+        self.objects().copied().collect()
     }
 
     pub fn get_pickups(&self) -> Vec<PickupPy> {
-        self.pickups.entities().cloned().collect()
+        self.pickups().copied().collect()
     }
 
     pub fn get_markers(&self) -> Vec<MarkerPy> {
-        self.markers.entities().cloned().collect()
+        self.markers().copied().collect()
     }
 
     pub fn get_checkpoints(&self) -> Vec<CheckPointPy> {
-        self.checkpoints.entities().cloned().collect()
+        self.checkpoints().copied().collect()
     }
 }
 
 /// 全局实体列表
 ///
-/// Thread safe
-pub static ENTITY_POOL: LazyLock<Mutex<EntityPool>> =
-    LazyLock::new(|| Mutex::new(EntityPool::default()));
+/// 读多写少：只有 entity pool change 和派生事件的 last_* 变量需要写锁
+pub static ENTITY_POOL: LazyLock<RwLock<EntityPool>> =
+    LazyLock::new(|| RwLock::new(EntityPool::default()));
+
+/// 读锁，锁被 panic 污染了也照样用，回调里不能再 panic
+pub fn read_pool() -> RwLockReadGuard<'static, EntityPool> {
+    ENTITY_POOL.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 写锁，同 [`read_pool`]
+pub fn write_pool() -> RwLockWriteGuard<'static, EntityPool> {
+    ENTITY_POOL.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 读锁拿一份拷贝，池子里没有就返回一个只有 id 的实体，不会往池子里补
+fn fetch_entity<E: Copy + From<EntityId>>(
+    entity_id: EntityId,
+    get: impl Fn(&EntityPool) -> Option<&E>,
+) -> E {
+    get(&read_pool())
+        .copied()
+        .unwrap_or_else(|| E::from(entity_id))
+}
+
+pub fn fetch_player(player_id: EntityId) -> PlayerPy {
+    fetch_entity(player_id, |pool| pool.get_player(player_id))
+}
+
+pub fn fetch_vehicle(vehicle_id: EntityId) -> VehiclePy {
+    fetch_entity(vehicle_id, |pool| pool.get_vehicle(vehicle_id))
+}
+
+pub fn fetch_object(object_id: EntityId) -> ObjectPy {
+    fetch_entity(object_id, |pool| pool.get_object(object_id))
+}
+
+pub fn fetch_pickup(pickup_id: EntityId) -> PickupPy {
+    fetch_entity(pickup_id, |pool| pool.get_pickup(pickup_id))
+}
+
+pub fn fetch_checkpoint(checkpoint_id: EntityId) -> CheckPointPy {
+    fetch_entity(checkpoint_id, |pool| pool.get_checkpoint(checkpoint_id))
+}
+
+/// 给 python 的 getter 用，实体已经没了就报错
+fn lookup_entity<E: Copy>(
+    entity_type: VcmpEntityPool,
+    entity_id: EntityId,
+    get: impl Fn(&EntityPool) -> Option<&E>,
+) -> PyResult<E> {
+    get(&read_pool()).copied().ok_or_else(|| {
+        PyValueError::new_err(format!("{entity_type:?}({entity_id}) no longer exists"))
+    })
+}
+
+/// 池子里没有但服务器说还连着的话算一次不同步，返回只有 id 的玩家，不往池子里补
+pub fn lookup_player(player_id: EntityId) -> PyResult<PlayerPy> {
+    if let Some(player) = read_pool().get_player(player_id) {
+        return Ok(*player);
+    }
+    if vcmp_func().is_player_connected(player_id) {
+        record_desync(VcmpEntityPool::Player, player_id);
+        return Ok(PlayerPy::from(player_id));
+    }
+    Err(PyValueError::new_err(format!(
+        "Player({player_id}) is not connected"
+    )))
+}
+
+pub fn lookup_vehicle(vehicle_id: EntityId) -> PyResult<VehiclePy> {
+    lookup_entity(VcmpEntityPool::Vehicle, vehicle_id, |pool| {
+        pool.get_vehicle(vehicle_id)
+    })
+}
+
+pub fn lookup_object(object_id: EntityId) -> PyResult<ObjectPy> {
+    lookup_entity(VcmpEntityPool::Object, object_id, |pool| {
+        pool.get_object(object_id)
+    })
+}
+
+pub fn lookup_pickup(pickup_id: EntityId) -> PyResult<PickupPy> {
+    lookup_entity(VcmpEntityPool::Pickup, pickup_id, |pool| {
+        pool.get_pickup(pickup_id)
+    })
+}
+
+pub fn lookup_checkpoint(checkpoint_id: EntityId) -> PyResult<CheckPointPy> {
+    lookup_entity(VcmpEntityPool::CheckPoint, checkpoint_id, |pool| {
+        pool.get_checkpoint(checkpoint_id)
+    })
+}
+
+/// 实体池不同步的次数
+///
+/// 服务器回调里找不到对应实体的时候 +1，然后自动补上；
+/// python 读到池子里没有但还连着的玩家也会 +1，但不会补
+pub static POOL_DESYNC_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn record_desync(entity_type: VcmpEntityPool, entity_id: EntityId) {
+    let count = POOL_DESYNC_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+    event!(
+        Level::WARN,
+        "Entity pool desync: {entity_type:?}({entity_id}) not found (total: {count})"
+    );
+}
+
+pub fn get_desync_count() -> usize {
+    POOL_DESYNC_COUNT.load(Ordering::SeqCst)
+}
+
+pub fn reset_desync_count() -> usize {
+    POOL_DESYNC_COUNT.swap(0, Ordering::SeqCst)
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 use pyo3::{
-    exceptions::PyKeyboardInterrupt,
+    exceptions::{PyKeyboardInterrupt, PyRuntimeError},
     prelude::*,
     types::{PyCFunction, PyNone},
 };
@@ -13,9 +13,10 @@ use tracing::{Level, event};
 use vcmp_bindings::{func::ServerMethods, vcmp_func};
 
 use crate::py::{
-    GLOBAL_VAR,
+    error_handler_for,
     events::{PyVcmpEvent, VcmpEvent, VcmpEventType, abc::PyEvent},
     get_traceback,
+    scope::{self, EventRoute},
 };
 
 pub const DEFAULT_CALLBACK_PRIORITY: u16 = 65535;
@@ -33,6 +34,8 @@ pub struct CallbackFunction {
     /// 从 0 开始到最后的 65535
     pub priority: u16,
     pub tag: Option<String>,
+    /// 注册它的脚本入口
+    pub scope: Option<usize>,
 }
 
 #[pymethods]
@@ -61,6 +64,8 @@ impl CallbackFunction {
 #[derive(Default)]
 pub struct PyCallbackStorage {
     pub callbacks: HashMap<VcmpEventType, Vec<CallbackFunction>>,
+    /// 每个脚本入口的事件过滤函数
+    pub filters: HashMap<usize, Py<PyAny>>,
 }
 
 impl PyCallbackStorage {
@@ -70,45 +75,82 @@ impl PyCallbackStorage {
         func: Py<PyAny>,
         priority: u16,
         tag: Option<String>,
+        scope: Option<usize>,
     ) {
-        self.callbacks.entry(event_type).or_default();
+        self.insert(
+            event_type,
+            CallbackFunction {
+                func,
+                priority,
+                tag,
+                scope,
+            },
+        );
+    }
+
+    fn insert(&mut self, event_type: VcmpEventType, handler: CallbackFunction) {
         // 根据优先级来排列
         // 最高的优先，最后的最后来执行
+        let handlers = self.callbacks.entry(event_type).or_default();
+        // fuck CodeGeeX why 1 < 500 ?  [500, 1]
+        let index = handlers
+            .iter()
+            .position(|h| h.priority > handler.priority)
+            .unwrap_or(handlers.len());
+        handlers.insert(index, handler);
+    }
+
+    /// 收起 target 入口注册的回调和过滤函数，None 是全部
+    pub fn take_target(&mut self, target: Option<usize>) -> Self {
+        let Some(scope) = target else {
+            return std::mem::take(self);
+        };
+        let mut taken = Self::default();
+        for (event_type, handlers) in self.callbacks.iter_mut() {
+            let (mine, rest): (Vec<_>, Vec<_>) = std::mem::take(handlers)
+                .into_iter()
+                .partition(|h| h.scope == Some(scope));
+            *handlers = rest;
+            if !mine.is_empty() {
+                taken.callbacks.insert(*event_type, mine);
+            }
+        }
+        if let Some(filter) = self.filters.remove(&scope) {
+            taken.filters.insert(scope, filter);
+        }
+        taken
+    }
 
-        let handlers = self
-            .callbacks
-            .get_mut(&event_type)
-            .expect("get handlers failed");
-        let mut i = 0;
-        while i < handlers.len() {
-            if handlers[i].priority > priority {
-                // fuck CodeGeeX why 1 < 500 ?  [500, 1]
-                handlers.insert(
-                    i,
-                    CallbackFunction {
-                        func,
-                        priority,
-                        tag,
-                    },
-                );
-                return;
+    /// 把 [`Self::take_target`] 收起来的换回去，这期间 target 入口新注册的都丢掉
+    pub fn restore_target(&mut self, taken: Self, target: Option<usize>) {
+        if target.is_none() {
+            *self = taken;
+            return;
+        }
+        drop(self.take_target(target));
+        for (event_type, handlers) in taken.callbacks {
+            for handler in handlers {
+                self.insert(event_type, handler);
             }
-            i += 1;
         }
-        handlers.push(CallbackFunction {
-            func,
-            priority,
-            tag,
-        });
+        self.filters.extend(taken.filters);
     }
 
     pub fn get_handlers(&self, event_type: VcmpEventType) -> Option<&Vec<CallbackFunction>> {
         self.callbacks.get(&event_type)
     }
 
+    /// 是否有注册这个事件的回调，用于跳过可选的状态追踪
+    pub fn has_handlers(&self, event_type: VcmpEventType) -> bool {
+        self.callbacks
+            .get(&event_type)
+            .is_some_and(|handlers| !handlers.is_empty())
+    }
+
     pub fn clear(&mut self) -> usize {
         let count = self.callbacks.values().map(|v| v.len()).sum::<usize>();
         self.callbacks.clear();
+        self.filters.clear();
         count
     }
 
@@ -206,11 +248,16 @@ impl PyCallbackManager {
         let kwargs = event.kwargs;
         let event_type = VcmpEventType::from(event.event_type.clone());
         let event = event.event_type;
-        let storage_handlers = {
+        let (storage_handlers, filters) = {
             let storage = PY_CALLBACK_STORAGE
                 .lock()
                 .expect("Failed to lock callback storage");
-            storage.get_handlers(event_type).cloned()
+            let filters = storage
+                .filters
+                .iter()
+                .map(|(scope, filter)| (*scope, filter.clone_ref(py)))
+                .collect::<HashMap<_, _>>();
+            (storage.get_handlers(event_type).cloned(), filters)
         };
         let handlers = match storage_handlers {
             Some(handlers) => handlers,
@@ -230,7 +277,14 @@ impl PyCallbackManager {
         // convert BaseEvent to set_kwargs and convert origin event
         let _ = py_event.setattr(py, "kwargs", kwargs);
 
+        let mut route = EventRoute::new(&event, &py_event, &filters);
+
         for handler in handlers {
+            if !route.allows(py, handler.scope) {
+                continue;
+            }
+            // 回调里注册的回调也算这个入口的
+            let _scope = scope::enter_scope(handler.scope);
             let func = handler.func.clone();
             match func.call1(py, (py_event.clone(),)) {
                 Ok(res) => {
@@ -254,13 +308,7 @@ impl PyCallbackManager {
                         vcmp_func().shutdown();
                         break;
                     } else {
-                        let error_handler = {
-                            &GLOBAL_VAR
-                                .lock()
-                                .expect("Failed to lock global var")
-                                .error_handler
-                        };
-                        if let Some(error_handler) = error_handler {
+                        if let Some(error_handler) = error_handler_for(py, handler.scope) {
                             match error_handler.call1(py, (e.clone_ref(py),)) {
                                 Ok(_) => {}
                                 Err(handler_err) => {
@@ -309,7 +357,13 @@ impl PyCallbackManager {
             PY_CALLBACK_STORAGE
                 .lock()
                 .expect("Failed to lock callback storage")
-                .register_func(event_type, func.clone(), priority, tag.clone());
+                .register_func(
+                    event_type,
+                    func.clone(),
+                    priority,
+                    tag.clone(),
+                    scope::current_scope(),
+                );
             func
         } else {
             PyCFunction::new_closure(
@@ -325,7 +379,13 @@ impl PyCallbackManager {
                     PY_CALLBACK_STORAGE
                         .lock()
                         .expect("Failed to lock callback storage")
-                        .register_func(event_type, func.clone(), priority, tag.clone());
+                        .register_func(
+                            event_type,
+                            func.clone(),
+                            priority,
+                            tag.clone(),
+                            scope::current_scope(),
+                        );
                     Ok(func)
                 },
             )
@@ -343,8 +403,11 @@ impl PyCallbackManager {
             VcmpEvent::ServerFrame(event) => event.init(py),
             VcmpEvent::ServerPerformanceReport(event) => event.init(py),
             VcmpEvent::ServerReloaded(event) => event.init(py),
+            VcmpEvent::ServerReloadFailed(event) => event.init(py),
+            VcmpEvent::SettingChanged(event) => event.init(py),
             VcmpEvent::IncomingConnection(event) => event.init(py),
             VcmpEvent::ClientScriptData(event) => event.init(py),
+            VcmpEvent::ClientChunkedData(event) => event.init(py),
             VcmpEvent::PlayerConnect(event) => event.init(py),
             VcmpEvent::PlayerDisconnect(event) => event.init(py),
             VcmpEvent::PlayerRequestClass(event) => event.init(py),
@@ -377,6 +440,13 @@ impl PyCallbackManager {
             VcmpEvent::PlayerWeaponChange(event) => event.init(py),
             VcmpEvent::PlayerAmmoChange(event) => event.init(py),
             VcmpEvent::PlayerMove(event) => event.init(py),
+            VcmpEvent::PlayerSkinChange(event) => event.init(py),
+            VcmpEvent::PlayerTeamChange(event) => event.init(py),
+            VcmpEvent::PlayerScoreChange(event) => event.init(py),
+            VcmpEvent::PlayerWorldChange(event) => event.init(py),
+            VcmpEvent::PlayerCashChange(event) => event.init(py),
+            VcmpEvent::PlayerWantedLevelChange(event) => event.init(py),
+            VcmpEvent::PlayerDamage(event) => event.init(py),
             VcmpEvent::PickupPickAttempt(event) => event.init(py),
             VcmpEvent::PickupPicked(event) => event.init(py),
             VcmpEvent::PickupRespawn(event) => event.init(py),
@@ -384,11 +454,19 @@ impl PyCallbackManager {
             VcmpEvent::CheckpointExited(event) => event.init(py),
             VcmpEvent::ObjectShot(event) => event.init(py),
             VcmpEvent::ObjectTouched(event) => event.init(py),
+            VcmpEvent::ObjectAnimationFinished(event) => event.init(py),
             VcmpEvent::VehicleExplode(event) => event.init(py),
             VcmpEvent::VehicleRespawn(event) => event.init(py),
             VcmpEvent::VehicleUpdate(event) => event.init(py),
             VcmpEvent::VehicleMove(event) => event.init(py),
             VcmpEvent::VehicleHealthChange(event) => event.init(py),
+            VcmpEvent::VehicleDamageChange(event) => event.init(py),
+            VcmpEvent::VehicleTyrePop(event) => event.init(py),
+            VcmpEvent::VehiclePartStatusChange(event) => event.init(py),
+            VcmpEvent::VehicleLightsDataChange(event) => event.init(py),
+            VcmpEvent::VehicleSirenChange(event) => event.init(py),
+            VcmpEvent::VehicleDoorsLockedChange(event) => event.init(py),
+            VcmpEvent::VehicleHandlingChange(event) => event.init(py),
             VcmpEvent::Custom(event) => event.init(py),
         }
     }
@@ -461,6 +539,28 @@ impl PyCallbackManager {
         self.register_func(py, VcmpEventType::ServerReloaded, func, priority, tag)
     }
 
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_server_reload_failed(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::ServerReloadFailed, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_setting_changed(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::SettingChanged, func, priority, tag)
+    }
+
     #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
     pub fn on_incoming_connection(
         &self,
@@ -483,6 +583,17 @@ impl PyCallbackManager {
         self.register_func(py, VcmpEventType::ClientScriptData, func, priority, tag)
     }
 
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_client_chunked_data(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::ClientChunkedData, func, priority, tag)
+    }
+
     #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
     pub fn on_player_connect(
         &self,
@@ -841,6 +952,89 @@ impl PyCallbackManager {
         self.register_func(py, VcmpEventType::PlayerMove, func, priority, tag)
     }
 
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_skin_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerSkinChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_team_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerTeamChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_score_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerScoreChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_world_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerWorldChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_cash_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerCashChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_wanted_level_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::PlayerWantedLevelChange,
+            func,
+            priority,
+            tag,
+        )
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_player_damage(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::PlayerDamage, func, priority, tag)
+    }
+
     #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
     pub fn on_pickup_pick_attempt(
         &self,
@@ -918,6 +1112,23 @@ impl PyCallbackManager {
         self.register_func(py, VcmpEventType::ObjectTouched, func, priority, tag)
     }
 
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_object_animation_finished(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::ObjectAnimationFinished,
+            func,
+            priority,
+            tag,
+        )
+    }
+
     #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
     pub fn on_vehicle_explode(
         &self,
@@ -973,6 +1184,107 @@ impl PyCallbackManager {
         self.register_func(py, VcmpEventType::VehicleHealthChange, func, priority, tag)
     }
 
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_damage_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::VehicleDamageChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_tyre_pop(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::VehicleTyrePop, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_part_status_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::VehiclePartStatusChange,
+            func,
+            priority,
+            tag,
+        )
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_lights_data_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::VehicleLightsDataChange,
+            func,
+            priority,
+            tag,
+        )
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_siren_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(py, VcmpEventType::VehicleSirenChange, func, priority, tag)
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_doors_locked_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::VehicleDoorsLockedChange,
+            func,
+            priority,
+            tag,
+        )
+    }
+
+    #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
+    pub fn on_vehicle_handling_change(
+        &self,
+        py: Python<'_>,
+        priority: u16,
+        func: Option<Py<PyAny>>,
+        tag: Option<String>,
+    ) -> Py<PyAny> {
+        self.register_func(
+            py,
+            VcmpEventType::VehicleHandlingChange,
+            func,
+            priority,
+            tag,
+        )
+    }
+
     #[pyo3(signature = (priority = DEFAULT_CALLBACK_PRIORITY, func = None, tag = None))]
     pub fn on_custom(
         &self,
@@ -1004,6 +1316,31 @@ impl PyCallbackManager {
             .expect("Failed to lock PyCallbackStorage");
         storage.remove(callback);
     }
+
+    /// 给当前的脚本入口设置事件过滤函数，func(event) -> bool
+    ///
+    /// 返回 False 的事件这个入口的回调都收不到，传 None 取消。
+    /// 只能在加载入口或者回调里调用，可以当装饰器用
+    #[pyo3(signature = (func))]
+    pub fn set_filter(&self, func: Option<Py<PyAny>>) -> PyResult<Option<Py<PyAny>>> {
+        let Some(scope) = scope::current_scope() else {
+            return Err(PyRuntimeError::new_err(
+                "set_filter must be called while a script entry is running",
+            ));
+        };
+        let mut storage = PY_CALLBACK_STORAGE
+            .lock()
+            .expect("Failed to lock PyCallbackStorage");
+        match &func {
+            Some(func) => {
+                storage.filters.insert(scope, func.clone());
+            }
+            None => {
+                storage.filters.remove(&scope);
+            }
+        }
+        Ok(func)
+    }
 }
 
 /// 全局的 callback 管理器
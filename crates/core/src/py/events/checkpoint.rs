@@ -5,7 +5,7 @@ use pyo3::{
 
 use crate::{
     functions::{checkpoint::CheckPointPy, player::PlayerPy},
-    pool::ENTITY_POOL,
+    pool::{fetch_checkpoint, fetch_player, lookup_checkpoint, lookup_player},
     py::events::abc::{BaseEvent, PyEvent},
 };
 
@@ -42,26 +42,20 @@ pub struct CheckpointEnteredEvent {
 #[pymethods]
 impl CheckpointEnteredEvent {
     #[getter]
-    fn checkpoint(&self) -> CheckPointPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_checkpoint(self.inner.checkpoint_id)
-            .expect("Failed to get checkpoint from entity pool")
+    fn checkpoint(&self) -> PyResult<CheckPointPy> {
+        lookup_checkpoint(self.inner.checkpoint_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "CheckpointEnteredEvent(checkpoint={}, player={})",
-            self.checkpoint(),
-            self.player()
+            fetch_checkpoint(self.inner.checkpoint_id),
+            fetch_player(self.inner.player_id)
         )
     }
 }
@@ -103,26 +97,20 @@ pub struct CheckpointExitedEvent {
 #[pymethods]
 impl CheckpointExitedEvent {
     #[getter]
-    fn checkpoint(&self) -> CheckPointPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_checkpoint(self.inner.checkpoint_id)
-            .expect("Failed to get checkpoint from entity pool")
+    fn checkpoint(&self) -> PyResult<CheckPointPy> {
+        lookup_checkpoint(self.inner.checkpoint_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "CheckpointExitedEvent(checkpoint={}, player={})",
-            self.checkpoint(),
-            self.player()
+            fetch_checkpoint(self.inner.checkpoint_id),
+            fetch_player(self.inner.player_id)
         )
     }
 }
@@ -34,10 +34,13 @@ pub enum VcmpEventType {
 
     // Server Extra
     ServerReloaded,
+    ServerReloadFailed,
+    SettingChanged,
 
     // Player
     IncomingConnection,
     ClientScriptData,
+    ClientChunkedData,
     PlayerConnect,
     PlayerDisconnect,
     PlayerRequestClass,
@@ -72,6 +75,13 @@ pub enum VcmpEventType {
     PlayerWeaponChange,
     PlayerAmmoChange,
     PlayerMove,
+    PlayerSkinChange,
+    PlayerTeamChange,
+    PlayerScoreChange,
+    PlayerWorldChange,
+    PlayerCashChange,
+    PlayerWantedLevelChange,
+    PlayerDamage,
 
     // Pickup
     PickupPickAttempt,
@@ -85,6 +95,7 @@ pub enum VcmpEventType {
     // Object
     ObjectShot,
     ObjectTouched,
+    ObjectAnimationFinished,
 
     // Vehicle
     VehicleExplode,
@@ -94,6 +105,13 @@ pub enum VcmpEventType {
     // Vehicle Extra
     VehicleMove,
     VehicleHealthChange,
+    VehicleDamageChange,
+    VehicleTyrePop,
+    VehiclePartStatusChange,
+    VehicleLightsDataChange,
+    VehicleSirenChange,
+    VehicleDoorsLockedChange,
+    VehicleHandlingChange,
 
     // Custom
     Custom,
@@ -109,10 +127,13 @@ pub enum VcmpEvent {
 
     // Server Extra
     ServerReloaded(server::ServerReloadedEvent),
+    ServerReloadFailed(server::ServerReloadFailedEvent),
+    SettingChanged(server::SettingChangedEvent),
 
     // Player
     IncomingConnection(player::IncomingConnectionEvent),
     ClientScriptData(player::ClientScriptDataEvent),
+    ClientChunkedData(player::ClientChunkedDataEvent),
     PlayerConnect(player::PlayerConnectEvent),
     PlayerDisconnect(player::PlayerDisconnectEvent),
     PlayerRequestClass(player::PlayerRequestClassEvent),
@@ -147,6 +168,13 @@ pub enum VcmpEvent {
     PlayerWeaponChange(player::PlayerWeaponChangeEvent),
     PlayerAmmoChange(player::PlayerAmmoChangeEvent),
     PlayerMove(player::PlayerMoveEvent),
+    PlayerSkinChange(player::PlayerSkinChangeEvent),
+    PlayerTeamChange(player::PlayerTeamChangeEvent),
+    PlayerScoreChange(player::PlayerScoreChangeEvent),
+    PlayerWorldChange(player::PlayerWorldChangeEvent),
+    PlayerCashChange(player::PlayerCashChangeEvent),
+    PlayerWantedLevelChange(player::PlayerWantedLevelChangeEvent),
+    PlayerDamage(player::PlayerDamageEvent),
 
     // pickup
     PickupPickAttempt(pickup::PickupPickAttemptEvent),
@@ -160,6 +188,7 @@ pub enum VcmpEvent {
     // object
     ObjectShot(object::ObjectShotEvent),
     ObjectTouched(object::ObjectTouchedEvent),
+    ObjectAnimationFinished(object::ObjectAnimationFinishedEvent),
 
     // vehicle
     VehicleExplode(vehicle::VehicleExplodeEvent),
@@ -169,6 +198,13 @@ pub enum VcmpEvent {
     // Vehicle Extra
     VehicleMove(vehicle::VehicleMoveEvent),
     VehicleHealthChange(vehicle::VehicleHealthChangeEvent),
+    VehicleDamageChange(vehicle::VehicleDamageChangeEvent),
+    VehicleTyrePop(vehicle::VehicleTyrePopEvent),
+    VehiclePartStatusChange(vehicle::VehiclePartStatusChangeEvent),
+    VehicleLightsDataChange(vehicle::VehicleLightsDataChangeEvent),
+    VehicleSirenChange(vehicle::VehicleSirenChangeEvent),
+    VehicleDoorsLockedChange(vehicle::VehicleDoorsLockedChangeEvent),
+    VehicleHandlingChange(vehicle::VehicleHandlingChangeEvent),
 
     // Custom
     Custom(custom::CustomEvent),
@@ -185,10 +221,13 @@ impl From<VcmpEvent> for VcmpEventType {
 
             // Server Extra
             VcmpEvent::ServerReloaded(_) => Self::ServerReloaded,
+            VcmpEvent::ServerReloadFailed(_) => Self::ServerReloadFailed,
+            VcmpEvent::SettingChanged(_) => Self::SettingChanged,
 
             // Player
             VcmpEvent::IncomingConnection(_) => Self::IncomingConnection,
             VcmpEvent::ClientScriptData(_) => Self::ClientScriptData,
+            VcmpEvent::ClientChunkedData(_) => Self::ClientChunkedData,
             VcmpEvent::PlayerConnect(_) => Self::PlayerConnect,
             VcmpEvent::PlayerDisconnect(_) => Self::PlayerDisconnect,
             VcmpEvent::PlayerRequestClass(_) => Self::PlayerRequestClass,
@@ -223,6 +262,13 @@ impl From<VcmpEvent> for VcmpEventType {
             VcmpEvent::PlayerWeaponChange(_) => Self::PlayerWeaponChange,
             VcmpEvent::PlayerAmmoChange(_) => Self::PlayerAmmoChange,
             VcmpEvent::PlayerMove(_) => Self::PlayerMove,
+            VcmpEvent::PlayerSkinChange(_) => Self::PlayerSkinChange,
+            VcmpEvent::PlayerTeamChange(_) => Self::PlayerTeamChange,
+            VcmpEvent::PlayerScoreChange(_) => Self::PlayerScoreChange,
+            VcmpEvent::PlayerWorldChange(_) => Self::PlayerWorldChange,
+            VcmpEvent::PlayerCashChange(_) => Self::PlayerCashChange,
+            VcmpEvent::PlayerWantedLevelChange(_) => Self::PlayerWantedLevelChange,
+            VcmpEvent::PlayerDamage(_) => Self::PlayerDamage,
 
             // Pickup
             VcmpEvent::PickupPickAttempt(_) => Self::PickupPickAttempt,
@@ -236,6 +282,7 @@ impl From<VcmpEvent> for VcmpEventType {
             // Object
             VcmpEvent::ObjectShot(_) => Self::ObjectShot,
             VcmpEvent::ObjectTouched(_) => Self::ObjectTouched,
+            VcmpEvent::ObjectAnimationFinished(_) => Self::ObjectAnimationFinished,
 
             // Vehicle
             VcmpEvent::VehicleExplode(_) => Self::VehicleExplode,
@@ -245,6 +292,13 @@ impl From<VcmpEvent> for VcmpEventType {
             // Vehicle Extra
             VcmpEvent::VehicleMove(_) => Self::VehicleMove,
             VcmpEvent::VehicleHealthChange(_) => Self::VehicleHealthChange,
+            VcmpEvent::VehicleDamageChange(_) => Self::VehicleDamageChange,
+            VcmpEvent::VehicleTyrePop(_) => Self::VehicleTyrePop,
+            VcmpEvent::VehiclePartStatusChange(_) => Self::VehiclePartStatusChange,
+            VcmpEvent::VehicleLightsDataChange(_) => Self::VehicleLightsDataChange,
+            VcmpEvent::VehicleSirenChange(_) => Self::VehicleSirenChange,
+            VcmpEvent::VehicleDoorsLockedChange(_) => Self::VehicleDoorsLockedChange,
+            VcmpEvent::VehicleHandlingChange(_) => Self::VehicleHandlingChange,
 
             // Custom
             VcmpEvent::Custom(_) => Self::Custom,
@@ -330,6 +384,20 @@ impl PyVcmpEvent {
         )))
     }
 
+    #[staticmethod]
+    fn server_reload_failed(traceback: String) -> Self {
+        Self::new(VcmpEvent::ServerReloadFailed(
+            server::ServerReloadFailedEvent::new(traceback),
+        ))
+    }
+
+    #[staticmethod]
+    fn setting_changed(name: String, old_value: Py<PyAny>, new_value: Py<PyAny>) -> Self {
+        Self::new(VcmpEvent::SettingChanged(server::SettingChangedEvent::new(
+            name, old_value, new_value,
+        )))
+    }
+
     #[staticmethod]
     fn checkpoint_entered(checkpoint: CheckPointPy, player: PlayerPy) -> Self {
         Self::new(VcmpEvent::CheckpointEntered(
@@ -358,6 +426,13 @@ impl PyVcmpEvent {
         )))
     }
 
+    #[staticmethod]
+    fn object_animation_finished(object: ObjectPy, tag: Option<String>) -> Self {
+        Self::new(VcmpEvent::ObjectAnimationFinished(
+            object::ObjectAnimationFinishedEvent::new(object, tag),
+        ))
+    }
+
     #[staticmethod]
     fn pickup_pick_attempt(pickup: PickupPy, player: PlayerPy) -> Self {
         Self::new(VcmpEvent::PickupPickAttempt(
@@ -393,6 +468,13 @@ impl PyVcmpEvent {
         ))
     }
 
+    #[staticmethod]
+    fn client_chunked_data(player: PlayerPy, transfer_id: i32, data: Vec<u8>) -> Self {
+        Self::new(VcmpEvent::ClientChunkedData(
+            player::ClientChunkedDataEvent::new(player, transfer_id, data),
+        ))
+    }
+
     #[staticmethod]
     fn player_connect(player: PlayerPy) -> Self {
         Self::new(VcmpEvent::PlayerConnect(player::PlayerConnectEvent::new(
@@ -620,6 +702,64 @@ impl PyVcmpEvent {
         )))
     }
 
+    #[staticmethod]
+    fn player_skin_change(player: PlayerPy, old_skin: i32, new_skin: i32) -> Self {
+        Self::new(VcmpEvent::PlayerSkinChange(
+            player::PlayerSkinChangeEvent::new(player, old_skin, new_skin),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_team_change(player: PlayerPy, old_team: i32, new_team: i32) -> Self {
+        Self::new(VcmpEvent::PlayerTeamChange(
+            player::PlayerTeamChangeEvent::new(player, old_team, new_team),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_score_change(player: PlayerPy, old_score: i32, new_score: i32) -> Self {
+        Self::new(VcmpEvent::PlayerScoreChange(
+            player::PlayerScoreChangeEvent::new(player, old_score, new_score),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_world_change(player: PlayerPy, old_world: i32, new_world: i32) -> Self {
+        Self::new(VcmpEvent::PlayerWorldChange(
+            player::PlayerWorldChangeEvent::new(player, old_world, new_world),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_cash_change(player: PlayerPy, old_cash: i32, new_cash: i32) -> Self {
+        Self::new(VcmpEvent::PlayerCashChange(
+            player::PlayerCashChangeEvent::new(player, old_cash, new_cash),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_wanted_level_change(
+        player: PlayerPy,
+        old_wanted_level: i32,
+        new_wanted_level: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::PlayerWantedLevelChange(
+            player::PlayerWantedLevelChangeEvent::new(player, old_wanted_level, new_wanted_level),
+        ))
+    }
+
+    #[staticmethod]
+    fn player_damage(
+        player: PlayerPy,
+        attacker: Option<PlayerPy>,
+        weapon: i32,
+        amount: f32,
+    ) -> Self {
+        Self::new(VcmpEvent::PlayerDamage(player::PlayerDamageEvent::new(
+            player, attacker, weapon, amount,
+        )))
+    }
+
     #[staticmethod]
     fn vehicle_update(vehicle: VehiclePy, update_type: i32) -> Self {
         Self::new(VcmpEvent::VehicleUpdate(vehicle::VehicleUpdateEvent::new(
@@ -659,6 +799,102 @@ impl PyVcmpEvent {
         ))
     }
 
+    #[staticmethod]
+    fn vehicle_damage_change(
+        vehicle: VehiclePy,
+        old_damage: u32,
+        new_damage: u32,
+        update_type: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::VehicleDamageChange(
+            vehicle::VehicleDamageChangeEvent::new(vehicle, old_damage, new_damage, update_type),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_tyre_pop(vehicle: VehiclePy, tyre: i32, update_type: i32) -> Self {
+        Self::new(VcmpEvent::VehicleTyrePop(
+            vehicle::VehicleTyrePopEvent::new(vehicle, tyre, update_type),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_part_status_change(
+        vehicle: VehiclePy,
+        part: i32,
+        old_status: bool,
+        new_status: bool,
+        update_type: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::VehiclePartStatusChange(
+            vehicle::VehiclePartStatusChangeEvent::new(
+                vehicle,
+                part,
+                old_status,
+                new_status,
+                update_type,
+            ),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_lights_data_change(
+        vehicle: VehiclePy,
+        old_lights_data: u32,
+        new_lights_data: u32,
+        update_type: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::VehicleLightsDataChange(
+            vehicle::VehicleLightsDataChangeEvent::new(
+                vehicle,
+                old_lights_data,
+                new_lights_data,
+                update_type,
+            ),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_siren_change(
+        vehicle: VehiclePy,
+        old_siren: bool,
+        new_siren: bool,
+        update_type: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::VehicleSirenChange(
+            vehicle::VehicleSirenChangeEvent::new(vehicle, old_siren, new_siren, update_type),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_doors_locked_change(
+        vehicle: VehiclePy,
+        old_doors_locked: bool,
+        new_doors_locked: bool,
+        update_type: i32,
+    ) -> Self {
+        Self::new(VcmpEvent::VehicleDoorsLockedChange(
+            vehicle::VehicleDoorsLockedChangeEvent::new(
+                vehicle,
+                old_doors_locked,
+                new_doors_locked,
+                update_type,
+            ),
+        ))
+    }
+
+    #[staticmethod]
+    fn vehicle_handling_change(
+        vehicle: VehiclePy,
+        rule_index: i32,
+        old_value: f64,
+        new_value: f64,
+    ) -> Self {
+        Self::new(VcmpEvent::VehicleHandlingChange(
+            vehicle::VehicleHandlingChangeEvent::new(vehicle, rule_index, old_value, new_value),
+        ))
+    }
+
     // Custom
     #[staticmethod]
     #[pyo3(signature = (**kwargs))]
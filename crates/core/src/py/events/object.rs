@@ -5,7 +5,7 @@ use pyo3::{
 
 use crate::{
     functions::{object::ObjectPy, player::PlayerPy},
-    pool::ENTITY_POOL,
+    pool::{fetch_object, fetch_player, lookup_object, lookup_player},
     py::events::abc::{BaseEvent, PyEvent},
 };
 
@@ -42,19 +42,13 @@ pub struct ObjectShotEvent {
 #[pymethods]
 impl ObjectShotEvent {
     #[getter]
-    fn object(&self) -> ObjectPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_object(self.inner.object_id)
-            .expect("Failed to get object from entity pool")
+    fn object(&self) -> PyResult<ObjectPy> {
+        lookup_object(self.inner.object_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     #[getter]
@@ -65,8 +59,8 @@ impl ObjectShotEvent {
     fn __repr__(&self) -> String {
         format!(
             "ObjectShotEvent(object={}, player={}, weapon_id={})",
-            self.object(),
-            self.player(),
+            fetch_object(self.inner.object_id),
+            fetch_player(self.inner.player_id),
             self.weapon_id()
         )
     }
@@ -110,26 +104,20 @@ pub struct ObjectTouchedEvent {
 #[pymethods]
 impl ObjectTouchedEvent {
     #[getter]
-    fn object(&self) -> ObjectPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_object(self.inner.object_id)
-            .expect("Failed to get object from entity pool")
+    fn object(&self) -> PyResult<ObjectPy> {
+        lookup_object(self.inner.object_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "ObjectTouchedEvent(object={}, player={})",
-            self.object(),
-            self.player()
+            fetch_object(self.inner.object_id),
+            fetch_player(self.inner.player_id)
         )
     }
 }
@@ -163,9 +151,67 @@ impl PyEvent for ObjectTouchedEvent {
     }
 }
 
+#[derive(Debug, Clone)]
+#[pyclass(extends=ObjectEvent, subclass)]
+pub struct ObjectAnimationFinishedEvent {
+    pub object_id: i32,
+    pub tag: Option<String>,
+}
+#[pymethods]
+impl ObjectAnimationFinishedEvent {
+    #[getter]
+    fn object(&self) -> PyResult<ObjectPy> {
+        lookup_object(self.object_id)
+    }
+
+    #[getter]
+    fn tag(&self) -> Option<String> {
+        self.tag.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ObjectAnimationFinishedEvent(object={}, tag={:?})",
+            fetch_object(self.object_id),
+            self.tag
+        )
+    }
+}
+impl From<(i32, Option<String>)> for ObjectAnimationFinishedEvent {
+    fn from(value: (i32, Option<String>)) -> Self {
+        Self {
+            object_id: value.0,
+            tag: value.1,
+        }
+    }
+}
+impl ObjectAnimationFinishedEvent {
+    pub fn new(object: ObjectPy, tag: Option<String>) -> Self {
+        Self {
+            object_id: object.get_id(),
+            tag,
+        }
+    }
+}
+impl PyEvent for ObjectAnimationFinishedEvent {
+    fn event_name(&self) -> String {
+        "ObjectAnimationFinishedEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(ObjectEvent::new()).add_subclass(self.clone()),
+        )
+        .expect("Failed to create ObjectAnimationFinishedEvent")
+        .into_any()
+    }
+}
+
 pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ObjectEvent>()?;
     m.add_class::<ObjectShotEvent>()?;
     m.add_class::<ObjectTouchedEvent>()?;
+    m.add_class::<ObjectAnimationFinishedEvent>()?;
     Ok(())
 }
@@ -5,7 +5,7 @@ use pyo3::{
 
 use crate::{
     functions::{pickup::PickupPy, player::PlayerPy},
-    pool::ENTITY_POOL,
+    pool::{fetch_pickup, fetch_player, lookup_pickup, lookup_player},
     py::events::abc::{BaseEvent, PyEvent},
 };
 
@@ -42,26 +42,20 @@ pub struct PickupPickAttemptEvent {
 #[pymethods]
 impl PickupPickAttemptEvent {
     #[getter]
-    fn pickup(&self) -> PickupPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_pickup(self.inner.pickup_id)
-            .expect("Failed to get pickup from entity pool")
+    fn pickup(&self) -> PyResult<PickupPy> {
+        lookup_pickup(self.inner.pickup_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PickupPickAttemptEvent(pickup={}, player={})",
-            self.pickup(),
-            self.player(),
+            fetch_pickup(self.inner.pickup_id),
+            fetch_player(self.inner.player_id),
         )
     }
 }
@@ -103,26 +97,20 @@ pub struct PickupPickedEvent {
 #[pymethods]
 impl PickupPickedEvent {
     #[getter]
-    fn pickup(&self) -> PickupPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_pickup(self.inner.pickup_id)
-            .expect("Failed to get pickup from entity pool")
+    fn pickup(&self) -> PyResult<PickupPy> {
+        lookup_pickup(self.inner.pickup_id)
     }
 
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PickupPickedEvent(pickup={}, player={})",
-            self.pickup(),
-            self.player()
+            fetch_pickup(self.inner.pickup_id),
+            fetch_player(self.inner.player_id)
         )
     }
 }
@@ -164,15 +152,15 @@ pub struct PickupRespawnEvent {
 #[pymethods]
 impl PickupRespawnEvent {
     #[getter]
-    fn pickup(&self) -> PickupPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_pickup(self.inner.pickup_id)
-            .expect("Failed to get pickup from entity pool")
+    fn pickup(&self) -> PyResult<PickupPy> {
+        lookup_pickup(self.inner.pickup_id)
     }
 
     fn __repr__(&self) -> String {
-        format!("PickupRespawnEvent(pickup={})", self.pickup())
+        format!(
+            "PickupRespawnEvent(pickup={})",
+            fetch_pickup(self.inner.pickup_id)
+        )
     }
 }
 impl From<pickup::PickupRespawnEvent> for PickupRespawnEvent {
@@ -1,16 +1,18 @@
 use pyo3::{
     Bound, Py, PyAny, PyClassInitializer, PyResult, Python, pyclass, pymethods,
-    types::{PyModule, PyModuleMethods},
+    types::{PyBytes, PyModule, PyModuleMethods},
 };
 use vcmp_bindings::events::player;
 
 use crate::{
+    damage::{DamageRecord, get_assist_players, get_damage_ledger},
     functions::{
         keybind::{KeyBindPy, get_bindkey},
         player::PlayerPy,
+        player_class::{ClassPy, get_class},
         vehicle::VehiclePy,
     },
-    pool::ENTITY_POOL,
+    pool::{fetch_player, fetch_vehicle, lookup_player, lookup_vehicle, read_pool, write_pool},
     py::{
         events::abc::{BaseEvent, PyEvent},
         streams::ReadStream,
@@ -108,11 +110,8 @@ pub struct ClientScriptDataEvent {
 #[pymethods]
 impl ClientScriptDataEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn stream(&self) -> ReadStream {
@@ -121,7 +120,7 @@ impl ClientScriptDataEvent {
     fn __repr__(&self) -> String {
         format!(
             "ClientScriptDataEvent(player={}, stream={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.stream()
         )
     }
@@ -156,6 +155,74 @@ impl PyEvent for ClientScriptDataEvent {
     }
 }
 
+/// 分片传输的数据全部收到之后触发
+#[derive(Debug, Clone)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct ClientChunkedDataEvent {
+    pub player_id: i32,
+    pub transfer_id: i32,
+    pub data: Vec<u8>,
+}
+#[pymethods]
+impl ClientChunkedDataEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+    #[getter]
+    fn transfer_id(&self) -> i32 {
+        self.transfer_id
+    }
+    #[getter]
+    fn data<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.data)
+    }
+    #[getter]
+    fn stream(&self) -> ReadStream {
+        ReadStream::from(self.data.clone())
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "ClientChunkedDataEvent(player={}, transfer_id={}, size={})",
+            fetch_player(self.player_id),
+            self.transfer_id,
+            self.data.len()
+        )
+    }
+}
+impl From<(i32, i32, Vec<u8>)> for ClientChunkedDataEvent {
+    fn from(value: (i32, i32, Vec<u8>)) -> Self {
+        Self {
+            player_id: value.0,
+            transfer_id: value.1,
+            data: value.2,
+        }
+    }
+}
+impl ClientChunkedDataEvent {
+    pub fn new(player: PlayerPy, transfer_id: i32, data: Vec<u8>) -> Self {
+        Self {
+            player_id: player.get_id(),
+            transfer_id,
+            data,
+        }
+    }
+}
+impl PyEvent for ClientChunkedDataEvent {
+    fn event_name(&self) -> String {
+        "ClientChunkedDataEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(self.clone()),
+        )
+        .expect("Failed to create ClientChunkedDataEvent")
+        .into_any()
+    }
+}
+
 // PlayerConnectEvent
 #[derive(Debug, Clone)]
 #[pyclass(extends=PlayerEvent, subclass)]
@@ -165,14 +232,14 @@ pub struct PlayerConnectEvent {
 #[pymethods]
 impl PlayerConnectEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     fn __repr__(&self) -> String {
-        format!("PlayerConnectEvent(player={})", self.player())
+        format!(
+            "PlayerConnectEvent(player={})",
+            fetch_player(self.inner.player_id)
+        )
     }
 }
 impl From<player::PlayerConnectEvent> for PlayerConnectEvent {
@@ -213,11 +280,8 @@ pub struct PlayerDisconnectEvent {
 #[pymethods]
 impl PlayerDisconnectEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn reason(&self) -> i32 {
@@ -226,7 +290,7 @@ impl PlayerDisconnectEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerDisconnectEvent(player={}, reason={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.reason()
         )
     }
@@ -270,20 +334,22 @@ pub struct PlayerRequestClassEvent {
 #[pymethods]
 impl PlayerRequestClassEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn class_id(&self) -> i32 {
         self.inner.class_id
     }
+    /// 通过 add_player_class 或者 class 文件添加的才有
+    #[getter]
+    fn player_class(&self) -> Option<ClassPy> {
+        get_class(self.inner.class_id)
+    }
     fn __repr__(&self) -> String {
         format!(
             "PlayerRequestClassEvent(player={}, class_id={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.class_id()
         )
     }
@@ -327,14 +393,14 @@ pub struct PlayerSpawnEvent {
 #[pymethods]
 impl PlayerSpawnEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     fn __repr__(&self) -> String {
-        format!("PlayerSpawnEvent(player={})", self.player())
+        format!(
+            "PlayerSpawnEvent(player={})",
+            fetch_player(self.inner.player_id)
+        )
     }
 }
 impl From<player::PlayerSpawnEvent> for PlayerSpawnEvent {
@@ -375,14 +441,14 @@ pub struct PlayerRequestSpawnEvent {
 #[pymethods]
 impl PlayerRequestSpawnEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     fn __repr__(&self) -> String {
-        format!("PlayerRequestSpawnEvent(player={})", self.player())
+        format!(
+            "PlayerRequestSpawnEvent(player={})",
+            fetch_player(self.inner.player_id)
+        )
     }
 }
 impl From<player::PlayerRequestSpawnEvent> for PlayerRequestSpawnEvent {
@@ -423,15 +489,12 @@ pub struct PlayerDeathEvent {
 #[pymethods]
 impl PlayerDeathEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn killer(&self) -> Option<PlayerPy> {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+        let pool = read_pool();
         pool.get_player(self.inner.killer_id).copied()
     }
     #[getter]
@@ -442,10 +505,21 @@ impl PlayerDeathEvent {
     fn body(&self) -> i32 {
         self.inner.body
     }
+    /// 这条命受到的所有伤害
+    #[getter]
+    fn damage_ledger(&self) -> Vec<DamageRecord> {
+        get_damage_ledger(self.inner.player_id)
+    }
+    /// 助攻者，按伤害总量从高到低，不包括凶手
+    #[pyo3(signature = (min_damage = 0.0))]
+    fn get_assists(&self, min_damage: f32) -> Vec<PlayerPy> {
+        let killer_id = self.killer().map(|k| k.get_id());
+        get_assist_players(self.inner.player_id, killer_id, min_damage)
+    }
     fn __repr__(&self) -> String {
         format!(
             "PlayerDeathEvent(player={}, killer={}, reason={}, body={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.killer()
                 .map(|k| format!("{k}"))
                 .unwrap_or("None".to_string()),
@@ -495,11 +569,8 @@ pub struct PlayerUpdateEvent {
 #[pymethods]
 impl PlayerUpdateEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn update(&self) -> i32 {
@@ -508,7 +579,7 @@ impl PlayerUpdateEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerUpdateEvent(player={}, update={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.update()
         )
     }
@@ -552,18 +623,12 @@ pub struct PlayerRequestEnterVehicleEvent {
 #[pymethods]
 impl PlayerRequestEnterVehicleEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
     #[getter]
     fn slot_index(&self) -> i32 {
@@ -572,8 +637,8 @@ impl PlayerRequestEnterVehicleEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerRequestEnterVehicleEvent(player={}, vehicle={}, slot_index={})",
-            self.player(),
-            self.vehicle(),
+            fetch_player(self.inner.player_id),
+            fetch_vehicle(self.inner.vehicle_id),
             self.slot_index()
         )
     }
@@ -618,18 +683,12 @@ pub struct PlayerEnterVehicleEvent {
 #[pymethods]
 impl PlayerEnterVehicleEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
     #[getter]
     fn slot_index(&self) -> i32 {
@@ -638,8 +697,8 @@ impl PlayerEnterVehicleEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerEnterVehicleEvent(player={}, vehicle={}, slot_index={})",
-            self.player(),
-            self.vehicle(),
+            fetch_player(self.inner.player_id),
+            fetch_vehicle(self.inner.vehicle_id),
             self.slot_index()
         )
     }
@@ -684,24 +743,18 @@ pub struct PlayerExitVehicleEvent {
 #[pymethods]
 impl PlayerExitVehicleEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
     fn __repr__(&self) -> String {
         format!(
             "PlayerExitVehicleEvent(player={}, vehicle={})",
-            self.player(),
-            self.vehicle()
+            fetch_player(self.inner.player_id),
+            fetch_vehicle(self.inner.vehicle_id)
         )
     }
 }
@@ -744,11 +797,8 @@ pub struct PlayerNameChangeEvent {
 #[pymethods]
 impl PlayerNameChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn old_name(&self) -> String {
@@ -761,7 +811,7 @@ impl PlayerNameChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerNameChangeEvent(player={}, old_name={}, new_name={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.old_name(),
             self.new_name()
         )
@@ -807,11 +857,8 @@ pub struct PlayerStateChangeEvent {
 #[pymethods]
 impl PlayerStateChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn old_state(&self) -> i32 {
@@ -824,7 +871,7 @@ impl PlayerStateChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerStateChangeEvent(player={}, old_state={}, new_state={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.old_state(),
             self.new_state()
         )
@@ -870,11 +917,8 @@ pub struct PlayerActionChangeEvent {
 #[pymethods]
 impl PlayerActionChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn old_action(&self) -> i32 {
@@ -887,7 +931,7 @@ impl PlayerActionChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerActionChangeEvent(player={}, old_action={}, new_action={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.old_action(),
             self.new_action()
         )
@@ -933,11 +977,8 @@ pub struct PlayerOnFireChangeEvent {
 #[pymethods]
 impl PlayerOnFireChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn is_on_fire(&self) -> bool {
@@ -946,7 +987,7 @@ impl PlayerOnFireChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerOnFireChangeEvent(player={}, is_on_fire={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.is_on_fire()
         )
     }
@@ -990,11 +1031,8 @@ pub struct PlayerCrouchChangeEvent {
 #[pymethods]
 impl PlayerCrouchChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn is_crouching(&self) -> bool {
@@ -1003,7 +1041,7 @@ impl PlayerCrouchChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerCrouchChangeEvent(player={}, is_crouching={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.is_crouching()
         )
     }
@@ -1047,11 +1085,8 @@ pub struct PlayerGameKeysChangeEvent {
 #[pymethods]
 impl PlayerGameKeysChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn old_keys(&self) -> u32 {
@@ -1064,7 +1099,7 @@ impl PlayerGameKeysChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerGameKeysChangeEvent(player={}, old_keys={}, new_keys={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.old_keys(),
             self.new_keys()
         )
@@ -1110,14 +1145,14 @@ pub struct PlayerBeginTypingEvent {
 #[pymethods]
 impl PlayerBeginTypingEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     fn __repr__(&self) -> String {
-        format!("PlayerBeginTypingEvent(player={})", self.player())
+        format!(
+            "PlayerBeginTypingEvent(player={})",
+            fetch_player(self.inner.player_id)
+        )
     }
 }
 impl From<player::PlayerBeginTypingEvent> for PlayerBeginTypingEvent {
@@ -1158,14 +1193,14 @@ pub struct PlayerEndTypingEvent {
 #[pymethods]
 impl PlayerEndTypingEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     fn __repr__(&self) -> String {
-        format!("PlayerEndTypingEvent(player={})", self.player())
+        format!(
+            "PlayerEndTypingEvent(player={})",
+            fetch_player(self.inner.player_id)
+        )
     }
 }
 impl From<player::PlayerEndTypingEvent> for PlayerEndTypingEvent {
@@ -1206,11 +1241,8 @@ pub struct PlayerAwayChangeEvent {
 #[pymethods]
 impl PlayerAwayChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn is_away(&self) -> bool {
@@ -1219,7 +1251,7 @@ impl PlayerAwayChangeEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerAwayChangeEvent(player={}, is_away={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.is_away()
         )
     }
@@ -1263,11 +1295,8 @@ pub struct PlayerMessageEvent {
 #[pymethods]
 impl PlayerMessageEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn message(&self) -> String {
@@ -1276,7 +1305,7 @@ impl PlayerMessageEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerMessageEvent(player={}, message='{}')",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.message()
         )
     }
@@ -1320,11 +1349,8 @@ pub struct PlayerCommandEvent {
 #[pymethods]
 impl PlayerCommandEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn command(&self) -> String {
@@ -1347,7 +1373,7 @@ impl PlayerCommandEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerCommandEvent(player={}, command='{}', text='{}')",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.command(),
             self.text()
         )
@@ -1393,18 +1419,12 @@ pub struct PlayerPrivateMessageEvent {
 #[pymethods]
 impl PlayerPrivateMessageEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
-    fn target(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.target_id)
-            .expect("Failed to get player from entity pool")
+    fn target(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.target_id)
     }
     #[getter]
     fn message(&self) -> String {
@@ -1413,8 +1433,8 @@ impl PlayerPrivateMessageEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerPrivateMessageEvent(player={}, target={}, message='{}')",
-            self.player(),
-            self.target(),
+            fetch_player(self.inner.player_id),
+            fetch_player(self.inner.target_id),
             self.message()
         )
     }
@@ -1459,11 +1479,8 @@ pub struct PlayerKeyBindDownEvent {
 #[pymethods]
 impl PlayerKeyBindDownEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn key(&self) -> KeyBindPy {
@@ -1472,7 +1489,7 @@ impl PlayerKeyBindDownEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerKeyBindDownEvent(player={}, key={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.key()
         )
     }
@@ -1516,11 +1533,8 @@ pub struct PlayerKeyBindUpEvent {
 #[pymethods]
 impl PlayerKeyBindUpEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn key(&self) -> KeyBindPy {
@@ -1529,7 +1543,7 @@ impl PlayerKeyBindUpEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerKeyBindUpEvent(player={}, key={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.key()
         )
     }
@@ -1573,21 +1587,18 @@ pub struct PlayerSpectateEvent {
 #[pymethods]
 impl PlayerSpectateEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn target(&self) -> Option<PlayerPy> {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+        let pool = read_pool();
         pool.get_player(self.inner.target_id).copied()
     }
     fn __repr__(&self) -> String {
         format!(
             "PlayerSpectateEvent(player={}, target={})",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.target()
                 .map(|t| format!("{t}"))
                 .unwrap_or("None".to_string())
@@ -1633,11 +1644,8 @@ pub struct PlayerCrashReportEvent {
 #[pymethods]
 impl PlayerCrashReportEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn report(&self) -> String {
@@ -1646,7 +1654,7 @@ impl PlayerCrashReportEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerCrashReportEvent(player={}, report='{}')",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.report()
         )
     }
@@ -1690,11 +1698,8 @@ pub struct PlayerModuleListEvent {
 #[pymethods]
 impl PlayerModuleListEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.inner.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.inner.player_id)
     }
     #[getter]
     fn modules(&self) -> String {
@@ -1703,7 +1708,7 @@ impl PlayerModuleListEvent {
     fn __repr__(&self) -> String {
         format!(
             "PlayerModuleListEvent(player={}, modules='{}')",
-            self.player(),
+            fetch_player(self.inner.player_id),
             self.modules()
         )
     }
@@ -1751,11 +1756,8 @@ pub struct PlayerHealthChangeEvent {
 #[pymethods]
 impl PlayerHealthChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
     }
 
     #[getter]
@@ -1777,18 +1779,17 @@ impl PlayerHealthChangeEvent {
     fn set_current_health(&mut self, health: f32) {
         self.current_health = health;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let player = pool
-                .get_mut_player(self.player_id)
-                .expect("Failed to get player from entity pool");
-            player.set_var_last_health(self.current_health);
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_health(self.current_health);
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PlayerHealthChangeEvent(player={}, old_health={}, new_health={})",
-            self.player(),
+            fetch_player(self.player_id),
             self.get_old_health(),
             self.get_new_health()
         )
@@ -1844,11 +1845,8 @@ pub struct PlayerArmourChangeEvent {
 #[pymethods]
 impl PlayerArmourChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
     }
 
     #[getter]
@@ -1870,18 +1868,17 @@ impl PlayerArmourChangeEvent {
     fn set_current_armour(&mut self, armour: f32) {
         self.current_armour = armour;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let player = pool
-                .get_mut_player(self.player_id)
-                .expect("Failed to get player from entity pool");
-            player.set_var_last_armour(self.current_armour);
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_armour(self.current_armour);
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PlayerArmourChangeEvent(player={}, old_armour={}, new_armour={})",
-            self.player(),
+            fetch_player(self.player_id),
             self.get_old_armour(),
             self.get_new_armour()
         )
@@ -1937,11 +1934,8 @@ pub struct PlayerWeaponChangeEvent {
 #[pymethods]
 impl PlayerWeaponChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
     }
 
     #[getter]
@@ -1963,18 +1957,17 @@ impl PlayerWeaponChangeEvent {
     fn set_current_weapon(&mut self, weapon: i32) {
         self.current_weapon = weapon;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let player = pool
-                .get_mut_player(self.player_id)
-                .expect("Failed to get player from entity pool");
-            player.set_var_last_weapon(self.current_weapon);
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_weapon(self.current_weapon);
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PlayerWeaponChangeEvent(player={}, old_weapon={}, new_weapon={})",
-            self.player(),
+            fetch_player(self.player_id),
             self.get_old_weapon(),
             self.get_new_weapon()
         )
@@ -2030,11 +2023,8 @@ pub struct PlayerAmmoChangeEvent {
 #[pymethods]
 impl PlayerAmmoChangeEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
     }
 
     #[getter]
@@ -2056,18 +2046,17 @@ impl PlayerAmmoChangeEvent {
     fn set_current_ammo(&mut self, ammo: i32) {
         self.current_ammo = ammo;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let player = pool
-                .get_mut_player(self.player_id)
-                .expect("Failed to get player from entity pool");
-            player.set_var_last_ammo(self.current_ammo);
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_ammo(self.current_ammo);
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PlayerWeaponChangeEvent(player={}, old_ammo={}, new_ammo={})",
-            self.player(),
+            fetch_player(self.player_id),
             self.get_old_ammo(),
             self.get_new_ammo()
         )
@@ -2112,6 +2101,540 @@ impl PyEvent for PlayerAmmoChangeEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerSkinChangeEvent {
+    pub player_id: i32,
+    pub old_skin: i32,
+    pub new_skin: i32,
+    pub current_skin: i32,
+}
+#[pymethods]
+impl PlayerSkinChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_skin(&self) -> i32 {
+        self.old_skin
+    }
+
+    #[getter]
+    fn get_new_skin(&self) -> i32 {
+        self.new_skin
+    }
+
+    #[getter]
+    fn get_current_skin(&self) -> i32 {
+        self.current_skin
+    }
+
+    #[setter]
+    fn set_current_skin(&mut self, skin: i32) {
+        self.current_skin = skin;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_skin(Some(self.current_skin));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerSkinChangeEvent(player={}, old_skin={}, new_skin={})",
+            fetch_player(self.player_id),
+            self.get_old_skin(),
+            self.get_new_skin()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerSkinChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_skin: value.1,
+            new_skin: value.2,
+            current_skin: 0,
+        };
+        this.set_current_skin(value.2);
+        this
+    }
+}
+impl PlayerSkinChangeEvent {
+    pub fn new(player: PlayerPy, old_skin: i32, new_skin: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_skin,
+            new_skin,
+            current_skin: 0,
+        };
+        this.set_current_skin(new_skin);
+        this
+    }
+}
+impl PyEvent for PlayerSkinChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerSkinChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerSkinChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerTeamChangeEvent {
+    pub player_id: i32,
+    pub old_team: i32,
+    pub new_team: i32,
+    pub current_team: i32,
+}
+#[pymethods]
+impl PlayerTeamChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_team(&self) -> i32 {
+        self.old_team
+    }
+
+    #[getter]
+    fn get_new_team(&self) -> i32 {
+        self.new_team
+    }
+
+    #[getter]
+    fn get_current_team(&self) -> i32 {
+        self.current_team
+    }
+
+    #[setter]
+    fn set_current_team(&mut self, team: i32) {
+        self.current_team = team;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_team(Some(self.current_team));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerTeamChangeEvent(player={}, old_team={}, new_team={})",
+            fetch_player(self.player_id),
+            self.get_old_team(),
+            self.get_new_team()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerTeamChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_team: value.1,
+            new_team: value.2,
+            current_team: 0,
+        };
+        this.set_current_team(value.2);
+        this
+    }
+}
+impl PlayerTeamChangeEvent {
+    pub fn new(player: PlayerPy, old_team: i32, new_team: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_team,
+            new_team,
+            current_team: 0,
+        };
+        this.set_current_team(new_team);
+        this
+    }
+}
+impl PyEvent for PlayerTeamChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerTeamChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerTeamChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerScoreChangeEvent {
+    pub player_id: i32,
+    pub old_score: i32,
+    pub new_score: i32,
+    pub current_score: i32,
+}
+#[pymethods]
+impl PlayerScoreChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_score(&self) -> i32 {
+        self.old_score
+    }
+
+    #[getter]
+    fn get_new_score(&self) -> i32 {
+        self.new_score
+    }
+
+    #[getter]
+    fn get_current_score(&self) -> i32 {
+        self.current_score
+    }
+
+    #[setter]
+    fn set_current_score(&mut self, score: i32) {
+        self.current_score = score;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_score(Some(self.current_score));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerScoreChangeEvent(player={}, old_score={}, new_score={})",
+            fetch_player(self.player_id),
+            self.get_old_score(),
+            self.get_new_score()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerScoreChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_score: value.1,
+            new_score: value.2,
+            current_score: 0,
+        };
+        this.set_current_score(value.2);
+        this
+    }
+}
+impl PlayerScoreChangeEvent {
+    pub fn new(player: PlayerPy, old_score: i32, new_score: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_score,
+            new_score,
+            current_score: 0,
+        };
+        this.set_current_score(new_score);
+        this
+    }
+}
+impl PyEvent for PlayerScoreChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerScoreChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerScoreChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerWorldChangeEvent {
+    pub player_id: i32,
+    pub old_world: i32,
+    pub new_world: i32,
+    pub current_world: i32,
+}
+#[pymethods]
+impl PlayerWorldChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_world(&self) -> i32 {
+        self.old_world
+    }
+
+    #[getter]
+    fn get_new_world(&self) -> i32 {
+        self.new_world
+    }
+
+    #[getter]
+    fn get_current_world(&self) -> i32 {
+        self.current_world
+    }
+
+    #[setter]
+    fn set_current_world(&mut self, world: i32) {
+        self.current_world = world;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_world(Some(self.current_world));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerWorldChangeEvent(player={}, old_world={}, new_world={})",
+            fetch_player(self.player_id),
+            self.get_old_world(),
+            self.get_new_world()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerWorldChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_world: value.1,
+            new_world: value.2,
+            current_world: 0,
+        };
+        this.set_current_world(value.2);
+        this
+    }
+}
+impl PlayerWorldChangeEvent {
+    pub fn new(player: PlayerPy, old_world: i32, new_world: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_world,
+            new_world,
+            current_world: 0,
+        };
+        this.set_current_world(new_world);
+        this
+    }
+}
+impl PyEvent for PlayerWorldChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerWorldChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerWorldChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerCashChangeEvent {
+    pub player_id: i32,
+    pub old_cash: i32,
+    pub new_cash: i32,
+    pub current_cash: i32,
+}
+#[pymethods]
+impl PlayerCashChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_cash(&self) -> i32 {
+        self.old_cash
+    }
+
+    #[getter]
+    fn get_new_cash(&self) -> i32 {
+        self.new_cash
+    }
+
+    #[getter]
+    fn get_current_cash(&self) -> i32 {
+        self.current_cash
+    }
+
+    #[setter]
+    fn set_current_cash(&mut self, cash: i32) {
+        self.current_cash = cash;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_cash(Some(self.current_cash));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerCashChangeEvent(player={}, old_cash={}, new_cash={})",
+            fetch_player(self.player_id),
+            self.get_old_cash(),
+            self.get_new_cash()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerCashChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_cash: value.1,
+            new_cash: value.2,
+            current_cash: 0,
+        };
+        this.set_current_cash(value.2);
+        this
+    }
+}
+impl PlayerCashChangeEvent {
+    pub fn new(player: PlayerPy, old_cash: i32, new_cash: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_cash,
+            new_cash,
+            current_cash: 0,
+        };
+        this.set_current_cash(new_cash);
+        this
+    }
+}
+impl PyEvent for PlayerCashChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerCashChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerCashChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerWantedLevelChangeEvent {
+    pub player_id: i32,
+    pub old_wanted_level: i32,
+    pub new_wanted_level: i32,
+    pub current_wanted_level: i32,
+}
+#[pymethods]
+impl PlayerWantedLevelChangeEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    #[getter]
+    fn get_old_wanted_level(&self) -> i32 {
+        self.old_wanted_level
+    }
+
+    #[getter]
+    fn get_new_wanted_level(&self) -> i32 {
+        self.new_wanted_level
+    }
+
+    #[getter]
+    fn get_current_wanted_level(&self) -> i32 {
+        self.current_wanted_level
+    }
+
+    #[setter]
+    fn set_current_wanted_level(&mut self, wanted_level: i32) {
+        self.current_wanted_level = wanted_level;
+        {
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_wanted_level(Some(self.current_wanted_level));
+            }
+        };
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerWantedLevelChangeEvent(player={}, old_wanted_level={}, new_wanted_level={})",
+            fetch_player(self.player_id),
+            self.get_old_wanted_level(),
+            self.get_new_wanted_level()
+        )
+    }
+}
+impl From<(i32, i32, i32)> for PlayerWantedLevelChangeEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        let mut this = Self {
+            player_id: value.0,
+            old_wanted_level: value.1,
+            new_wanted_level: value.2,
+            current_wanted_level: 0,
+        };
+        this.set_current_wanted_level(value.2);
+        this
+    }
+}
+impl PlayerWantedLevelChangeEvent {
+    pub fn new(player: PlayerPy, old_wanted_level: i32, new_wanted_level: i32) -> Self {
+        let mut this = Self {
+            player_id: player.get_id(),
+            old_wanted_level,
+            new_wanted_level,
+            current_wanted_level: 0,
+        };
+        this.set_current_wanted_level(new_wanted_level);
+        this
+    }
+}
+impl PyEvent for PlayerWantedLevelChangeEvent {
+    fn event_name(&self) -> String {
+        "PlayerWantedLevelChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerWantedLevelChangeEvent")
+        .into_any()
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 #[pyclass(extends=PlayerEvent, subclass)]
 pub struct PlayerMoveEvent {
@@ -2123,11 +2646,8 @@ pub struct PlayerMoveEvent {
 #[pymethods]
 impl PlayerMoveEvent {
     #[getter]
-    fn player(&self) -> PlayerPy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_player(self.player_id)
-            .expect("Failed to get player from entity pool")
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
     }
 
     #[getter]
@@ -2149,18 +2669,17 @@ impl PlayerMoveEvent {
     fn set_current_position(&mut self, position: VectorPy) {
         self.current_position = position;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let player = pool
-                .get_mut_player(self.player_id)
-                .expect("Failed to get player from entity pool");
-            player.set_var_last_position(self.current_position.get_entity_pos());
+            let mut pool = write_pool();
+            if let Some(player) = pool.get_mut_player(self.player_id) {
+                player.set_var_last_position(self.current_position.get_entity_pos());
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "PlayerMoveEvent(player={}, old_position={:?}, new_position={:?})",
-            self.player(),
+            fetch_player(self.player_id),
             self.get_old_position(),
             self.get_new_position()
         )
@@ -2205,10 +2724,90 @@ impl PyEvent for PlayerMoveEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=PlayerEvent, subclass)]
+pub struct PlayerDamageEvent {
+    pub player_id: i32,
+    pub attacker_id: Option<i32>,
+    pub weapon: i32,
+    pub amount: f32,
+}
+#[pymethods]
+impl PlayerDamageEvent {
+    #[getter]
+    fn player(&self) -> PyResult<PlayerPy> {
+        lookup_player(self.player_id)
+    }
+
+    /// 推测出来的攻击者，不一定准确
+    #[getter]
+    fn attacker(&self) -> Option<PlayerPy> {
+        let pool = read_pool();
+        self.attacker_id.and_then(|id| pool.get_player(id).copied())
+    }
+
+    #[getter]
+    fn weapon(&self) -> i32 {
+        self.weapon
+    }
+
+    #[getter]
+    fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PlayerDamageEvent(player={}, attacker={}, weapon={}, amount={})",
+            fetch_player(self.player_id),
+            self.attacker()
+                .map(|p| format!("{p}"))
+                .unwrap_or("None".to_string()),
+            self.weapon,
+            self.amount
+        )
+    }
+}
+impl From<(i32, Option<i32>, i32, f32)> for PlayerDamageEvent {
+    fn from(value: (i32, Option<i32>, i32, f32)) -> Self {
+        Self {
+            player_id: value.0,
+            attacker_id: value.1,
+            weapon: value.2,
+            amount: value.3,
+        }
+    }
+}
+impl PlayerDamageEvent {
+    pub fn new(player: PlayerPy, attacker: Option<PlayerPy>, weapon: i32, amount: f32) -> Self {
+        Self {
+            player_id: player.get_id(),
+            attacker_id: attacker.map(|p| p.get_id()),
+            weapon,
+            amount,
+        }
+    }
+}
+impl PyEvent for PlayerDamageEvent {
+    fn event_name(&self) -> String {
+        "PlayerDamageEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(PlayerEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create PlayerDamageEvent")
+        .into_any()
+    }
+}
+
 pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PlayerEvent>()?;
     m.add_class::<IncomingConnectionEvent>()?;
     m.add_class::<ClientScriptDataEvent>()?;
+    m.add_class::<ClientChunkedDataEvent>()?;
     m.add_class::<PlayerConnectEvent>()?;
     m.add_class::<PlayerDisconnectEvent>()?;
     m.add_class::<PlayerRequestClassEvent>()?;
@@ -2243,6 +2842,14 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PlayerHealthChangeEvent>()?;
     m.add_class::<PlayerArmourChangeEvent>()?;
     m.add_class::<PlayerWeaponChangeEvent>()?;
+    m.add_class::<PlayerSkinChangeEvent>()?;
+    m.add_class::<PlayerTeamChangeEvent>()?;
+    m.add_class::<PlayerScoreChangeEvent>()?;
+    m.add_class::<PlayerWorldChangeEvent>()?;
+    m.add_class::<PlayerCashChangeEvent>()?;
+    m.add_class::<PlayerWantedLevelChangeEvent>()?;
+    m.add_class::<PlayerDamageEvent>()?;
+    m.add_class::<DamageRecord>()?;
 
     Ok(())
 }
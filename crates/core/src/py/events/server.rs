@@ -213,6 +213,111 @@ impl PyEvent for ServerReloadedEvent {
     }
 }
 
+#[derive(Debug, Clone)]
+#[pyclass(extends=ServerEvent, subclass)]
+pub struct ServerReloadFailedEvent {
+    /// 编译或者试运行失败的 traceback，旧脚本还在跑
+    pub traceback: String,
+}
+#[pymethods]
+impl ServerReloadFailedEvent {
+    #[getter]
+    fn traceback(&self) -> String {
+        self.traceback.clone()
+    }
+    fn __repr__(&self) -> String {
+        format!("ServerReloadFailedEvent(traceback={:?})", self.traceback)
+    }
+}
+impl From<String> for ServerReloadFailedEvent {
+    fn from(value: String) -> Self {
+        Self { traceback: value }
+    }
+}
+impl ServerReloadFailedEvent {
+    pub fn new(traceback: String) -> Self {
+        Self { traceback }
+    }
+}
+impl PyEvent for ServerReloadFailedEvent {
+    fn event_name(&self) -> String {
+        "ServerReloadFailedEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(ServerEvent::new()).add_subclass(self.clone()),
+        )
+        .expect("Failed to create ServerReloadFailedEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone)]
+#[pyclass(extends=ServerEvent, subclass)]
+pub struct SettingChangedEvent {
+    pub name: String,
+    pub old_value: Py<PyAny>,
+    pub new_value: Py<PyAny>,
+}
+#[pymethods]
+impl SettingChangedEvent {
+    #[getter]
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[getter]
+    fn old_value(&self, py: Python<'_>) -> Py<PyAny> {
+        self.old_value.clone_ref(py)
+    }
+
+    #[getter]
+    fn new_value(&self, py: Python<'_>) -> Py<PyAny> {
+        self.new_value.clone_ref(py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SettingChangedEvent(name={}, old_value={}, new_value={})",
+            self.name, self.old_value, self.new_value
+        )
+    }
+}
+impl From<(String, Py<PyAny>, Py<PyAny>)> for SettingChangedEvent {
+    fn from(value: (String, Py<PyAny>, Py<PyAny>)) -> Self {
+        Self {
+            name: value.0,
+            old_value: value.1,
+            new_value: value.2,
+        }
+    }
+}
+impl SettingChangedEvent {
+    pub fn new(name: String, old_value: Py<PyAny>, new_value: Py<PyAny>) -> Self {
+        Self {
+            name,
+            old_value,
+            new_value,
+        }
+    }
+}
+impl PyEvent for SettingChangedEvent {
+    fn event_name(&self) -> String {
+        "SettingChangedEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(ServerEvent::new()).add_subclass(self.clone()),
+        )
+        .expect("Failed to create SettingChangedEvent")
+        .into_any()
+    }
+}
+
 pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ServerEvent>()?;
     m.add_class::<ServerInitialiseEvent>()?;
@@ -220,5 +325,7 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ServerFrameEvent>()?;
     m.add_class::<ServerPerformanceReportEvent>()?;
     m.add_class::<ServerReloadedEvent>()?;
+    m.add_class::<ServerReloadFailedEvent>()?;
+    m.add_class::<SettingChangedEvent>()?;
     Ok(())
 }
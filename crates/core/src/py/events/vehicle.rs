@@ -5,7 +5,7 @@ use pyo3::{
 
 use crate::{
     functions::vehicle::VehiclePy,
-    pool::ENTITY_POOL,
+    pool::{fetch_vehicle, lookup_vehicle, write_pool},
     py::{
         events::abc::{BaseEvent, PyEvent},
         types::VectorPy,
@@ -45,11 +45,8 @@ pub struct VehicleUpdateEvent {
 #[pymethods]
 impl VehicleUpdateEvent {
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
 
     #[getter]
@@ -60,7 +57,7 @@ impl VehicleUpdateEvent {
     fn __repr__(&self) -> String {
         format!(
             "VehicleUpdateEvent(vehicle={}, update_type={})",
-            self.vehicle(),
+            fetch_vehicle(self.inner.vehicle_id),
             self.update_type()
         )
     }
@@ -103,15 +100,15 @@ pub struct VehicleExplodeEvent {
 #[pymethods]
 impl VehicleExplodeEvent {
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
 
     fn __repr__(&self) -> String {
-        format!("VehicleExplodeEvent(vehicle={})", self.vehicle())
+        format!(
+            "VehicleExplodeEvent(vehicle={})",
+            fetch_vehicle(self.inner.vehicle_id)
+        )
     }
 }
 impl From<vehicle::VehicleExplodeEvent> for VehicleExplodeEvent {
@@ -151,15 +148,15 @@ pub struct VehicleRespawnEvent {
 #[pymethods]
 impl VehicleRespawnEvent {
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.inner.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.inner.vehicle_id)
     }
 
     fn __repr__(&self) -> String {
-        format!("VehicleRespawnEvent(vehicle={})", self.vehicle())
+        format!(
+            "VehicleRespawnEvent(vehicle={})",
+            fetch_vehicle(self.inner.vehicle_id)
+        )
     }
 }
 impl From<vehicle::VehicleRespawnEvent> for VehicleRespawnEvent {
@@ -202,11 +199,8 @@ pub struct VehicleMoveEvent {
 #[pymethods]
 impl VehicleMoveEvent {
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
     }
 
     #[getter]
@@ -228,18 +222,17 @@ impl VehicleMoveEvent {
     fn set_current_position(&mut self, position: VectorPy) {
         self.current_position = position;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let vehicle = pool
-                .get_mut_vehicle(self.vehicle_id)
-                .expect("Failed to get vehicle from pool");
-            vehicle.set_var_last_position(self.current_position.get_entity_pos());
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_position(self.current_position.get_entity_pos());
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "VehicleMoveEvent(vehicle={}, old_position={:?}, new_position={:?})",
-            self.vehicle(),
+            fetch_vehicle(self.vehicle_id),
             self.get_old_position(),
             self.get_new_position()
         )
@@ -295,11 +288,8 @@ pub struct VehicleHealthChangeEvent {
 #[pymethods]
 impl VehicleHealthChangeEvent {
     #[getter]
-    fn vehicle(&self) -> VehiclePy {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        *pool
-            .get_vehicle(self.vehicle_id)
-            .expect("Failed to get vehicle from pool")
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
     }
 
     #[getter]
@@ -321,18 +311,17 @@ impl VehicleHealthChangeEvent {
     fn set_current_health(&mut self, health: f32) {
         self.current_health = health;
         {
-            let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            let vehicle = pool
-                .get_mut_vehicle(self.vehicle_id)
-                .expect("Failed to get vehicle from pool");
-            vehicle.set_var_last_health(self.current_health);
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_health(self.current_health);
+            }
         };
     }
 
     fn __repr__(&self) -> String {
         format!(
             "VehicleHealthChangeEvent(vehicle={}, old_health={}, new_health={})",
-            self.vehicle(),
+            fetch_vehicle(self.vehicle_id),
             self.get_old_health(),
             self.get_new_health()
         )
@@ -377,6 +366,640 @@ impl PyEvent for VehicleHealthChangeEvent {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleDamageChangeEvent {
+    pub vehicle_id: i32,
+    pub old_damage: u32,
+    pub new_damage: u32,
+    pub current_damage: u32,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehicleDamageChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn get_old_damage(&self) -> u32 {
+        self.old_damage
+    }
+
+    #[getter]
+    fn get_new_damage(&self) -> u32 {
+        self.new_damage
+    }
+
+    #[getter]
+    fn get_current_damage(&self) -> u32 {
+        self.current_damage
+    }
+
+    #[setter]
+    fn set_current_damage(&mut self, damage: u32) {
+        self.current_damage = damage;
+        {
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_damage(Some(self.current_damage));
+            }
+        };
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleDamageChangeEvent(vehicle={}, old_damage={}, new_damage={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.get_old_damage(),
+            self.get_new_damage(),
+            self.update_type
+        )
+    }
+}
+impl From<(i32, u32, u32, i32)> for VehicleDamageChangeEvent {
+    fn from(value: (i32, u32, u32, i32)) -> Self {
+        let mut this = Self {
+            vehicle_id: value.0,
+            old_damage: value.1,
+            new_damage: value.2,
+            current_damage: 0,
+            update_type: value.3,
+        };
+        this.set_current_damage(value.2);
+        this
+    }
+}
+impl VehicleDamageChangeEvent {
+    pub fn new(vehicle: VehiclePy, old_damage: u32, new_damage: u32, update_type: i32) -> Self {
+        let mut this = Self {
+            vehicle_id: vehicle.get_id(),
+            old_damage,
+            new_damage,
+            current_damage: 0,
+            update_type,
+        };
+        this.set_current_damage(new_damage);
+        this
+    }
+}
+impl PyEvent for VehicleDamageChangeEvent {
+    fn event_name(&self) -> String {
+        "VehicleDamageChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleDamageChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleTyrePopEvent {
+    pub vehicle_id: i32,
+    pub tyre: i32,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehicleTyrePopEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn tyre(&self) -> i32 {
+        self.tyre
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleTyrePopEvent(vehicle={}, tyre={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.tyre,
+            self.update_type
+        )
+    }
+}
+impl From<(i32, i32, i32)> for VehicleTyrePopEvent {
+    fn from(value: (i32, i32, i32)) -> Self {
+        Self {
+            vehicle_id: value.0,
+            tyre: value.1,
+            update_type: value.2,
+        }
+    }
+}
+impl VehicleTyrePopEvent {
+    pub fn new(vehicle: VehiclePy, tyre: i32, update_type: i32) -> Self {
+        Self {
+            vehicle_id: vehicle.get_id(),
+            tyre,
+            update_type,
+        }
+    }
+}
+impl PyEvent for VehicleTyrePopEvent {
+    fn event_name(&self) -> String {
+        "VehicleTyrePopEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleTyrePopEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehiclePartStatusChangeEvent {
+    pub vehicle_id: i32,
+    pub part: i32,
+    pub old_status: bool,
+    pub new_status: bool,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehiclePartStatusChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn part(&self) -> i32 {
+        self.part
+    }
+
+    #[getter]
+    fn get_old_status(&self) -> bool {
+        self.old_status
+    }
+
+    #[getter]
+    fn get_new_status(&self) -> bool {
+        self.new_status
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehiclePartStatusChangeEvent(vehicle={}, part={}, old_status={}, new_status={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.part,
+            self.old_status,
+            self.new_status,
+            self.update_type
+        )
+    }
+}
+impl From<(i32, i32, bool, bool, i32)> for VehiclePartStatusChangeEvent {
+    fn from(value: (i32, i32, bool, bool, i32)) -> Self {
+        Self {
+            vehicle_id: value.0,
+            part: value.1,
+            old_status: value.2,
+            new_status: value.3,
+            update_type: value.4,
+        }
+    }
+}
+impl VehiclePartStatusChangeEvent {
+    pub fn new(
+        vehicle: VehiclePy,
+        part: i32,
+        old_status: bool,
+        new_status: bool,
+        update_type: i32,
+    ) -> Self {
+        Self {
+            vehicle_id: vehicle.get_id(),
+            part,
+            old_status,
+            new_status,
+            update_type,
+        }
+    }
+}
+impl PyEvent for VehiclePartStatusChangeEvent {
+    fn event_name(&self) -> String {
+        "VehiclePartStatusChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehiclePartStatusChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleLightsDataChangeEvent {
+    pub vehicle_id: i32,
+    pub old_lights_data: u32,
+    pub new_lights_data: u32,
+    pub current_lights_data: u32,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehicleLightsDataChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn get_old_lights_data(&self) -> u32 {
+        self.old_lights_data
+    }
+
+    #[getter]
+    fn get_new_lights_data(&self) -> u32 {
+        self.new_lights_data
+    }
+
+    #[getter]
+    fn get_current_lights_data(&self) -> u32 {
+        self.current_lights_data
+    }
+
+    #[setter]
+    fn set_current_lights_data(&mut self, lights_data: u32) {
+        self.current_lights_data = lights_data;
+        {
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_lights_data(Some(self.current_lights_data));
+            }
+        };
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleLightsDataChangeEvent(vehicle={}, old_lights_data={}, new_lights_data={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.get_old_lights_data(),
+            self.get_new_lights_data(),
+            self.update_type
+        )
+    }
+}
+impl From<(i32, u32, u32, i32)> for VehicleLightsDataChangeEvent {
+    fn from(value: (i32, u32, u32, i32)) -> Self {
+        let mut this = Self {
+            vehicle_id: value.0,
+            old_lights_data: value.1,
+            new_lights_data: value.2,
+            current_lights_data: 0,
+            update_type: value.3,
+        };
+        this.set_current_lights_data(value.2);
+        this
+    }
+}
+impl VehicleLightsDataChangeEvent {
+    pub fn new(
+        vehicle: VehiclePy,
+        old_lights_data: u32,
+        new_lights_data: u32,
+        update_type: i32,
+    ) -> Self {
+        let mut this = Self {
+            vehicle_id: vehicle.get_id(),
+            old_lights_data,
+            new_lights_data,
+            current_lights_data: 0,
+            update_type,
+        };
+        this.set_current_lights_data(new_lights_data);
+        this
+    }
+}
+impl PyEvent for VehicleLightsDataChangeEvent {
+    fn event_name(&self) -> String {
+        "VehicleLightsDataChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleLightsDataChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleSirenChangeEvent {
+    pub vehicle_id: i32,
+    pub old_siren: bool,
+    pub new_siren: bool,
+    pub current_siren: bool,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehicleSirenChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn get_old_siren(&self) -> bool {
+        self.old_siren
+    }
+
+    #[getter]
+    fn get_new_siren(&self) -> bool {
+        self.new_siren
+    }
+
+    #[getter]
+    fn get_current_siren(&self) -> bool {
+        self.current_siren
+    }
+
+    #[setter]
+    fn set_current_siren(&mut self, siren: bool) {
+        self.current_siren = siren;
+        {
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_siren(Some(self.current_siren));
+            }
+        };
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleSirenChangeEvent(vehicle={}, old_siren={}, new_siren={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.old_siren,
+            self.new_siren,
+            self.update_type
+        )
+    }
+}
+impl From<(i32, bool, bool, i32)> for VehicleSirenChangeEvent {
+    fn from(value: (i32, bool, bool, i32)) -> Self {
+        let mut this = Self {
+            vehicle_id: value.0,
+            old_siren: value.1,
+            new_siren: value.2,
+            current_siren: false,
+            update_type: value.3,
+        };
+        this.set_current_siren(value.2);
+        this
+    }
+}
+impl VehicleSirenChangeEvent {
+    pub fn new(vehicle: VehiclePy, old_siren: bool, new_siren: bool, update_type: i32) -> Self {
+        let mut this = Self {
+            vehicle_id: vehicle.get_id(),
+            old_siren,
+            new_siren,
+            current_siren: false,
+            update_type,
+        };
+        this.set_current_siren(new_siren);
+        this
+    }
+}
+impl PyEvent for VehicleSirenChangeEvent {
+    fn event_name(&self) -> String {
+        "VehicleSirenChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleSirenChangeEvent")
+        .into_any()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleDoorsLockedChangeEvent {
+    pub vehicle_id: i32,
+    pub old_doors_locked: bool,
+    pub new_doors_locked: bool,
+    pub current_doors_locked: bool,
+    pub update_type: i32,
+}
+#[pymethods]
+impl VehicleDoorsLockedChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn get_old_doors_locked(&self) -> bool {
+        self.old_doors_locked
+    }
+
+    #[getter]
+    fn get_new_doors_locked(&self) -> bool {
+        self.new_doors_locked
+    }
+
+    #[getter]
+    fn get_current_doors_locked(&self) -> bool {
+        self.current_doors_locked
+    }
+
+    #[setter]
+    fn set_current_doors_locked(&mut self, doors_locked: bool) {
+        self.current_doors_locked = doors_locked;
+        {
+            let mut pool = write_pool();
+            if let Some(vehicle) = pool.get_mut_vehicle(self.vehicle_id) {
+                vehicle.set_var_last_doors_locked(Some(self.current_doors_locked));
+            }
+        };
+    }
+
+    #[getter]
+    fn update_type(&self) -> i32 {
+        self.update_type
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleDoorsLockedChangeEvent(vehicle={}, old_doors_locked={}, new_doors_locked={}, update_type={})",
+            fetch_vehicle(self.vehicle_id),
+            self.old_doors_locked,
+            self.new_doors_locked,
+            self.update_type
+        )
+    }
+}
+impl From<(i32, bool, bool, i32)> for VehicleDoorsLockedChangeEvent {
+    fn from(value: (i32, bool, bool, i32)) -> Self {
+        let mut this = Self {
+            vehicle_id: value.0,
+            old_doors_locked: value.1,
+            new_doors_locked: value.2,
+            current_doors_locked: false,
+            update_type: value.3,
+        };
+        this.set_current_doors_locked(value.2);
+        this
+    }
+}
+impl VehicleDoorsLockedChangeEvent {
+    pub fn new(
+        vehicle: VehiclePy,
+        old_doors_locked: bool,
+        new_doors_locked: bool,
+        update_type: i32,
+    ) -> Self {
+        let mut this = Self {
+            vehicle_id: vehicle.get_id(),
+            old_doors_locked,
+            new_doors_locked,
+            current_doors_locked: false,
+            update_type,
+        };
+        this.set_current_doors_locked(new_doors_locked);
+        this
+    }
+}
+impl PyEvent for VehicleDoorsLockedChangeEvent {
+    fn event_name(&self) -> String {
+        "VehicleDoorsLockedChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleDoorsLockedChangeEvent")
+        .into_any()
+    }
+}
+
+/// 操控规则被 set_handling_rule / reset_handling_rule / reset_handling 改了
+#[derive(Debug, Clone, Copy)]
+#[pyclass(extends=VehicleEvent, subclass)]
+pub struct VehicleHandlingChangeEvent {
+    pub vehicle_id: i32,
+    pub rule_index: i32,
+    pub old_value: f64,
+    pub new_value: f64,
+}
+#[pymethods]
+impl VehicleHandlingChangeEvent {
+    #[getter]
+    fn vehicle(&self) -> PyResult<VehiclePy> {
+        lookup_vehicle(self.vehicle_id)
+    }
+
+    #[getter]
+    fn rule_index(&self) -> i32 {
+        self.rule_index
+    }
+
+    #[getter]
+    fn get_old_value(&self) -> f64 {
+        self.old_value
+    }
+
+    #[getter]
+    fn get_new_value(&self) -> f64 {
+        self.new_value
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VehicleHandlingChangeEvent(vehicle={}, rule_index={}, old_value={}, new_value={})",
+            fetch_vehicle(self.vehicle_id),
+            self.rule_index,
+            self.old_value,
+            self.new_value
+        )
+    }
+}
+impl From<(i32, i32, f64, f64)> for VehicleHandlingChangeEvent {
+    fn from(value: (i32, i32, f64, f64)) -> Self {
+        Self {
+            vehicle_id: value.0,
+            rule_index: value.1,
+            old_value: value.2,
+            new_value: value.3,
+        }
+    }
+}
+impl VehicleHandlingChangeEvent {
+    pub fn new(vehicle: VehiclePy, rule_index: i32, old_value: f64, new_value: f64) -> Self {
+        Self {
+            vehicle_id: vehicle.get_id(),
+            rule_index,
+            old_value,
+            new_value,
+        }
+    }
+}
+impl PyEvent for VehicleHandlingChangeEvent {
+    fn event_name(&self) -> String {
+        "VehicleHandlingChangeEvent".to_string()
+    }
+
+    fn init(&self, py: Python<'_>) -> Py<PyAny> {
+        Py::new(
+            py,
+            PyClassInitializer::from(VehicleEvent::new()).add_subclass(*self),
+        )
+        .expect("Failed to create VehicleHandlingChangeEvent")
+        .into_any()
+    }
+}
+
 pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<VehicleEvent>()?;
     m.add_class::<VehicleUpdateEvent>()?;
@@ -386,5 +1009,12 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Vehicle Extra
     m.add_class::<VehicleMoveEvent>()?;
     m.add_class::<VehicleHealthChangeEvent>()?;
+    m.add_class::<VehicleDamageChangeEvent>()?;
+    m.add_class::<VehicleTyrePopEvent>()?;
+    m.add_class::<VehiclePartStatusChangeEvent>()?;
+    m.add_class::<VehicleLightsDataChangeEvent>()?;
+    m.add_class::<VehicleSirenChangeEvent>()?;
+    m.add_class::<VehicleDoorsLockedChangeEvent>()?;
+    m.add_class::<VehicleHandlingChangeEvent>()?;
     Ok(())
 }
@@ -0,0 +1,297 @@
+//! 脚本入口
+//!
+//! `script_path` 和 `scripts` 里的每一项都是一个入口，按顺序加载：
+//!
+//! | 写法 | 加载方式 |
+//! | --- | --- |
+//! | `main.py` | 单个文件，`runpy.run_path` |
+//! | `game` | 包目录，import 这个包，有 `__main__.py` 的话再 `runpy.run_module` |
+//! | `game.server:main` | import 模块之后调用里面的函数 |
+//!
+//! 文件入口和包的 `__main__.py` 跟 `python main.py`、`python -m game` 一样，
+//! `__name__` 都是 `__main__`，每个入口有自己的命名空间，跑完不会留在 `sys.modules` 里。
+//! `module:function` 入口是正常 import 的模块，`__name__` 就是模块名。
+//!
+//! 文件和包所在的目录会加到 `sys.path` 最前面（跟 `python main.py` 一样），
+//! 所以包里的相对 import 都能用。
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use pyo3::{
+    PyResult, Python,
+    exceptions::{PyIOError, PyValueError},
+    types::{PyAnyMethods, PyDict, PyDictMethods},
+};
+use tracing::{Level, event};
+
+use crate::cfg::get_script_entries;
+use crate::py::get_traceback;
+use crate::py::scope::{self, enter_scope};
+
+#[derive(Debug, Clone)]
+pub enum ScriptEntry {
+    File(PathBuf),
+    Package { path: PathBuf, name: String },
+    Function { module: String, function: String },
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_dotted_name(name: &str) -> bool {
+    name.split('.').all(is_identifier)
+}
+
+impl ScriptEntry {
+    pub fn parse(entry: &str) -> Self {
+        let entry = entry.trim();
+        // C:\server\main.py 这种也有冒号，所以冒号后面必须是函数名
+        if let Some((module, function)) = entry.rsplit_once(':')
+            && is_dotted_name(module)
+            && is_identifier(function)
+            && !Path::new(entry).exists()
+        {
+            return Self::Function {
+                module: module.to_string(),
+                function: function.to_string(),
+            };
+        }
+        let path = PathBuf::from(entry);
+        if !path.is_dir() {
+            return Self::File(path);
+        }
+        // `.`、`..` 这种没有目录名，先转成绝对路径再取，`..` 结尾的只能 canonicalize
+        let path = std::path::absolute(&path).unwrap_or(path);
+        let path = match path.file_name() {
+            Some(_) => path,
+            None => path.canonicalize().unwrap_or(path),
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self::Package { path, name }
+    }
+
+    /// 要加到 sys.path 的目录
+    pub fn search_path(&self) -> PathBuf {
+        let path = match self {
+            Self::File(path) | Self::Package { path, .. } => path.parent(),
+            Self::Function { .. } => None,
+        };
+        match path {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        }
+    }
+}
+
+impl Display for ScriptEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) | Self::Package { path, .. } => write!(f, "{}", path.display()),
+            Self::Function { module, function } => write!(f, "{module}:{function}"),
+        }
+    }
+}
+
+/// 配置里的所有入口，按配置的顺序，下标就是 [`crate::py::scope`] 里的入口编号
+pub fn script_entries() -> Vec<ScriptEntry> {
+    get_script_entries()
+        .iter()
+        .map(|entry| ScriptEntry::parse(entry))
+        .collect()
+}
+
+fn prepend_sys_path(py: Python<'_>, entries: &[ScriptEntry]) -> PyResult<()> {
+    let sys_path = py.import("sys")?.getattr("path")?;
+    let mut dirs: Vec<String> = Vec::new();
+    for entry in entries {
+        let dir = entry.search_path();
+        let dir = std::path::absolute(&dir).unwrap_or(dir);
+        let dir = dir.to_string_lossy().to_string();
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    // 倒着插到最前面，保持配置里的顺序
+    for dir in dirs.into_iter().rev() {
+        if !sys_path.contains(&dir)? {
+            sys_path.call_method1("insert", (0, dir))?;
+        }
+    }
+    Ok(())
+}
+
+fn read_source(path: &Path) -> PyResult<String> {
+    std::fs::read_to_string(path)
+        .map_err(|e| PyIOError::new_err(format!("Failed to read script {}: {e}", path.display())))
+}
+
+fn compile_file(py: Python<'_>, path: &Path) -> PyResult<()> {
+    let code = read_source(path)?;
+    py.import("builtins")?.getattr("compile")?.call1((
+        code,
+        path.to_string_lossy().to_string(),
+        "exec",
+    ))?;
+    Ok(())
+}
+
+/// 包目录下所有的 .py，跳过隐藏目录和 __pycache__
+fn package_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if !name.starts_with('.') && name != "__pycache__" {
+                    dirs.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "py") {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// 只编译不执行，`module:function` 不 import 找不到文件，这里跳过
+pub fn compile_entry(py: Python<'_>, entry: &ScriptEntry) -> PyResult<()> {
+    match entry {
+        ScriptEntry::File(path) => compile_file(py, path),
+        ScriptEntry::Package { path, .. } => {
+            for file in package_files(path) {
+                compile_file(py, &file)?;
+            }
+            Ok(())
+        }
+        ScriptEntry::Function { .. } => Ok(()),
+    }
+}
+
+/// 加载一个入口，文件和包的 `__main__.py` 用 `run_name` 当 `__name__` 跑，
+/// `call` 为 false 的时候 `module:function` 只 import 不调用
+fn load_entry(py: Python<'_>, entry: &ScriptEntry, run_name: &str, call: bool) -> PyResult<()> {
+    let importlib = py.import("importlib")?;
+    let runpy = py.import("runpy")?;
+    match entry {
+        ScriptEntry::File(path) => {
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("run_name", run_name)?;
+            runpy.call_method(
+                "run_path",
+                (path.to_string_lossy().to_string(),),
+                Some(&kwargs),
+            )?;
+        }
+        ScriptEntry::Package { path, name } => {
+            if !is_identifier(name) {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid package name: {name:?}"
+                )));
+            }
+            if path.join("__main__.py").is_file() {
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("run_name", run_name)?;
+                runpy.call_method("run_module", (name,), Some(&kwargs))?;
+            } else {
+                importlib.call_method1("import_module", (name,))?;
+            }
+        }
+        ScriptEntry::Function { module, function } => {
+            let module = importlib.call_method1("import_module", (module,))?;
+            let func = module.getattr(function.as_str())?;
+            if call {
+                func.call0()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// sys.modules 里现在有的模块名
+fn module_names(py: Python<'_>) -> HashSet<String> {
+    py.import("sys")
+        .and_then(|sys| sys.getattr("modules"))
+        .and_then(|modules| modules.call_method0("keys"))
+        .and_then(|keys| {
+            keys.try_iter()
+                .map(|keys| keys.flatten().collect::<Vec<_>>())
+        })
+        .map(|keys| keys.iter().filter_map(|key| key.extract().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// 按顺序加载 target 入口，None 是全部，一个失败了不影响后面的，返回第一个错误
+///
+/// 每个入口加载时新 import 的模块记在这个入口下面，单独重载这个入口的时候卸载
+pub fn load_all(py: Python<'_>, target: Option<usize>) -> Result<(), String> {
+    let entries = script_entries();
+    if entries.is_empty() {
+        event!(Level::ERROR, "No script configured");
+        return Err("No script configured".to_string());
+    }
+    let mut result = Ok(());
+    if let Err(e) = prepend_sys_path(py, &entries) {
+        let traceback = get_traceback(&e, Some(py));
+        event!(Level::ERROR, "Failed to set sys.path: {traceback}");
+        result = Err(traceback);
+    }
+    // 加载器自己要用的模块先 import，不算到第一个入口头上
+    for module in ["runpy", "pkgutil"] {
+        let _ = py.import(module);
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        if !scope::in_target(Some(index), target) {
+            continue;
+        }
+        let _scope = enter_scope(Some(index));
+        let before = module_names(py);
+        let loaded = load_entry(py, entry, "__main__", true);
+        let imported = module_names(py)
+            .into_iter()
+            .filter(|name| !before.contains(name))
+            .collect();
+        scope::set_entry_modules(index, imported);
+        match loaded {
+            Ok(()) => event!(Level::INFO, "Script loaded: {entry}"),
+            Err(e) => {
+                let traceback = get_traceback(&e, Some(py));
+                event!(Level::ERROR, "Failed to load script {entry}: {traceback}");
+                if result.is_ok() {
+                    result = Err(format!("{entry}: {traceback}"));
+                }
+            }
+        }
+    }
+    result
+}
+
+/// 试运行 target 入口，None 是全部，遇到失败就停下返回 traceback
+///
+/// `__name__` 换成 `__reload_check__`，入口函数也不调用
+pub fn dry_run_all(py: Python<'_>, target: Option<usize>) -> Result<(), String> {
+    let entries = script_entries();
+    prepend_sys_path(py, &entries).map_err(|e| get_traceback(&e, Some(py)))?;
+    for (index, entry) in entries.iter().enumerate() {
+        if !scope::in_target(Some(index), target) {
+            continue;
+        }
+        let _scope = enter_scope(Some(index));
+        load_entry(py, entry, "__reload_check__", false)
+            .map_err(|e| format!("{entry}: {}", get_traceback(&e, Some(py))))?;
+    }
+    Ok(())
+}
@@ -1,17 +1,17 @@
 use std::collections::HashMap;
 use std::ffi::CString;
-use std::path::Path;
 use std::process::exit;
-use std::sync::{LazyLock, Mutex};
+use std::sync::{LazyLock, Mutex, PoisonError};
 use std::time::Instant;
 
+use pyo3::exceptions::PyValueError;
 use pyo3::types::{
-    PyAnyMethods, PyDict, PyDictMethods, PyModule, PyModuleMethods, PyTracebackMethods,
+    PyAnyMethods, PyDict, PyDictMethods, PyModule, PyModuleMethods, PyString, PyTracebackMethods,
 };
 use pyo3::{Bound, Py, PyAny, PyErr, PyResult, Python, pyfunction, pymodule, wrap_pyfunction};
 use tracing::{Level, event};
 
-use crate::cfg::{get_preloader, get_script_path, get_virtual_env, get_ignore_py_modules};
+use crate::cfg::{get_ignore_py_modules, get_preloader, get_reload_dry_run, get_virtual_env};
 use crate::functions;
 use crate::functions::checkpoint::CheckPointPy;
 use crate::functions::marker::MarkerPy;
@@ -19,17 +19,25 @@ use crate::functions::object::ObjectPy;
 use crate::functions::pickup::PickupPy;
 use crate::functions::player::PlayerPy;
 use crate::functions::vehicle::VehiclePy;
-use crate::pool::ENTITY_POOL;
-use crate::py::callbacks::{PY_CALLBACK_MANAGER, PY_CALLBACK_STORAGE};
+use crate::pool::{read_pool, write_pool};
+use crate::py::callbacks::{PY_CALLBACK_MANAGER, PY_CALLBACK_STORAGE, PyCallbackStorage};
 use crate::py::events::player::{
     PlayerConnectEvent, PlayerDisconnectEvent, PlayerRequestClassEvent, PlayerSpawnEvent,
 };
-use crate::py::events::server::{ServerInitialiseEvent, ServerReloadedEvent, ServerShutdownEvent};
+use crate::py::events::server::{
+    ServerInitialiseEvent, ServerReloadFailedEvent, ServerReloadedEvent, ServerShutdownEvent,
+};
 use crate::py::events::{PyVcmpEvent, VcmpEvent};
+use crate::py::reload_state::ReloadHooks;
+use crate::py::streams::message::MessageRoute;
 
 pub mod callbacks;
 pub mod events;
+pub mod loader;
 pub mod pool;
+pub mod reload_state;
+pub mod scope;
+pub mod settings;
 pub mod streams;
 pub mod types;
 pub mod util;
@@ -57,7 +65,10 @@ pub struct GlobalVar {
     pub need_reload: bool,
     pub capture_modules: Option<Vec<String>>,
     pub reload_var: Option<HashMap<String, Py<PyAny>>>,
-    pub error_handler: Option<Py<PyAny>>,
+    /// 只重载这个入口，None 是全部重载
+    pub reload_target: Option<usize>,
+    /// 每个入口自己的 error handler，None 是不属于任何入口的
+    pub error_handlers: HashMap<Option<usize>, Py<PyAny>>,
 }
 
 /// 非常好的 CPython signal, 使我 OSError: Signal 2 ignored due to race condition
@@ -177,7 +188,16 @@ fn register_module(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     fix_module_name(py, &pool_module, "pool");
     m.add_submodule(&pool_module)?;
 
+    let reload_state_module = PyModule::new(py, "reload_state")?;
+    reload_state::module_define(py, &reload_state_module)?;
+    fix_module_name(py, &reload_state_module, "reload_state");
+    m.add_submodule(&reload_state_module)?;
+
+    m.add_class::<settings::SettingsPy>()?;
+    m.add("settings", Py::new(py, settings::SettingsPy)?)?;
+
     m.add_function(wrap_pyfunction!(py_reload, m)?)?;
+    m.add_function(wrap_pyfunction!(py_reload_entry, m)?)?;
     m.add_function(wrap_pyfunction!(py_set_error_handler, m)?)?;
     m.add_function(wrap_pyfunction!(py_get_error_handler, m)?)?;
 
@@ -285,13 +305,27 @@ pub fn init_py() {
     capture_modules(None);
 
     if get_preloader() {
-        load_script();
+        let _ = load_script(None);
     }
 }
 
 #[pyfunction]
 #[pyo3(name = "reload", signature = (**kwargs))]
 pub fn py_reload(kwargs: Option<HashMap<String, Py<PyAny>>>) {
+    request_reload(None, kwargs);
+}
+
+/// 只重载一个入口，entry 是配置里的写法或者入口的编号
+#[pyfunction]
+#[pyo3(name = "reload_entry", signature = (entry, **kwargs))]
+pub fn py_reload_entry(entry: String, kwargs: Option<HashMap<String, Py<PyAny>>>) -> PyResult<()> {
+    let index = scope::entry_index(&entry)
+        .ok_or_else(|| PyValueError::new_err(format!("Unknown script entry: {entry:?}")))?;
+    request_reload(Some(index), kwargs);
+    Ok(())
+}
+
+fn request_reload(target: Option<usize>, kwargs: Option<HashMap<String, Py<PyAny>>>) {
     let mut var = match GLOBAL_VAR.try_lock() {
         Ok(var) => var,
         Err(_) => {
@@ -305,30 +339,43 @@ pub fn py_reload(kwargs: Option<HashMap<String, Py<PyAny>>>) {
     }
     event!(
         Level::DEBUG,
-        "Script need reload, kwargs: {:?}",
+        "Script need reload, target: {target:?}, kwargs: {:?}",
         kwargs.clone()
     );
     var.need_reload = true;
+    var.reload_target = target;
     var.reload_var = kwargs;
 }
 
+/// 设置当前入口的 error handler，传 None 取消
 #[pyfunction]
-#[pyo3(name = "set_error_handler", signature = (handler))]
-pub fn py_set_error_handler(handler: Py<PyAny>) {
-    GLOBAL_VAR
-        .lock()
-        .expect("Failed to lock global var")
-        .error_handler = Some(handler);
+#[pyo3(name = "set_error_handler", signature = (handler = None))]
+pub fn py_set_error_handler(handler: Option<Py<PyAny>>) {
+    let scope = scope::current_scope();
+    let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
+    match handler {
+        Some(handler) => {
+            var.error_handlers.insert(scope, handler);
+        }
+        None => {
+            var.error_handlers.remove(&scope);
+        }
+    }
 }
 
 #[pyfunction]
 #[pyo3(name = "get_error_handler")]
-pub fn py_get_error_handler() -> Option<Py<PyAny>> {
-    GLOBAL_VAR
-        .lock()
-        .expect("Failed to lock global var")
-        .error_handler
-        .clone()
+pub fn py_get_error_handler(py: Python<'_>) -> Option<Py<PyAny>> {
+    error_handler_for(py, scope::current_scope())
+}
+
+/// 入口自己没设置的话用不属于任何入口的那个
+pub fn error_handler_for(py: Python<'_>, scope: Option<usize>) -> Option<Py<PyAny>> {
+    let var = GLOBAL_VAR.lock().unwrap_or_else(PoisonError::into_inner);
+    var.error_handlers
+        .get(&scope)
+        .or_else(|| var.error_handlers.get(&None))
+        .map(|handler| handler.clone_ref(py))
 }
 
 pub fn reload() {
@@ -339,18 +386,23 @@ pub fn reload() {
             return;
         }
     }
-    event!(Level::INFO, "Script start reload");
     let start_time = Instant::now();
 
-    let kwargs = {
+    let (kwargs, target) = {
         let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
         let kwargs = var.reload_var.take().unwrap_or_default();
         event!(Level::DEBUG, "Reload kwargs: {:?}", kwargs.clone());
-        kwargs
+        (kwargs, var.reload_target.take())
     };
+    match target {
+        Some(index) => event!(Level::INFO, "Script entry {index} start reload"),
+        None => event!(Level::INFO, "Script start reload"),
+    }
+    // 只重载一个入口的话，下面的事件都只交给这个入口
+    let _only = scope::deliver_only(target);
     let players = {
-        let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-        pool.get_all_players().clone()
+        let pool = read_pool();
+        pool.get_all_players()
     };
 
     event!(Level::DEBUG, "Reload players: {:?}", players.len());
@@ -363,9 +415,37 @@ pub fn reload() {
             .clone()
             .unwrap_or_default()
     };
+    let kept = kept_modules(capture_modules);
+
+    // 新脚本有问题的话旧脚本继续跑，什么都不拆
+    if let Err(traceback) = Python::with_gil(|py| precheck_script(py, &kept, target)) {
+        event!(
+            Level::ERROR,
+            "Script reload aborted, keeping old script: {traceback}"
+        );
+        Python::with_gil(|py| {
+            let _ = PY_CALLBACK_MANAGER.trigger(
+                py,
+                PyVcmpEvent::from(VcmpEvent::ServerReloadFailed(ServerReloadFailedEvent::new(
+                    traceback,
+                )))
+                .with_kwargs(kwargs.clone()),
+            );
+        });
+        let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
+        var.need_reload = false;
+        return;
+    }
+
     let mut elapsed_time = 0f64;
+    let mut reloaded = false;
+    let mut stashed = true;
 
     Python::with_gil(|py| {
+        // 先存状态，后面的 disconnect / shutdown 回调可能会把状态清掉
+        let count = reload_state::save_states(py, target);
+        event!(Level::DEBUG, "Saved {count} reload state(s)");
+
         event!(Level::DEBUG, "Callback manager trigger player disconnect");
         for player in players.clone() {
             let _ = PY_CALLBACK_MANAGER.trigger(
@@ -376,11 +456,10 @@ pub fn reload() {
                 .with_kwargs(kwargs.clone()),
             );
             {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player.get_id())
-                    .expect("Failed to get mut player");
-                player.set_var_reload_joined(false);
+                let mut pool = write_pool();
+                if let Some(player) = pool.get_mut_player(player.get_id()) {
+                    player.set_var_reload_joined(false);
+                }
             }
         }
 
@@ -391,50 +470,47 @@ pub fn reload() {
                 .with_kwargs(kwargs.clone()),
         );
 
-        py.allow_threads(|| {
-            let count = PY_CALLBACK_STORAGE
-                .lock()
-                .expect("Failed to lock PyCallbackStorage")
-                .clear();
-            event!(Level::DEBUG, "Cleared {count} callback(s)");
-        });
-
-        event!(Level::DEBUG, "Unload modules");
-        {
-            // 删 python 加载的模块
-            // Copy 一份新的 IGNORE_MODULES 防止修改原数组
-            let modules = IGNORE_MODULES
-                .clone()
-                .into_iter()
-                .chain(capture_modules)
-                .chain(get_ignore_py_modules())
-                .collect::<Vec<_>>();
-            event!(Level::DEBUG, "Unload modules: {:?}", modules);
-            let py_sys_modules = py
-                .import("sys")
-                .expect("Failed to import sys")
-                .getattr("modules")
-                .expect("Failed to get sys.modules");
-            let py_modules_unbind = py_sys_modules
-                .extract::<Py<PyDict>>()
-                .expect("Failed to extract sys.modules");
-            let py_modules = py_modules_unbind.bind(py);
-            for m in py_modules.keys() {
-                let m = m
-                    .extract::<String>()
-                    .expect("Failed to extract module name");
-                if modules.contains(&m) || m.starts_with("vcmp") {
-                    continue;
-                }
-                let _ = py_modules.del_item(m);
+        // 旧脚本的东西先收起来，新脚本加载失败就换回去
+        // 换回去之前旧脚本已经收到过上面的 disconnect / shutdown
+        event!(Level::DEBUG, "Unload modules, keep modules: {:?}", kept);
+        let stash = match ScriptStash::take(py, &kept, target) {
+            Ok(stash) => stash,
+            Err(e) => {
+                // 收起来失败的话什么都还没动，跟检查失败一样保留旧脚本
+                let traceback = get_traceback(&e, Some(py));
+                event!(
+                    Level::ERROR,
+                    "Script reload aborted, failed to stash old script: {traceback}"
+                );
+                let _ = PY_CALLBACK_MANAGER.trigger(
+                    py,
+                    PyVcmpEvent::from(VcmpEvent::ServerReloadFailed(ServerReloadFailedEvent::new(
+                        traceback,
+                    )))
+                    .with_kwargs(kwargs.clone()),
+                );
+                stashed = false;
+                return;
             }
-        }
+        };
 
         event!(Level::DEBUG, "Reload script");
-        py.allow_threads(|| {
-            load_script();
-        });
+        let loaded = py.allow_threads(|| load_script(target));
         event!(Level::DEBUG, "Reload script done");
+        if let Err(traceback) = &loaded {
+            event!(
+                Level::ERROR,
+                "Script reload failed, restoring old script: {traceback}"
+            );
+            stash.restore(py, &kept);
+        }
+
+        // 服务器里的 class 还在，这里只是把文件里新加的 class 补上
+        event!(Level::DEBUG, "Replay class file");
+        functions::player_class::load_class_file();
+
+        let count = reload_state::restore_states(py, target);
+        event!(Level::DEBUG, "Restored {count} reload state(s)");
 
         event!(Level::DEBUG, "Callback manager trigger server init");
         let _ = PY_CALLBACK_MANAGER.trigger(
@@ -445,18 +521,17 @@ pub fn reload() {
 
         // 重新获取玩家，防止玩家断开连接后，玩家列表为空
         let players = {
-            let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-            pool.get_all_players().clone()
+            let pool = read_pool();
+            pool.get_all_players()
         };
 
         event!(Level::DEBUG, "Callback manager trigger player join");
         for player in players.clone() {
             {
-                let mut pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-                let player = pool
-                    .get_mut_player(player.get_id())
-                    .expect("Failed to get mut player");
-                player.set_var_reload_joined(true);
+                let mut pool = write_pool();
+                if let Some(player) = pool.get_mut_player(player.get_id()) {
+                    player.set_var_reload_joined(true);
+                }
             }
             let _ = PY_CALLBACK_MANAGER.trigger(
                 py,
@@ -483,21 +558,36 @@ pub fn reload() {
             }
         }
 
-        event!(Level::DEBUG, "Callback manager trigger server reloaded");
-        let _ = PY_CALLBACK_MANAGER.trigger(
-            py,
-            PyVcmpEvent::from(VcmpEvent::ServerReloaded(ServerReloadedEvent::new({
-                elapsed_time = start_time.elapsed().as_secs_f64();
-                elapsed_time
-            })))
-            .with_kwargs(kwargs.clone()),
-        );
+        elapsed_time = start_time.elapsed().as_secs_f64();
+        let event = match loaded {
+            Ok(()) => {
+                reloaded = true;
+                event!(Level::DEBUG, "Callback manager trigger server reloaded");
+                VcmpEvent::ServerReloaded(ServerReloadedEvent::new(elapsed_time))
+            }
+            Err(traceback) => {
+                event!(
+                    Level::DEBUG,
+                    "Callback manager trigger server reload failed"
+                );
+                VcmpEvent::ServerReloadFailed(ServerReloadFailedEvent::new(traceback))
+            }
+        };
+        let _ =
+            PY_CALLBACK_MANAGER.trigger(py, PyVcmpEvent::from(event).with_kwargs(kwargs.clone()));
     });
 
-    event!(
-        Level::INFO,
-        "Script reloaded, elapsed time: {elapsed_time:.2?}"
-    );
+    if reloaded {
+        event!(
+            Level::INFO,
+            "Script reloaded, elapsed time: {elapsed_time:.2?}"
+        );
+    } else if stashed {
+        event!(
+            Level::ERROR,
+            "Script reload failed, old script restored, elapsed time: {elapsed_time:.2?}"
+        );
+    }
 
     {
         let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
@@ -505,34 +595,181 @@ pub fn reload() {
     }
 }
 
-pub fn load_script() {
-    let script_path_string = get_script_path();
-    let script_path = script_path_string.as_str();
-    let script = Path::new(script_path);
+/// 重载时不卸载的模块
+fn kept_modules(capture_modules: Vec<String>) -> Vec<String> {
+    // Copy 一份新的 IGNORE_MODULES 防止修改原数组
+    IGNORE_MODULES
+        .clone()
+        .into_iter()
+        .chain(capture_modules)
+        .chain(get_ignore_py_modules())
+        .collect()
+}
 
-    if !script.exists() {
-        event!(Level::ERROR, "Script file not found: {}", script_path);
-        return;
+fn is_kept_module(name: &str, kept: &[String]) -> bool {
+    kept.iter().any(|m| m == name) || name.starts_with("vcmp")
+}
+
+/// 重载前检查新脚本，失败返回 traceback
+///
+/// 先 compile 一遍看有没有语法错误，开了 `reload_dry_run` 再试着 import 一遍
+fn precheck_script(py: Python<'_>, kept: &[String], target: Option<usize>) -> Result<(), String> {
+    for (index, entry) in loader::script_entries().iter().enumerate() {
+        if !scope::in_target(Some(index), target) {
+            continue;
+        }
+        loader::compile_entry(py, entry)
+            .map_err(|e| format!("{entry}: {}", get_traceback(&e, Some(py))))?;
     }
-    let code = CString::new(std::fs::read_to_string(script).unwrap_or_default())
-        .expect("faild to create c string for code");
-    let c_path = CString::new(script.to_str().unwrap_or_default())
-        .expect("faild to create c string for path");
-    Python::with_gil(|py| {
-        let res = PyModule::from_code(
-            py,
-            &code,
-            &c_path,
-            &CString::new("__main__").expect("faild to create c string for module name"),
+
+    if !get_reload_dry_run() {
+        return Ok(());
+    }
+    event!(Level::DEBUG, "Dry run scripts");
+    dry_run_script(py, kept, target)
+}
+
+/// 试运行新脚本
+///
+/// 旧脚本的东西先收起来，跑完之后全部换回去，试运行注册的东西都丢掉。
+/// `__name__` 不是 `__main__`，所以 `if __name__ == "__main__"` 里的代码不会跑，
+/// `module:function` 入口也只 import 不调用，
+/// 其他顶层代码的副作用（比如创建载具）是撤不回来的
+fn dry_run_script(py: Python<'_>, kept: &[String], target: Option<usize>) -> Result<(), String> {
+    let stash = ScriptStash::take(py, kept, target).map_err(|e| get_traceback(&e, Some(py)))?;
+    let result = loader::dry_run_all(py, target);
+    stash.restore(py, kept);
+    result
+}
+
+/// 旧脚本的模块和注册的东西
+///
+/// 加载新脚本之前收起来，试运行完或者新脚本加载失败的时候再换回去。
+/// target 是要重载的入口，None 是全部
+struct ScriptStash {
+    target: Option<usize>,
+    sys_modules: Py<PyDict>,
+    modules: Py<PyDict>,
+    entry_modules: HashMap<usize, Vec<String>>,
+    callbacks: PyCallbackStorage,
+    routes: HashMap<i32, MessageRoute>,
+    hooks: ReloadHooks,
+    error_handlers: HashMap<Option<usize>, Py<PyAny>>,
+}
+
+impl ScriptStash {
+    /// 脚本自己的模块从 sys.modules 挪开，回调、消息处理函数、重载状态的函数和 error handler 也收起来
+    ///
+    /// 只重载一个入口的话只挪这个入口加载时 import 的模块
+    fn take(py: Python<'_>, kept: &[String], target: Option<usize>) -> PyResult<Self> {
+        let sys_modules = py
+            .import("sys")?
+            .getattr("modules")?
+            .extract::<Bound<'_, PyDict>>()?;
+
+        let names = match target {
+            None => script_modules(&sys_modules, kept),
+            Some(index) => scope::entry_modules(index)
+                .into_iter()
+                .filter(|name| !is_kept_module(name, kept))
+                .map(|name| PyString::new(py, &name).into_any())
+                .collect(),
+        };
+        let modules = PyDict::new(py);
+        for name in names {
+            if let Ok(Some(module)) = sys_modules.get_item(&name) {
+                let _ = modules.set_item(&name, module);
+                let _ = sys_modules.del_item(name);
+            }
+        }
+        let callbacks = py.allow_threads(|| {
+            PY_CALLBACK_STORAGE
+                .lock()
+                .expect("Failed to lock PyCallbackStorage")
+                .take_target(target)
+        });
+        event!(
+            Level::DEBUG,
+            "Stashed {} module(s), {} callback(s)",
+            modules.len(),
+            callbacks.size()
         );
-        if let Err(e) = res {
-            event!(
-                Level::ERROR,
-                "Failed to load script: {}",
-                get_traceback(&e, Some(py))
-            );
-        } else {
-            event!(Level::INFO, "Script loaded: {}", script_path);
+        let error_handlers = {
+            let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
+            match target {
+                None => std::mem::take(&mut var.error_handlers),
+                Some(index) => var
+                    .error_handlers
+                    .remove_entry(&Some(index))
+                    .into_iter()
+                    .collect(),
+            }
+        };
+
+        Ok(Self {
+            target,
+            sys_modules: sys_modules.unbind(),
+            modules: modules.unbind(),
+            entry_modules: scope::take_entry_modules(target),
+            callbacks,
+            routes: streams::message::take_routes(target),
+            hooks: reload_state::take_hooks(target),
+            error_handlers,
+        })
+    }
+
+    /// 丢掉新脚本加载的模块和注册的东西，把旧的换回去
+    fn restore(self, py: Python<'_>, kept: &[String]) {
+        let target = self.target;
+        {
+            let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
+            match target {
+                None => var.error_handlers = self.error_handlers,
+                Some(index) => {
+                    var.error_handlers.remove(&Some(index));
+                    var.error_handlers.extend(self.error_handlers);
+                }
+            }
         }
-    });
+        streams::message::restore_routes(self.routes, target);
+        reload_state::restore_hooks(self.hooks, target);
+        py.allow_threads(|| {
+            PY_CALLBACK_STORAGE
+                .lock()
+                .expect("Failed to lock PyCallbackStorage")
+                .restore_target(self.callbacks, target);
+        });
+        let sys_modules = self.sys_modules.bind(py);
+        let loaded = match target {
+            None => script_modules(sys_modules, kept),
+            Some(index) => scope::entry_modules(index)
+                .into_iter()
+                .filter(|name| !is_kept_module(name, kept))
+                .map(|name| PyString::new(py, &name).into_any())
+                .collect(),
+        };
+        for name in loaded {
+            let _ = sys_modules.del_item(name);
+        }
+        let _ = sys_modules.update(self.modules.bind(py).as_mapping());
+        scope::restore_entry_modules(self.entry_modules, target);
+    }
+}
+
+fn script_modules<'py>(modules: &Bound<'py, PyDict>, kept: &[String]) -> Vec<Bound<'py, PyAny>> {
+    modules
+        .keys()
+        .into_iter()
+        .filter(|name| {
+            name.extract::<String>()
+                .is_ok_and(|name| !is_kept_module(&name, kept))
+        })
+        .collect()
+}
+
+/// 加载脚本，target 是只加载的入口，None 是全部
+///
+/// 返回第一个失败的入口的 traceback，失败的入口已经打过日志了
+pub fn load_script(target: Option<usize>) -> Result<(), String> {
+    Python::with_gil(|py| loader::load_all(py, target))
 }
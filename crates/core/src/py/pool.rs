@@ -2,7 +2,7 @@ use crate::functions::{
     checkpoint::CheckPointPy, marker::MarkerPy, object::ObjectPy, pickup::PickupPy,
     player::PlayerPy, vehicle::VehiclePy,
 };
-use crate::pool::ENTITY_POOL;
+use crate::pool::{self, read_pool};
 
 use pyo3::types::PyModuleMethods;
 use pyo3::{Bound, PyResult, Python, pyfunction, types::PyModule, wrap_pyfunction};
@@ -11,7 +11,7 @@ use pyo3::{Py, PyAny};
 #[pyfunction]
 #[pyo3(signature = (all = false))]
 pub fn get_players(all: bool) -> Vec<PlayerPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     if all {
         pool.get_all_players()
     } else {
@@ -21,31 +21,31 @@ pub fn get_players(all: bool) -> Vec<PlayerPy> {
 
 #[pyfunction]
 pub fn get_vehicles() -> Vec<VehiclePy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_vehicles()
 }
 
 #[pyfunction]
 pub fn get_pickups() -> Vec<PickupPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_pickups()
 }
 
 #[pyfunction]
 pub fn get_objects() -> Vec<ObjectPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_objects()
 }
 
 #[pyfunction]
 pub fn get_markers() -> Vec<MarkerPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_markers()
 }
 
 #[pyfunction]
 pub fn get_checkpoints() -> Vec<CheckPointPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_checkpoints()
 }
 
@@ -113,6 +113,18 @@ pub fn find_player(py: Python<'_>, value: Py<PyAny>) -> Option<PlayerPy> {
     }
 }
 
+/// 实体池不同步（回调里找不到实体，自动补上）的次数
+#[pyfunction]
+pub fn get_desync_count() -> usize {
+    pool::get_desync_count()
+}
+
+/// 重置不同步计数，返回重置前的值
+#[pyfunction]
+pub fn reset_desync_count() -> usize {
+    pool::reset_desync_count()
+}
+
 pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(get_players, m)?)?;
     m.add_function(wrap_pyfunction!(get_vehicles, m)?)?;
@@ -127,5 +139,7 @@ pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(clear_pickups, m)?)?;
     m.add_function(wrap_pyfunction!(clear_all, m)?)?;
     m.add_function(wrap_pyfunction!(find_player, m)?)?;
+    m.add_function(wrap_pyfunction!(get_desync_count, m)?)?;
+    m.add_function(wrap_pyfunction!(reset_desync_count, m)?)?;
     Ok(())
 }
@@ -0,0 +1,315 @@
+//! 重载时保存脚本状态
+//!
+//! 重载会把脚本的模块全部卸载，模块里的全局变量也就没了。
+//! `on_before_reload` 注册的函数在卸载之前调用，返回一个 dict，
+//! 用 pickle 存成 bytes 放在插件这边，新脚本加载完之后交给同名的 `on_after_reload`。
+//!
+//! ```python
+//! from vcmp import reload_state
+//!
+//! scores = {}
+//!
+//! @reload_state.on_before_reload(version=2)
+//! def save():
+//!     return {"scores": scores}
+//!
+//! @reload_state.on_after_reload
+//! def load(state, version):
+//!     if version < 2:
+//!         ...  # 迁移旧格式
+//!     scores.update(state["scores"])
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use pyo3::{
+    Bound, Py, PyAny, PyResult, Python,
+    exceptions::PyValueError,
+    pyfunction,
+    types::{
+        PyAnyMethods, PyBytes, PyCFunction, PyDict, PyModule, PyModuleMethods, PyTupleMethods,
+    },
+    wrap_pyfunction,
+};
+use tracing::{Level, event};
+
+use crate::py::get_traceback;
+use crate::py::scope::{current_scope, in_target};
+
+/// scope 是注册这个函数的入口，单独重载一个入口的时候只调用这个入口的
+struct BeforeHook {
+    func: Py<PyAny>,
+    version: i64,
+    scope: Option<usize>,
+}
+
+#[derive(Default)]
+pub(crate) struct ReloadHooks {
+    before: HashMap<String, BeforeHook>,
+    after: Vec<(String, Option<usize>, Py<PyAny>)>,
+}
+
+struct SavedState {
+    version: i64,
+    data: Vec<u8>,
+}
+
+static RELOAD_HOOKS: LazyLock<Mutex<ReloadHooks>> =
+    LazyLock::new(|| Mutex::new(ReloadHooks::default()));
+
+/// 还没被 `on_after_reload` 取走的状态，下次重载同名的会被覆盖
+static SAVED_STATES: LazyLock<Mutex<HashMap<String, SavedState>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 没给名字就用函数所在的模块名，这样同一个文件里的 before / after 能对上
+///
+/// 文件入口的模块名都是 `__main__`，这时候用文件路径区分
+fn hook_name(func: &Bound<'_, PyAny>, name: Option<String>) -> PyResult<String> {
+    if let Some(name) = name {
+        return Ok(name);
+    }
+    let module = func.getattr("__module__")?.extract::<String>()?;
+    if module != "__main__" {
+        return Ok(module);
+    }
+    match func
+        .getattr("__code__")
+        .and_then(|code| code.getattr("co_filename"))
+    {
+        Ok(file) => file.extract::<String>(),
+        Err(_) => Ok(module),
+    }
+}
+
+fn register_before(func: &Bound<'_, PyAny>, name: Option<String>, version: i64) -> PyResult<()> {
+    let name = hook_name(func, name)?;
+    let mut hooks = RELOAD_HOOKS.lock().expect("Failed to lock reload hooks");
+    if hooks.before.contains_key(&name) {
+        return Err(PyValueError::new_err(format!(
+            "Reload state {name:?} already has a before reload hook"
+        )));
+    }
+    hooks.before.insert(
+        name,
+        BeforeHook {
+            func: func.clone().unbind(),
+            version,
+            scope: current_scope(),
+        },
+    );
+    Ok(())
+}
+
+fn register_after(func: &Bound<'_, PyAny>, name: Option<String>) -> PyResult<()> {
+    let name = hook_name(func, name)?;
+    RELOAD_HOOKS
+        .lock()
+        .expect("Failed to lock reload hooks")
+        .after
+        .push((name, current_scope(), func.clone().unbind()));
+    Ok(())
+}
+
+/// 注册重载前保存状态的函数，func() -> dict | None
+///
+/// 不传 func 就当装饰器用
+#[pyfunction]
+#[pyo3(signature = (func = None, name = None, version = 1))]
+pub fn on_before_reload(
+    py: Python<'_>,
+    func: Option<Py<PyAny>>,
+    name: Option<String>,
+    version: i64,
+) -> PyResult<Py<PyAny>> {
+    if let Some(func) = func {
+        register_before(func.bind(py), name, version)?;
+        return Ok(func);
+    }
+    let decorator = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args, _kwargs| -> PyResult<Py<PyAny>> {
+            let func = args.get_item(0)?;
+            register_before(&func, name.clone(), version)?;
+            Ok(func.unbind())
+        },
+    )?;
+    Ok(decorator.into_any().unbind())
+}
+
+/// 注册重载后恢复状态的函数，func(state: dict, version: int)
+///
+/// 只有上次重载保存了同名状态才会调用
+#[pyfunction]
+#[pyo3(signature = (func = None, name = None))]
+pub fn on_after_reload(
+    py: Python<'_>,
+    func: Option<Py<PyAny>>,
+    name: Option<String>,
+) -> PyResult<Py<PyAny>> {
+    if let Some(func) = func {
+        register_after(func.bind(py), name)?;
+        return Ok(func);
+    }
+    let decorator = PyCFunction::new_closure(
+        py,
+        None,
+        None,
+        move |args, _kwargs| -> PyResult<Py<PyAny>> {
+            let func = args.get_item(0)?;
+            register_after(&func, name.clone())?;
+            Ok(func.unbind())
+        },
+    )?;
+    Ok(decorator.into_any().unbind())
+}
+
+fn dump_state(py: Python<'_>, hook: &BeforeHook) -> PyResult<Option<Vec<u8>>> {
+    let state = hook.func.call0(py)?.into_bound(py);
+    if state.is_none() {
+        return Ok(None);
+    }
+    if !state.is_instance_of::<PyDict>() {
+        return Err(PyValueError::new_err(format!(
+            "Before reload hook must return a dict, got {}",
+            state.get_type()
+        )));
+    }
+    let data = py.import("pickle")?.call_method1("dumps", (state,))?;
+    Ok(Some(data.extract::<Vec<u8>>()?))
+}
+
+/// 卸载模块之前调用，保存 target 入口的状态，None 是全部，返回保存了几份
+///
+/// 注册的函数留着不动，重载时跟回调一起收起来，新脚本加载失败还要换回去
+pub fn save_states(py: Python<'_>, target: Option<usize>) -> usize {
+    let before = {
+        let hooks = RELOAD_HOOKS.lock().expect("Failed to lock reload hooks");
+        hooks
+            .before
+            .iter()
+            .filter(|(_, hook)| in_target(hook.scope, target))
+            .map(|(name, hook)| {
+                (
+                    name.clone(),
+                    BeforeHook {
+                        func: hook.func.clone_ref(py),
+                        version: hook.version,
+                        scope: hook.scope,
+                    },
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut count = 0;
+    for (name, hook) in before {
+        match dump_state(py, &hook) {
+            Ok(Some(data)) => {
+                SAVED_STATES
+                    .lock()
+                    .expect("Failed to lock saved states")
+                    .insert(
+                        name,
+                        SavedState {
+                            version: hook.version,
+                            data,
+                        },
+                    );
+                count += 1;
+            }
+            Ok(None) => {}
+            Err(e) => event!(
+                Level::ERROR,
+                "Failed to save reload state {name:?}: {}",
+                get_traceback(&e, Some(py))
+            ),
+        }
+    }
+    count
+}
+
+fn load_state(py: Python<'_>, func: &Py<PyAny>, state: &SavedState) -> PyResult<()> {
+    let data = PyBytes::new(py, &state.data);
+    let value = py.import("pickle")?.call_method1("loads", (data,))?;
+    func.call1(py, (value, state.version))?;
+    Ok(())
+}
+
+/// 新脚本加载完之后调用，把保存的状态交给 target 入口的 `on_after_reload`，返回调用了几个函数
+pub fn restore_states(py: Python<'_>, target: Option<usize>) -> usize {
+    let after = {
+        let hooks = RELOAD_HOOKS.lock().expect("Failed to lock reload hooks");
+        hooks
+            .after
+            .iter()
+            .filter(|(_, scope, _)| in_target(*scope, target))
+            .map(|(name, _, func)| (name.clone(), func.clone_ref(py)))
+            .collect::<Vec<_>>()
+    };
+    let mut states =
+        std::mem::take(&mut *SAVED_STATES.lock().expect("Failed to lock saved states"));
+    let mut restored = Vec::new();
+    for (name, func) in after {
+        let Some(state) = states.get(&name) else {
+            continue;
+        };
+        if let Err(e) = load_state(py, &func, state) {
+            event!(
+                Level::ERROR,
+                "Failed to restore reload state {name:?}: {}",
+                get_traceback(&e, Some(py))
+            );
+        }
+        restored.push(name);
+    }
+    for name in &restored {
+        states.remove(name);
+    }
+    if !states.is_empty() {
+        event!(
+            Level::DEBUG,
+            "Unclaimed reload states: {:?}",
+            states.keys().collect::<Vec<_>>()
+        );
+    }
+    SAVED_STATES
+        .lock()
+        .expect("Failed to lock saved states")
+        .extend(states);
+    restored.len()
+}
+
+/// 加载新脚本之前先把 target 入口注册的函数收起来，None 是全部
+pub(crate) fn take_hooks(target: Option<usize>) -> ReloadHooks {
+    let mut hooks = RELOAD_HOOKS.lock().expect("Failed to lock reload hooks");
+    let before = hooks
+        .before
+        .extract_if(|_, hook| in_target(hook.scope, target))
+        .collect();
+    let after = hooks
+        .after
+        .extract_if(.., |(_, scope, _)| in_target(*scope, target))
+        .collect();
+    ReloadHooks { before, after }
+}
+
+/// 丢掉 target 入口新注册的函数，把收起来的放回去
+pub(crate) fn restore_hooks(taken: ReloadHooks, target: Option<usize>) {
+    let mut hooks = RELOAD_HOOKS.lock().expect("Failed to lock reload hooks");
+    hooks
+        .before
+        .retain(|_, hook| !in_target(hook.scope, target));
+    hooks
+        .after
+        .retain(|(_, scope, _)| !in_target(*scope, target));
+    hooks.before.extend(taken.before);
+    hooks.after.extend(taken.after);
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(on_before_reload, m)?)?;
+    m.add_function(wrap_pyfunction!(on_after_reload, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,239 @@
+//! 按脚本入口分开事件和状态
+//!
+//! `script_path` 和 `scripts` 里的每个入口是一个作用域，加载入口时注册的回调记在这个入口下面，
+//! 回调里再注册的回调跟着正在跑的那个回调。
+//! 入口可以在配置里限定 world，也可以用 `callbacks.set_filter` 设一个过滤函数，
+//! 事件先按入口过滤再交给回调，这样一个服务器可以同时跑几个互不干扰的玩法：
+//!
+//! ```toml
+//! [script]
+//! script_path = "lobby.py"
+//! scripts = ["race.py", "deathmatch"]
+//!
+//! [script.worlds]
+//! "race.py" = [2]
+//! deathmatch = [3, 4]
+//! ```
+//!
+//! 事件里没有 player / vehicle / object / pickup / checkpoint 的（比如 server frame）所有入口都收得到，
+//! `PlayerWorldChange` 新旧两个 world 的入口都收得到。
+//!
+//! 回调、过滤函数、消息处理函数、重载状态的函数和 error handler 都是按入口存的，
+//! 加载入口时 import 的模块也记在这个入口下面。
+//! `vcmp.reload_entry` 只重载一个入口：只有这个入口的回调收到 disconnect / shutdown，
+//! 只卸载这个入口的模块，别的入口的模块、回调和状态都不动。
+//! 几个入口 import 同一个模块的话，模块算第一个 import 它的入口，
+//! 回调里才 import 的模块不记，要整个重载才会卸载。
+//!
+//! 所有入口还是在同一个解释器里（pyo3 的模块不支持子解释器），
+//! 互相 import 对方的模块还是能改到对方的全局变量。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+
+use pyo3::{Py, PyAny, Python, types::PyAnyMethods};
+use tracing::{Level, event};
+
+use crate::cfg::{get_script_entries, get_script_worlds};
+use crate::py::events::VcmpEvent;
+use crate::py::get_traceback;
+
+/// 现在是哪个入口在跑，入口的编号就是配置里的顺序
+static CURRENT_SCOPE: Mutex<Option<usize>> = Mutex::new(None);
+
+/// 只重载一个入口的时候，事件只交给这个入口
+static ONLY_SCOPE: Mutex<Option<usize>> = Mutex::new(None);
+
+/// 每个入口加载时 import 的模块
+static ENTRY_MODULES: LazyLock<Mutex<HashMap<usize, Vec<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static SCRIPT_WORLDS: LazyLock<Vec<Option<Vec<i32>>>> = LazyLock::new(get_script_worlds);
+
+/// 带 world 的实体，按顺序找第一个有的
+const WORLD_ENTITIES: [&str; 5] = ["player", "vehicle", "object", "pickup", "checkpoint"];
+
+/// 事件回调里也会用到，锁被 panic 污染了也照样用
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub fn current_scope() -> Option<usize> {
+    *lock(&CURRENT_SCOPE)
+}
+
+/// 重载的目标，None 是所有入口，注册的东西属于目标入口就算在里面
+pub fn in_target(scope: Option<usize>, target: Option<usize>) -> bool {
+    target.is_none() || scope == target
+}
+
+/// 切换当前的入口，drop 的时候换回原来的
+pub struct ScopeGuard {
+    slot: &'static Mutex<Option<usize>>,
+    previous: Option<usize>,
+}
+
+impl ScopeGuard {
+    fn replace(slot: &'static Mutex<Option<usize>>, scope: Option<usize>) -> Self {
+        let previous = std::mem::replace(&mut *lock(slot), scope);
+        Self { slot, previous }
+    }
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        *lock(self.slot) = self.previous;
+    }
+}
+
+pub fn enter_scope(scope: Option<usize>) -> ScopeGuard {
+    ScopeGuard::replace(&CURRENT_SCOPE, scope)
+}
+
+/// 之后的事件只交给 target 入口的回调，None 就是不限制
+pub fn deliver_only(target: Option<usize>) -> ScopeGuard {
+    ScopeGuard::replace(&ONLY_SCOPE, target)
+}
+
+/// 配置里写的入口或者入口的编号
+pub fn entry_index(entry: &str) -> Option<usize> {
+    let entries = get_script_entries();
+    entries
+        .iter()
+        .position(|e| e.trim() == entry.trim())
+        .or_else(|| entry.trim().parse().ok().filter(|&i| i < entries.len()))
+}
+
+pub fn entry_modules(scope: usize) -> Vec<String> {
+    lock(&ENTRY_MODULES)
+        .get(&scope)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn set_entry_modules(scope: usize, modules: Vec<String>) {
+    lock(&ENTRY_MODULES).insert(scope, modules);
+}
+
+/// 收起 target 入口的模块记录，None 是全部
+pub(crate) fn take_entry_modules(target: Option<usize>) -> HashMap<usize, Vec<String>> {
+    let mut modules = lock(&ENTRY_MODULES);
+    match target {
+        None => std::mem::take(&mut *modules),
+        Some(scope) => modules.remove_entry(&scope).into_iter().collect(),
+    }
+}
+
+pub(crate) fn restore_entry_modules(taken: HashMap<usize, Vec<String>>, target: Option<usize>) {
+    let mut modules = lock(&ENTRY_MODULES);
+    match target {
+        None => *modules = taken,
+        Some(scope) => {
+            modules.remove(&scope);
+            modules.extend(taken);
+        }
+    }
+}
+
+fn scope_name(scope: usize) -> String {
+    get_script_entries()
+        .get(scope)
+        .cloned()
+        .unwrap_or_else(|| scope.to_string())
+}
+
+/// 事件发生在哪些 world，找不到实体就是空的
+fn event_worlds(py: Python<'_>, event: &VcmpEvent, py_event: &Py<PyAny>) -> Vec<i32> {
+    if let VcmpEvent::PlayerWorldChange(event) = event {
+        return vec![event.old_world, event.new_world];
+    }
+    let py_event = py_event.bind(py);
+    for name in WORLD_ENTITIES {
+        let world = py_event
+            .getattr(name)
+            .and_then(|entity| entity.getattr("world"))
+            .and_then(|world| world.extract::<i32>());
+        if let Ok(world) = world {
+            return vec![world];
+        }
+    }
+    Vec::new()
+}
+
+/// 一个事件该不该交给某个入口的回调
+///
+/// 每个入口只判断一次，过滤函数也只调用一次
+pub struct EventRoute<'a> {
+    event: &'a VcmpEvent,
+    only: Option<usize>,
+    py_event: &'a Py<PyAny>,
+    filters: &'a HashMap<usize, Py<PyAny>>,
+    worlds: Option<Vec<i32>>,
+    decided: HashMap<usize, bool>,
+}
+
+impl<'a> EventRoute<'a> {
+    pub fn new(
+        event: &'a VcmpEvent,
+        py_event: &'a Py<PyAny>,
+        filters: &'a HashMap<usize, Py<PyAny>>,
+    ) -> Self {
+        Self {
+            event,
+            only: *lock(&ONLY_SCOPE),
+            py_event,
+            filters,
+            worlds: None,
+            decided: HashMap::new(),
+        }
+    }
+
+    /// 不属于任何入口的回调（比如在控制台里注册的）什么事件都收，只重载一个入口的时候除外
+    pub fn allows(&mut self, py: Python<'_>, scope: Option<usize>) -> bool {
+        if self.only.is_some() && scope != self.only {
+            return false;
+        }
+        let Some(scope) = scope else {
+            return true;
+        };
+        if let Some(&allowed) = self.decided.get(&scope) {
+            return allowed;
+        }
+        let allowed = self.in_worlds(py, scope) && self.passes_filter(py, scope);
+        self.decided.insert(scope, allowed);
+        allowed
+    }
+
+    fn in_worlds(&mut self, py: Python<'_>, scope: usize) -> bool {
+        let Some(Some(allowed)) = SCRIPT_WORLDS.get(scope) else {
+            return true;
+        };
+        let worlds = self
+            .worlds
+            .get_or_insert_with(|| event_worlds(py, self.event, self.py_event));
+        worlds.is_empty() || worlds.iter().any(|world| allowed.contains(world))
+    }
+
+    /// 过滤函数出错的话这个入口收不到这个事件，不影响别的入口
+    fn passes_filter(&self, py: Python<'_>, scope: usize) -> bool {
+        let Some(filter) = self.filters.get(&scope) else {
+            return true;
+        };
+        let _scope = enter_scope(Some(scope));
+        match filter
+            .call1(py, (self.py_event.clone_ref(py),))
+            .and_then(|res| res.bind(py).is_truthy())
+        {
+            Ok(res) => res,
+            Err(e) => {
+                event!(
+                    Level::ERROR,
+                    "Failed to call event filter of {}: {}",
+                    scope_name(scope),
+                    get_traceback(&e, Some(py))
+                );
+                false
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+//! `vcmp.settings`
+//!
+//! 把散落在 `functions::server` 和 `functions::environment` 里的设置项统一成一个对象，
+//! 通过它修改的设置会触发 `SettingChangedEvent`。
+
+use pyo3::{
+    Bound, IntoPyObjectExt, Py, PyAny, PyResult, Python,
+    exceptions::{PyAttributeError, PyValueError},
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyDict, PyDictMethods},
+};
+use vcmp_bindings::{func::ServerMethods, vcmp_func};
+
+use crate::{
+    functions::{environment, server},
+    py::{
+        callbacks::PY_CALLBACK_MANAGER,
+        events::{VcmpEvent, server::SettingChangedEvent},
+    },
+};
+
+type Getter = fn(Python<'_>) -> PyResult<Py<PyAny>>;
+/// 真正去改设置的那一步
+type Apply = Box<dyn FnOnce()>;
+/// 只检查值的类型，返回 [`Apply`]，这样 restore 可以先全部检查完再改
+type Setter = fn(&Bound<'_, PyAny>) -> PyResult<Apply>;
+
+struct Setting {
+    name: &'static str,
+    get: Getter,
+    /// None 就是只读
+    set: Option<Setter>,
+    /// 密码之类的，snapshot 默认不导出
+    secret: bool,
+}
+
+macro_rules! setting {
+    ($name:literal, $get:expr) => {
+        Setting {
+            name: $name,
+            get: |py| ($get)().into_py_any(py),
+            set: None,
+            secret: false,
+        }
+    };
+    ($name:literal: $ty:ty, $get:expr, $set:expr, secret = $secret:literal) => {
+        Setting {
+            name: $name,
+            get: |py| ($get)().into_py_any(py),
+            set: Some(|value| {
+                let value = value.extract::<$ty>()?;
+                Ok(Box::new(move || ($set)(value)) as Apply)
+            }),
+            secret: $secret,
+        }
+    };
+    ($name:literal: $ty:ty, $get:expr, $set:expr) => {
+        setting!($name: $ty, $get, $set, secret = false)
+    };
+}
+
+static SETTINGS: &[Setting] = &[
+    // server
+    setting!("server_name": String, server::get_servername, |v: String| {
+        server::set_servername(&v)
+    }),
+    setting!(
+        "password": String,
+        server::get_password,
+        |v: String| server::set_password(&v),
+        secret = true
+    ),
+    setting!("gamemode": String, server::get_gamemode, |v: String| {
+        server::set_gamemode(&v)
+    }),
+    setting!(
+        "max_players": u32,
+        server::get_max_players,
+        server::set_max_players
+    ),
+    setting!("port", || vcmp_func().server_settings().port()),
+    setting!("flags", || vcmp_func().server_settings().flags()),
+    // world
+    setting!("hour": i32, environment::get_hour, environment::set_hour),
+    setting!(
+        "minute": i32,
+        environment::get_minute,
+        environment::set_minute
+    ),
+    setting!("time": i32, environment::get_time, environment::set_time),
+    setting!(
+        "time_rate": i32,
+        environment::get_time_rate,
+        environment::set_time_rate
+    ),
+    setting!(
+        "weather": i32,
+        environment::get_weather,
+        environment::set_weather
+    ),
+    setting!(
+        "gravity": f32,
+        environment::get_gravity,
+        environment::set_gravity
+    ),
+    setting!(
+        "gamespeed": f32,
+        environment::get_gamespeed,
+        environment::set_gamespeed
+    ),
+    setting!(
+        "water_level": f32,
+        environment::get_water_level,
+        environment::set_water_level
+    ),
+    setting!(
+        "max_flight_altitude": f32,
+        environment::get_max_flight_altitude,
+        environment::set_max_flight_altitude
+    ),
+    setting!(
+        "kill_command_delay": i32,
+        environment::get_kill_command_delay,
+        environment::set_kill_command_delay
+    ),
+    setting!(
+        "vehicles_forced_respawn_height": f32,
+        environment::get_vehicles_forced_respawn_height,
+        environment::set_vehicles_forced_respawn_height
+    ),
+    setting!(
+        "fall_timer": u16,
+        environment::get_fall_timer,
+        environment::set_fall_timer
+    ),
+    // flags
+    setting!(
+        "taxi_boost_jump": bool,
+        environment::get_taxi_boost_jump,
+        environment::set_taxi_boost_jump
+    ),
+    setting!(
+        "drive_on_water": bool,
+        environment::get_drive_on_water,
+        environment::set_drive_on_water
+    ),
+    setting!(
+        "fast_switch": bool,
+        environment::get_fast_switch,
+        environment::set_fast_switch
+    ),
+    setting!(
+        "friendly_fire": bool,
+        environment::get_friendly_fire,
+        environment::set_friendly_fire
+    ),
+    setting!(
+        "disable_drive_by": bool,
+        environment::get_disable_drive_by,
+        environment::set_disable_drive_by
+    ),
+    setting!(
+        "perfect_handling": bool,
+        environment::get_perfect_handling,
+        environment::set_perfect_handling
+    ),
+    setting!(
+        "flying_cars": bool,
+        environment::get_flying_cars,
+        environment::set_flying_cars
+    ),
+    setting!(
+        "jump_switch": bool,
+        environment::get_jump_switch,
+        environment::set_jump_switch
+    ),
+    setting!(
+        "show_markers": bool,
+        environment::get_show_markers,
+        environment::set_show_markers
+    ),
+    setting!(
+        "only_show_team_markers": bool,
+        environment::get_only_show_team_markers,
+        environment::set_only_show_team_markers
+    ),
+    setting!(
+        "stunt_bike": bool,
+        environment::get_stunt_bike,
+        environment::set_stunt_bike
+    ),
+    setting!(
+        "shoot_in_air": bool,
+        environment::get_shoot_in_air,
+        environment::set_shoot_in_air
+    ),
+    setting!(
+        "show_name_tags": bool,
+        environment::get_show_name_tags,
+        environment::set_show_name_tags
+    ),
+    setting!(
+        "join_messages": bool,
+        environment::get_join_messages,
+        environment::set_join_messages
+    ),
+    setting!(
+        "death_messages": bool,
+        environment::get_death_messages,
+        environment::set_death_messages
+    ),
+    setting!(
+        "chat_tags_enabled": bool,
+        environment::get_chat_tags_enabled,
+        environment::set_chat_tags_enabled
+    ),
+    setting!(
+        "use_classes": bool,
+        environment::get_use_classes,
+        environment::set_use_classes
+    ),
+    setting!(
+        "wall_glitch": bool,
+        environment::get_wall_glitch,
+        environment::set_wall_glitch
+    ),
+    setting!(
+        "disable_backface_culling": bool,
+        environment::get_disable_backface_culling,
+        environment::set_disable_backface_culling
+    ),
+    setting!(
+        "disable_heli_blade_damage": bool,
+        environment::get_disable_heli_blade_damage,
+        environment::set_disable_heli_blade_damage
+    ),
+];
+
+fn find_setting(name: &str) -> Option<&'static Setting> {
+    SETTINGS.iter().find(|s| s.name == name)
+}
+
+/// 检查新的值，还不会修改
+fn prepare_setting(setting: &Setting, value: &Bound<'_, PyAny>) -> PyResult<Apply> {
+    let Some(set) = setting.set else {
+        return Err(PyAttributeError::new_err(format!(
+            "Setting '{}' is read-only",
+            setting.name
+        )));
+    };
+    set(value)
+}
+
+fn apply_setting(py: Python<'_>, setting: &Setting, value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let apply = prepare_setting(setting, value)?;
+    commit_setting(py, setting, apply)
+}
+
+/// 修改设置，值真的变了才触发事件
+fn commit_setting(py: Python<'_>, setting: &Setting, apply: Apply) -> PyResult<()> {
+    let old_value = (setting.get)(py)?;
+    apply();
+    let new_value = (setting.get)(py)?;
+    if old_value.bind(py).ne(new_value.bind(py))? {
+        let event = SettingChangedEvent::from((setting.name.to_string(), old_value, new_value));
+        let _ = PY_CALLBACK_MANAGER.handle(VcmpEvent::SettingChanged(event), false);
+    }
+    Ok(())
+}
+
+#[pyclass]
+#[pyo3(name = "Settings")]
+pub struct SettingsPy;
+
+#[pymethods]
+impl SettingsPy {
+    fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+        match find_setting(name) {
+            Some(setting) => (setting.get)(py),
+            None => Err(PyAttributeError::new_err(format!(
+                "Unknown setting: {name}"
+            ))),
+        }
+    }
+
+    fn __setattr__(&self, py: Python<'_>, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        match find_setting(name) {
+            Some(setting) => apply_setting(py, setting, value),
+            None => Err(PyAttributeError::new_err(format!(
+                "Unknown setting: {name}"
+            ))),
+        }
+    }
+
+    fn __dir__(&self) -> Vec<&'static str> {
+        self.keys()
+    }
+
+    /// 所有设置项的名字
+    fn keys(&self) -> Vec<&'static str> {
+        SETTINGS.iter().map(|s| s.name).collect()
+    }
+
+    fn is_read_only(&self, name: &str) -> PyResult<bool> {
+        find_setting(name)
+            .map(|s| s.set.is_none())
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown setting: {name}")))
+    }
+
+    /// 当前所有设置（包括只读的），密码要传 include_secrets=True 才会导出
+    #[pyo3(signature = (include_secrets = false))]
+    fn snapshot<'py>(
+        &self,
+        py: Python<'py>,
+        include_secrets: bool,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for setting in SETTINGS {
+            if setting.secret && !include_secrets {
+                continue;
+            }
+            dict.set_item(setting.name, (setting.get)(py)?)?;
+        }
+        Ok(dict)
+    }
+
+    /// 还原 `snapshot` 的结果，只读的设置会被跳过
+    ///
+    /// 先检查所有名字和值，有不认识的名字或者类型不对的值就一个都不改
+    fn restore(&self, py: Python<'_>, snapshot: &Bound<'_, PyDict>) -> PyResult<()> {
+        let mut pending = Vec::with_capacity(snapshot.len());
+        for (key, value) in snapshot.iter() {
+            let name = key.extract::<String>()?;
+            let setting = find_setting(&name)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown setting: {name}")))?;
+            if setting.set.is_some() {
+                pending.push((setting, prepare_setting(setting, &value)?));
+            }
+        }
+        for (setting, apply) in pending {
+            commit_setting(py, setting, apply)?;
+        }
+        Ok(())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Settings(keys={})", SETTINGS.len())
+    }
+}
@@ -1,24 +1,84 @@
 use pyo3::{
     Bound, Py, PyResult, Python,
     exceptions::{PyEOFError, PyValueError},
-    pyclass, pymethods,
+    pyclass, pyfunction, pymethods,
     types::{
         PyAny, PyAnyMethods, PyByteArray, PyByteArrayMethods, PyBytes, PyBytesMethods, PyModule,
         PyModuleMethods,
     },
+    wrap_pyfunction,
 };
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
-use std::io::{Cursor, Read, Write};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use tracing::{Level, event};
 use vcmp_bindings::encodes::{decode_gbk, encode_to_gbk};
+use vcmp_bindings::utils::Vectorf32;
+use vcmp_bindings::{func::PlayerMethods, vcmp_func};
+
+use crate::functions::player::PlayerPy;
+use crate::pool::read_pool;
+use crate::py::{bytes_repr, types::VectorPy};
+
+pub mod chunk;
+pub mod compress;
+pub mod message;
+
+/// 数字的字节序，默认是大端（squirrel 的 Stream 也是大端）
+///
+/// sq_string 的长度前缀固定是大端，不受这个影响
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ByteOrder {
+    #[default]
+    Big,
+    Little,
+}
 
-use crate::py::bytes_repr;
+impl ByteOrder {
+    fn parse(value: &str) -> PyResult<Self> {
+        match value {
+            "big" => Ok(Self::Big),
+            "little" => Ok(Self::Little),
+            _ => Err(PyValueError::new_err(
+                "byte_order must be 'big' or 'little'",
+            )),
+        }
+    }
 
-#[derive(Clone)]
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Big => "big",
+            Self::Little => "little",
+        }
+    }
+}
+
+/// 按字节序把 `[u8; N]` 转成数字
+macro_rules! from_bytes {
+    ($order:expr, $ty:ty, $buf:expr) => {
+        match $order {
+            ByteOrder::Big => <$ty>::from_be_bytes($buf),
+            ByteOrder::Little => <$ty>::from_le_bytes($buf),
+        }
+    };
+}
+
+/// 按字节序把数字转成 `[u8; N]`
+macro_rules! to_bytes {
+    ($order:expr, $value:expr) => {
+        match $order {
+            ByteOrder::Big => $value.to_be_bytes(),
+            ByteOrder::Little => $value.to_le_bytes(),
+        }
+    };
+}
+
+#[derive(Clone, Default)]
 #[pyclass]
 #[pyo3(name = "WriteStream")]
 pub struct WriteStream {
     buffer: Vec<u8>,
+    byte_order: ByteOrder,
 }
 
 impl WriteStream {
@@ -30,8 +90,27 @@ impl WriteStream {
 #[pymethods]
 impl WriteStream {
     #[new]
-    fn new() -> Self {
-        WriteStream { buffer: Vec::new() }
+    #[pyo3(signature = (byte_order = "big"))]
+    fn new(byte_order: &str) -> PyResult<Self> {
+        Ok(WriteStream {
+            buffer: Vec::new(),
+            byte_order: ByteOrder::parse(byte_order)?,
+        })
+    }
+
+    #[getter]
+    fn get_byte_order(&self) -> &'static str {
+        self.byte_order.name()
+    }
+
+    #[setter]
+    fn set_byte_order(&mut self, byte_order: &str) -> PyResult<()> {
+        self.byte_order = ByteOrder::parse(byte_order)?;
+        Ok(())
+    }
+
+    fn __len__(&self) -> usize {
+        self.buffer.len()
     }
 
     fn __repr__(&self) -> String {
@@ -67,8 +146,40 @@ impl WriteStream {
     }
 
     fn write_int(&mut self, value: u32) -> PyResult<()> {
-        let data = value.to_be_bytes();
-        self.buffer.write_all(&data)?;
+        self.write_u32(value)
+    }
+
+    fn write_i8(&mut self, value: i8) -> PyResult<()> {
+        self.buffer.write_all(&value.to_be_bytes())?;
+        Ok(())
+    }
+
+    fn write_u8(&mut self, value: u8) -> PyResult<()> {
+        self.write_byte(value)
+    }
+
+    fn write_i16(&mut self, value: i16) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
+        Ok(())
+    }
+
+    fn write_u16(&mut self, value: u16) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
+        Ok(())
+    }
+
+    fn write_i32(&mut self, value: i32) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
+        Ok(())
+    }
+
+    fn write_u32(&mut self, value: u32) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
+        Ok(())
+    }
+
+    fn write_i64(&mut self, value: i64) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
         Ok(())
     }
 
@@ -129,8 +240,7 @@ impl WriteStream {
     }
 
     fn write_float(&mut self, value: f32) -> PyResult<()> {
-        let data = value.to_be_bytes();
-        self.buffer.write_all(&data)?;
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
         Ok(())
     }
 
@@ -139,8 +249,27 @@ impl WriteStream {
         Ok(())
     }
 
-    fn get_raw_buffer<'a>(&mut self, py: Python<'a>) -> Bound<'a, PyBytes> {
-        PyBytes::new(py, &self.raw_buffer())
+    fn write_f64(&mut self, value: f64) -> PyResult<()> {
+        self.buffer.write_all(&to_bytes!(self.byte_order, value))?;
+        Ok(())
+    }
+
+    /// 依次写入 x, y, z 三个 f32
+    fn write_vector(&mut self, value: VectorPy) -> PyResult<()> {
+        let pos = value.get_entity_pos();
+        self.write_f32(pos.x)?;
+        self.write_f32(pos.y)?;
+        self.write_f32(pos.z)
+    }
+
+    /// compress 为 True 时用 LZSS 压缩，压缩之后没变小就还是原始数据
+    #[pyo3(signature = (compress = false))]
+    fn get_raw_buffer<'a>(&mut self, py: Python<'a>, compress: bool) -> Bound<'a, PyBytes> {
+        if compress {
+            PyBytes::new(py, &compress::pack(self.raw_buffer()))
+        } else {
+            PyBytes::new(py, &self.raw_buffer())
+        }
     }
 }
 
@@ -149,6 +278,7 @@ impl WriteStream {
 #[derive(Clone)]
 pub struct ReadStream {
     buffer: Cursor<Vec<u8>>,
+    byte_order: ByteOrder,
 }
 
 impl ReadStream {
@@ -167,12 +297,21 @@ impl ReadStream {
         buf.truncate(res);
         Ok(buf)
     }
+
+    fn read_array<const N: usize>(&mut self) -> PyResult<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.buffer
+            .read_exact(&mut buf)
+            .map_err(|_| PyEOFError::new_err("ReadStream read EOF"))?;
+        Ok(buf)
+    }
 }
 
 impl From<Vec<u8>> for ReadStream {
     fn from(data: Vec<u8>) -> Self {
         ReadStream {
             buffer: Cursor::new(data),
+            byte_order: ByteOrder::default(),
         }
     }
 }
@@ -180,12 +319,60 @@ impl From<Vec<u8>> for ReadStream {
 #[pymethods]
 impl ReadStream {
     #[new]
-    #[pyo3(signature = (data = None))]
-    fn new(py: Python<'_>, data: Option<Py<PyBytes>>) -> Self {
+    #[pyo3(signature = (data = None, byte_order = "big"))]
+    fn new(py: Python<'_>, data: Option<Py<PyBytes>>, byte_order: &str) -> PyResult<Self> {
         let data = data.map(|d| d.as_bytes(py).to_vec()).unwrap_or_default();
-        ReadStream {
+        Ok(ReadStream {
             buffer: Cursor::new(data),
+            byte_order: ByteOrder::parse(byte_order)?,
+        })
+    }
+
+    #[getter]
+    fn get_byte_order(&self) -> &'static str {
+        self.byte_order.name()
+    }
+
+    #[setter]
+    fn set_byte_order(&mut self, byte_order: &str) -> PyResult<()> {
+        self.byte_order = ByteOrder::parse(byte_order)?;
+        Ok(())
+    }
+
+    /// 和 io 的 seek 一样，whence: 0 开头，1 当前位置，2 末尾
+    #[pyo3(signature = (offset, whence = 0))]
+    fn seek(&mut self, offset: i64, whence: i32) -> PyResult<u64> {
+        let base = match whence {
+            0 => 0,
+            1 => self.buffer.position() as i64,
+            2 => self.buffer.get_ref().len() as i64,
+            _ => return Err(PyValueError::new_err("whence must be 0, 1 or 2")),
+        };
+        let target = base + offset;
+        if target < 0 {
+            return Err(PyValueError::new_err("Cannot seek before the start"));
         }
+        Ok(self.buffer.seek(SeekFrom::Start(target as u64))?)
+    }
+
+    fn tell(&self) -> u64 {
+        self.buffer.position()
+    }
+
+    /// 还剩多少字节没读
+    fn remaining(&self) -> usize {
+        self.buffer
+            .get_ref()
+            .len()
+            .saturating_sub(self.buffer.position() as usize)
+    }
+
+    /// 读但不移动位置
+    fn peek<'a>(&mut self, py: Python<'a>, length: usize) -> PyResult<Bound<'a, PyBytes>> {
+        let position = self.buffer.position();
+        let result = self.read(length);
+        self.buffer.set_position(position);
+        Ok(PyBytes::new(py, &result?))
     }
 
     fn __repr__(&self) -> String {
@@ -208,8 +395,35 @@ impl ReadStream {
     // read_int
     // from old api
     fn read_int(&mut self) -> PyResult<i32> {
-        let buf = self.read(4).map(|i| i[0..4].try_into().unwrap())?;
-        Ok(i32::from_be_bytes(buf))
+        self.read_i32()
+    }
+
+    fn read_i8(&mut self) -> PyResult<i8> {
+        Ok(i8::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u8(&mut self) -> PyResult<u8> {
+        Ok(u8::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_i16(&mut self) -> PyResult<i16> {
+        Ok(from_bytes!(self.byte_order, i16, self.read_array()?))
+    }
+
+    fn read_u16(&mut self) -> PyResult<u16> {
+        Ok(from_bytes!(self.byte_order, u16, self.read_array()?))
+    }
+
+    fn read_i32(&mut self) -> PyResult<i32> {
+        Ok(from_bytes!(self.byte_order, i32, self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> PyResult<u32> {
+        Ok(from_bytes!(self.byte_order, u32, self.read_array()?))
+    }
+
+    fn read_i64(&mut self) -> PyResult<i64> {
+        Ok(from_bytes!(self.byte_order, i64, self.read_array()?))
     }
     // var int avro encode
     // from old api
@@ -257,8 +471,23 @@ impl ReadStream {
     }
 
     fn read_float(&mut self) -> PyResult<f32> {
-        let buf = self.read(4).map(|i| i[0..4].try_into().unwrap())?;
-        Ok(f32::from_be_bytes(buf))
+        Ok(from_bytes!(self.byte_order, f32, self.read_array()?))
+    }
+
+    fn read_f32(&mut self) -> PyResult<f32> {
+        self.read_float()
+    }
+
+    fn read_f64(&mut self) -> PyResult<f64> {
+        Ok(from_bytes!(self.byte_order, f64, self.read_array()?))
+    }
+
+    /// 依次读取 x, y, z 三个 f32
+    fn read_vector(&mut self) -> PyResult<VectorPy> {
+        let x = self.read_f32()?;
+        let y = self.read_f32()?;
+        let z = self.read_f32()?;
+        Ok(VectorPy::from(Vectorf32::new(x, y, z)))
     }
 
     fn get_raw_buffer(&self) -> Vec<u8> {
@@ -284,8 +513,49 @@ impl Display for ReadStream {
     }
 }
 
-pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+/// 编码一次发给多个玩家，返回发送成功的人数
+///
+/// players 为 None 时是所有玩家，再按 world / team 过滤，exclude 里的玩家不发
+///
+/// compress 跟 `Player.send_data` 的一样，压缩一次再发给所有人
+#[pyfunction]
+#[pyo3(signature = (stream, players = None, world = None, team = None, exclude = Vec::new(), compress = false))]
+pub fn broadcast(
+    py: Python<'_>,
+    stream: &WriteStream,
+    players: Option<Vec<PlayerPy>>,
+    world: Option<i32>,
+    team: Option<i32>,
+    exclude: Vec<PlayerPy>,
+    compress: bool,
+) -> usize {
+    let buffer = if compress {
+        compress::pack(stream.raw_buffer())
+    } else {
+        stream.raw_buffer()
+    };
+    let players = players.unwrap_or_else(|| read_pool().get_players());
+    // 发过的也放进去，players 里有重复的只发一次
+    let mut skipped = exclude.iter().map(|p| p.get_id()).collect::<HashSet<_>>();
+    py.allow_threads(|| {
+        let func = vcmp_func();
+        players
+            .iter()
+            .map(|p| p.get_id())
+            .filter(|&id| world.is_none_or(|world| func.get_player_world(id) == world))
+            .filter(|&id| team.is_none_or(|team| func.get_player_team(id) == team))
+            .filter(|&id| skipped.insert(id))
+            .filter(|&id| func.send_client_script_data(id, &buffer).is_ok())
+            .count()
+    })
+}
+
+pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<WriteStream>()?;
     m.add_class::<ReadStream>()?;
+    m.add_class::<message::MessageSchemaPy>()?;
+    m.add_function(wrap_pyfunction!(broadcast, m)?)?;
+    chunk::module_define(py, m)?;
+    compress::module_define(py, m)?;
     Ok(())
 }
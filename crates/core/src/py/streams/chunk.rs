@@ -0,0 +1,445 @@
+//! 大数据分片传输
+//!
+//! 客户端脚本数据一次发不了太多，这里把数据切成若干片发送，另一边收齐了再拼起来。
+//! 每一片的格式（大端）：
+//!
+//! | 字段 | 类型 |
+//! | --- | --- |
+//! | magic | int，固定为 [`CHUNK_MAGIC`] |
+//! | transfer id | int |
+//! | 分片序号 | int，从 0 开始 |
+//! | 分片总数 | int |
+//! | 长度 | int |
+//! | 数据 | byte * 长度 |
+//!
+//! squirrel 那边用 `extend/chunk.nut`
+
+use std::collections::HashMap;
+use std::sync::{
+    LazyLock, Mutex,
+    atomic::{AtomicI32, Ordering},
+};
+use std::time::{Duration, Instant};
+
+use pyo3::{
+    Bound, PyAny, PyResult, Python,
+    exceptions::{PyTypeError, PyValueError},
+    pyfunction,
+    types::{
+        PyAnyMethods, PyByteArray, PyByteArrayMethods, PyBytes, PyBytesMethods, PyModule,
+        PyModuleMethods, PyString, PyStringMethods,
+    },
+    wrap_pyfunction,
+};
+use tracing::{Level, event};
+use vcmp_bindings::{encodes::encode_to_gbk, func::PlayerMethods, vcmp_func};
+
+use crate::{
+    functions::player::PlayerPy,
+    py::{
+        callbacks::PY_CALLBACK_MANAGER,
+        events::{VcmpEvent, player::ClientChunkedDataEvent},
+        streams::{WriteStream, compress, message},
+    },
+};
+
+/// "CHNK"
+pub const CHUNK_MAGIC: i32 = 0x43484E4B;
+/// 每片默认的数据长度，加上头部也不会超过客户端的限制
+pub const DEFAULT_CHUNK_SIZE: usize = 4000;
+/// 一次传输最多多少片，防止客户端乱发把内存吃光
+const MAX_CHUNK_COUNT: i32 = 4096;
+/// 多久没收到下一片就丢掉
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(30);
+/// 每个玩家同时最多几个没收齐的传输，再开新的就丢掉最久没动静的那个
+const MAX_TRANSFERS_PER_PLAYER: usize = 4;
+/// 每个玩家没收齐的数据最多占多少内存
+const MAX_PENDING_BYTES_PER_PLAYER: usize = 8 * 1024 * 1024;
+
+const HEADER_SIZE: usize = 4 * 5;
+
+static NEXT_TRANSFER_ID: AtomicI32 = AtomicI32::new(0);
+
+struct PendingTransfer {
+    parts: Vec<Option<Vec<u8>>>,
+    received: usize,
+    /// 已经收到的数据长度
+    bytes: usize,
+    last_update: Instant,
+}
+
+/// 所有玩家还没收齐的传输
+#[derive(Default)]
+struct TransferStore {
+    /// (player id, transfer id) -> 还没收齐的数据
+    transfers: HashMap<(i32, i32), PendingTransfer>,
+}
+
+static PENDING_TRANSFERS: LazyLock<Mutex<TransferStore>> =
+    LazyLock::new(|| Mutex::new(TransferStore::default()));
+
+fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    data.get(offset..offset + 4)
+        .map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+pub fn is_chunk(data: &[u8]) -> bool {
+    read_i32(data, 0) == Some(CHUNK_MAGIC)
+}
+
+impl TransferStore {
+    fn player_transfers(&self, player_id: i32) -> impl Iterator<Item = (&i32, &PendingTransfer)> {
+        self.transfers
+            .iter()
+            .filter(move |((id, _), _)| *id == player_id)
+            .map(|((_, transfer_id), transfer)| (transfer_id, transfer))
+    }
+
+    fn player_bytes(&self, player_id: i32) -> usize {
+        self.player_transfers(player_id)
+            .map(|(_, transfer)| transfer.bytes)
+            .sum()
+    }
+
+    /// 开新传输之前腾位置，丢掉最久没动静的
+    fn make_room(&mut self, player_id: i32) {
+        while self.player_transfers(player_id).count() >= MAX_TRANSFERS_PER_PLAYER {
+            let Some(oldest) = self
+                .player_transfers(player_id)
+                .min_by_key(|(_, transfer)| transfer.last_update)
+                .map(|(transfer_id, _)| *transfer_id)
+            else {
+                break;
+            };
+            event!(
+                Level::WARN,
+                "Too many chunked transfers from player {player_id}, dropped transfer {oldest}"
+            );
+            self.transfers.remove(&(player_id, oldest));
+        }
+    }
+
+    /// 处理客户端发来的一片，收齐了就返回完整的数据
+    fn receive(
+        &mut self,
+        player_id: i32,
+        data: &[u8],
+        now: Instant,
+    ) -> Result<Option<(i32, Vec<u8>)>, String> {
+        let (Some(transfer_id), Some(index), Some(count), Some(length)) = (
+            read_i32(data, 4),
+            read_i32(data, 8),
+            read_i32(data, 12),
+            read_i32(data, 16),
+        ) else {
+            return Err("Chunk header is too short".to_string());
+        };
+        if count <= 0 || count > MAX_CHUNK_COUNT || index < 0 || index >= count || length < 0 {
+            return Err(format!(
+                "Invalid chunk header: index={index}, count={count}, length={length}"
+            ));
+        }
+        let payload = data
+            .get(HEADER_SIZE..HEADER_SIZE + length as usize)
+            .ok_or("Chunk payload is shorter than its length")?;
+
+        let key = (player_id, transfer_id);
+        if !self.transfers.contains_key(&key) {
+            self.make_room(player_id);
+        }
+        let is_new_part = self.transfers.get(&key).is_none_or(|transfer| {
+            transfer
+                .parts
+                .get(index as usize)
+                .is_some_and(Option::is_none)
+        });
+        if is_new_part
+            && self.player_bytes(player_id) + payload.len() > MAX_PENDING_BYTES_PER_PLAYER
+        {
+            self.transfers.remove(&key);
+            return Err(format!(
+                "Transfer {transfer_id} exceeds the pending limit of {MAX_PENDING_BYTES_PER_PLAYER} bytes"
+            ));
+        }
+
+        let transfer = self
+            .transfers
+            .entry(key)
+            .or_insert_with(|| PendingTransfer {
+                parts: vec![None; count as usize],
+                received: 0,
+                bytes: 0,
+                last_update: now,
+            });
+        if transfer.parts.len() != count as usize {
+            self.transfers.remove(&key);
+            return Err(format!("Chunk count changed in transfer {transfer_id}"));
+        }
+        transfer.last_update = now;
+        let slot = &mut transfer.parts[index as usize];
+        if slot.is_none() {
+            *slot = Some(payload.to_vec());
+            transfer.received += 1;
+            transfer.bytes += payload.len();
+        }
+        if transfer.received < transfer.parts.len() {
+            return Ok(None);
+        }
+        let transfer = self.transfers.remove(&key).expect("transfer disappeared");
+        // 整个数据可能是压缩过的
+        compress::unpack(transfer.parts.into_iter().flatten().flatten().collect())
+            .map(|data| Some((transfer_id, data)))
+    }
+}
+
+/// 在 on_client_script_data 里调用，是分片的话返回 true，不再当普通数据处理
+pub fn handle_client_data(player_id: i32, data: &[u8]) -> bool {
+    if !is_chunk(data) {
+        return false;
+    }
+    let result = PENDING_TRANSFERS
+        .lock()
+        .expect("Failed to lock pending transfers")
+        .receive(player_id, data, Instant::now());
+    match result {
+        Ok(Some((transfer_id, payload))) => {
+            let event = ClientChunkedDataEvent::from((player_id, transfer_id, payload.clone()));
+            let _ = PY_CALLBACK_MANAGER.handle(VcmpEvent::ClientChunkedData(event), false);
+            // 拼好的数据也可以是 MessageSchema 的消息
+            message::dispatch(player_id, payload);
+        }
+        Ok(None) => {}
+        Err(e) => event!(Level::WARN, "Dropped chunk from player {player_id}: {e}"),
+    }
+    true
+}
+
+/// 在 server frame 里调用，清掉超时的传输
+pub fn tick_transfers() {
+    let mut store = PENDING_TRANSFERS
+        .lock()
+        .expect("Failed to lock pending transfers");
+    store.transfers.retain(|(player_id, transfer_id), transfer| {
+        let alive = transfer.last_update.elapsed() < CHUNK_TIMEOUT;
+        if !alive {
+            event!(
+                Level::WARN,
+                "Chunked transfer {transfer_id} from player {player_id} timed out ({}/{} chunks)",
+                transfer.received,
+                transfer.parts.len()
+            );
+        }
+        alive
+    });
+}
+
+/// 玩家断开的时候丢掉他没传完的数据
+pub fn forget_player(player_id: i32) {
+    PENDING_TRANSFERS
+        .lock()
+        .expect("Failed to lock pending transfers")
+        .transfers
+        .retain(|(id, _), _| *id != player_id);
+}
+
+/// 切成一片片带头部的数据
+fn split_chunks(transfer_id: i32, data: &[u8], chunk_size: usize) -> Vec<Vec<u8>> {
+    let count = data.len().div_ceil(chunk_size).max(1);
+    (0..count)
+        .map(|index| {
+            let part = &data
+                [(index * chunk_size).min(data.len())..((index + 1) * chunk_size).min(data.len())];
+            let mut chunk = Vec::with_capacity(HEADER_SIZE + part.len());
+            for value in [
+                CHUNK_MAGIC,
+                transfer_id,
+                index as i32,
+                count as i32,
+                part.len() as i32,
+            ] {
+                chunk.extend_from_slice(&value.to_be_bytes());
+            }
+            chunk.extend_from_slice(part);
+            chunk
+        })
+        .collect()
+}
+
+fn extract_payload(data: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(bytes) = data.downcast::<PyBytes>() {
+        return Ok(bytes.as_bytes().to_vec());
+    }
+    if let Ok(bytearray) = data.downcast::<PyByteArray>() {
+        return Ok(bytearray.to_vec());
+    }
+    if let Ok(string) = data.downcast::<PyString>() {
+        let encoded = encode_to_gbk(&string.to_cow()?);
+        let data: &[u8] = encoded.as_ref();
+        return Ok(data.to_vec());
+    }
+    if let Ok(stream) = data.extract::<WriteStream>() {
+        return Ok(stream.raw_buffer());
+    }
+    Err(PyTypeError::new_err(
+        "data must be bytes, bytearray, str or WriteStream.",
+    ))
+}
+
+/// 分片发送给玩家，返回 transfer id
+pub fn send_chunked(
+    py: Python<'_>,
+    player: &PlayerPy,
+    data: &Bound<'_, PyAny>,
+    chunk_size: Option<usize>,
+) -> PyResult<i32> {
+    let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+    if chunk_size == 0 {
+        return Err(PyValueError::new_err("chunk_size must be greater than 0"));
+    }
+    let payload = extract_payload(data)?;
+    let transfer_id = NEXT_TRANSFER_ID.fetch_add(1, Ordering::Relaxed);
+    let chunks = split_chunks(transfer_id, &payload, chunk_size);
+    if chunks.len() > MAX_CHUNK_COUNT as usize {
+        return Err(PyValueError::new_err(format!(
+            "Data is too large: {} chunks (max {MAX_CHUNK_COUNT})",
+            chunks.len()
+        )));
+    }
+    let player_id = player.get_id();
+    py.allow_threads(|| {
+        for chunk in chunks {
+            let _ = vcmp_func().send_client_script_data(player_id, &chunk);
+        }
+    });
+    Ok(transfer_id)
+}
+
+#[pyfunction]
+#[pyo3(name = "send_chunked", signature = (player, data, chunk_size = None))]
+pub fn py_send_chunked(
+    py: Python<'_>,
+    player: PlayerPy,
+    data: &Bound<'_, PyAny>,
+    chunk_size: Option<usize>,
+) -> PyResult<i32> {
+    send_chunked(py, &player, data, chunk_size)
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("CHUNK_MAGIC", CHUNK_MAGIC)?;
+    m.add("DEFAULT_CHUNK_SIZE", DEFAULT_CHUNK_SIZE)?;
+    m.add_function(wrap_pyfunction!(py_send_chunked, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn receive_all(store: &mut TransferStore, chunks: &[Vec<u8>]) -> Option<(i32, Vec<u8>)> {
+        let mut result = None;
+        for chunk in chunks {
+            if let Some(done) = store.receive(1, chunk, Instant::now()).unwrap() {
+                assert!(result.is_none(), "transfer completed twice");
+                result = Some(done);
+            }
+        }
+        result
+    }
+
+    fn sample_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn round_trip_in_order() {
+        let data = sample_data(10_000);
+        let chunks = split_chunks(7, &data, 1000);
+        assert_eq!(chunks.len(), 10);
+        let mut store = TransferStore::default();
+        assert_eq!(receive_all(&mut store, &chunks), Some((7, data)));
+        assert!(store.transfers.is_empty());
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        let chunks = split_chunks(1, &[], 1000);
+        assert_eq!(chunks.len(), 1);
+        let mut store = TransferStore::default();
+        assert_eq!(receive_all(&mut store, &chunks), Some((1, Vec::new())));
+    }
+
+    #[test]
+    fn round_trip_out_of_order() {
+        let data = sample_data(5_500);
+        let mut chunks = split_chunks(3, &data, 1000);
+        chunks.reverse();
+        chunks.swap(1, 4);
+        let mut store = TransferStore::default();
+        assert_eq!(receive_all(&mut store, &chunks), Some((3, data)));
+    }
+
+    #[test]
+    fn duplicated_chunks_are_ignored() {
+        let data = sample_data(3_000);
+        let chunks = split_chunks(5, &data, 1000);
+        let mut store = TransferStore::default();
+        let now = Instant::now();
+        assert_eq!(store.receive(1, &chunks[0], now), Ok(None));
+        assert_eq!(store.receive(1, &chunks[0], now), Ok(None));
+        assert_eq!(store.receive(1, &chunks[1], now), Ok(None));
+        assert_eq!(store.transfers[&(1, 5)].received, 2);
+        assert_eq!(store.receive(1, &chunks[2], now), Ok(Some((5, data))));
+    }
+
+    #[test]
+    fn changed_count_drops_transfer() {
+        let data = sample_data(3_000);
+        let chunks = split_chunks(9, &data, 1000);
+        let other = split_chunks(9, &data, 500);
+        let mut store = TransferStore::default();
+        let now = Instant::now();
+        assert_eq!(store.receive(1, &chunks[0], now), Ok(None));
+        assert!(store.receive(1, &other[1], now).is_err());
+        assert!(store.transfers.is_empty());
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let mut store = TransferStore::default();
+        let now = Instant::now();
+        assert!(store.receive(1, &CHUNK_MAGIC.to_be_bytes(), now).is_err());
+        let mut chunk = split_chunks(2, b"hello", 1000).remove(0);
+        chunk.truncate(chunk.len() - 1);
+        assert!(store.receive(1, &chunk, now).is_err());
+        assert!(store.transfers.is_empty());
+    }
+
+    #[test]
+    fn too_many_transfers_drop_the_oldest() {
+        let mut store = TransferStore::default();
+        let start = Instant::now();
+        for transfer_id in 0..=MAX_TRANSFERS_PER_PLAYER as i32 {
+            let chunks = split_chunks(transfer_id, &sample_data(2_000), 1000);
+            let now = start + Duration::from_millis(transfer_id as u64);
+            assert_eq!(store.receive(1, &chunks[0], now), Ok(None));
+        }
+        assert_eq!(store.player_transfers(1).count(), MAX_TRANSFERS_PER_PLAYER);
+        assert!(!store.transfers.contains_key(&(1, 0)));
+        // 别的玩家不受影响
+        let chunks = split_chunks(0, &sample_data(2_000), 1000);
+        assert_eq!(store.receive(2, &chunks[0], start), Ok(None));
+        assert_eq!(store.player_transfers(1).count(), MAX_TRANSFERS_PER_PLAYER);
+    }
+
+    #[test]
+    fn pending_bytes_are_capped() {
+        let mut store = TransferStore::default();
+        let now = Instant::now();
+        let chunk_size = MAX_PENDING_BYTES_PER_PLAYER / 2 + 1;
+        let chunks = split_chunks(4, &vec![0; chunk_size * 3], chunk_size);
+        assert_eq!(store.receive(1, &chunks[0], now), Ok(None));
+        assert!(store.receive(1, &chunks[1], now).is_err());
+        assert_eq!(store.player_bytes(1), 0);
+    }
+}
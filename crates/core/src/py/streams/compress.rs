@@ -0,0 +1,274 @@
+//! 客户端脚本数据压缩
+//!
+//! 用的是 LZSS，squirrel 那边一个字节一个字节解也不难（`extend/compress.nut`）。
+//! 压缩过的数据前面有个头部（大端）：
+//!
+//! | 字段 | 类型 |
+//! | --- | --- |
+//! | magic | int，固定为 [`COMPRESS_MAGIC`] |
+//! | 算法 | byte，目前只有 [`ALGORITHM_LZSS`] |
+//! | 原始长度 | int |
+//!
+//! 没有这个头部的就是旧的未压缩数据，原样处理。
+//!
+//! LZSS 数据：每 8 个 token 前面一个标记字节，低位在前，
+//! 0 是一个原样字节，1 是两个字节的回溯引用：
+//! `距离 - 1` 占高 12 位，`长度 - 3` 占低 4 位。
+
+use std::collections::HashMap;
+
+use pyo3::{
+    Bound, PyResult, Python,
+    exceptions::PyValueError,
+    pyfunction,
+    types::{PyBytes, PyModule, PyModuleMethods},
+    wrap_pyfunction,
+};
+
+/// "CZL1"
+pub const COMPRESS_MAGIC: i32 = 0x435A4C31;
+pub const ALGORITHM_LZSS: u8 = 1;
+
+const HEADER_SIZE: usize = 4 + 1 + 4;
+const WINDOW_SIZE: usize = 4096;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 18;
+/// 每个位置最多往回找几个候选，再多就太慢了
+const MAX_CANDIDATES: usize = 32;
+/// 头部里的原始长度最大多少，长度是客户端给的，不能直接拿来分配内存
+const MAX_UNCOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+fn lzss_compress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() / 2 + 16);
+    let mut positions: HashMap<[u8; 3], Vec<usize>> = HashMap::new();
+    let mut flag_index = 0;
+    let mut bit = 8;
+    let mut i = 0;
+    while i < data.len() {
+        if bit == 8 {
+            flag_index = out.len();
+            out.push(0);
+            bit = 0;
+        }
+
+        let mut best = (0, 0);
+        if i + MIN_MATCH <= data.len() {
+            let key = [data[i], data[i + 1], data[i + 2]];
+            if let Some(candidates) = positions.get(&key) {
+                for &start in candidates.iter().rev().take(MAX_CANDIDATES) {
+                    let distance = i - start;
+                    if distance > WINDOW_SIZE {
+                        break;
+                    }
+                    let max = MAX_MATCH.min(data.len() - i);
+                    let length = (0..max)
+                        .take_while(|&k| data[start + k] == data[i + k])
+                        .count();
+                    if length > best.1 {
+                        best = (distance, length);
+                        if length == MAX_MATCH {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let step = if best.1 >= MIN_MATCH {
+            let (distance, length) = best;
+            out[flag_index] |= 1 << bit;
+            let value = (((distance - 1) as u16) << 4) | (length - MIN_MATCH) as u16;
+            out.extend_from_slice(&value.to_be_bytes());
+            length
+        } else {
+            out.push(data[i]);
+            1
+        };
+        for k in i..i + step {
+            if k + MIN_MATCH <= data.len() {
+                positions
+                    .entry([data[k], data[k + 1], data[k + 2]])
+                    .or_default()
+                    .push(k);
+            }
+        }
+        i += step;
+        bit += 1;
+    }
+    out
+}
+
+fn lzss_decompress(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    // 一个标记字节加 8 个引用共 17 个字节，最多解出 8 * MAX_MATCH = 144 个字节，不到 9 倍
+    let max_size = data.len().saturating_mul(9).saturating_add(8 * MAX_MATCH);
+    if size > MAX_UNCOMPRESSED_SIZE || size > max_size {
+        return Err(format!(
+            "Uncompressed size {size} is too large for {} bytes of data",
+            data.len()
+        ));
+    }
+    // 不按 size 预先分配，边解边长
+    let mut out = Vec::new();
+    let mut input = data.iter().copied();
+    let truncated = || "Compressed data is truncated".to_string();
+    while out.len() < size {
+        let flags = input.next().ok_or_else(truncated)?;
+        for bit in 0..8 {
+            if out.len() >= size {
+                break;
+            }
+            if flags & (1 << bit) == 0 {
+                out.push(input.next().ok_or_else(truncated)?);
+                continue;
+            }
+            let value = u16::from_be_bytes([
+                input.next().ok_or_else(truncated)?,
+                input.next().ok_or_else(truncated)?,
+            ]);
+            let distance = (value >> 4) as usize + 1;
+            let length = (value & 0xF) as usize + MIN_MATCH;
+            if distance > out.len() {
+                return Err(format!("Invalid back reference: distance {distance}"));
+            }
+            for _ in 0..length {
+                out.push(out[out.len() - distance]);
+            }
+        }
+    }
+    out.truncate(size);
+    Ok(out)
+}
+
+/// 压缩并加上头部，压缩之后没变小就原样返回
+pub fn pack(data: Vec<u8>) -> Vec<u8> {
+    let compressed = lzss_compress(&data);
+    if compressed.len() + HEADER_SIZE >= data.len() {
+        return data;
+    }
+    let mut out = Vec::with_capacity(HEADER_SIZE + compressed.len());
+    out.extend_from_slice(&COMPRESS_MAGIC.to_be_bytes());
+    out.push(ALGORITHM_LZSS);
+    out.extend_from_slice(&(data.len() as i32).to_be_bytes());
+    out.extend_from_slice(&compressed);
+    out
+}
+
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.len() >= HEADER_SIZE && data[0..4] == COMPRESS_MAGIC.to_be_bytes()
+}
+
+/// 有头部就解压，没有就原样返回
+pub fn unpack(data: Vec<u8>) -> Result<Vec<u8>, String> {
+    if !is_compressed(&data) {
+        return Ok(data);
+    }
+    let algorithm = data[4];
+    let size = i32::from_be_bytes([data[5], data[6], data[7], data[8]]);
+    if size < 0 {
+        return Err(format!("Invalid uncompressed size: {size}"));
+    }
+    match algorithm {
+        ALGORITHM_LZSS => lzss_decompress(&data[HEADER_SIZE..], size as usize),
+        _ => Err(format!("Unknown compression algorithm: {algorithm}")),
+    }
+}
+
+#[pyfunction]
+pub fn compress<'py>(py: Python<'py>, data: Vec<u8>) -> Bound<'py, PyBytes> {
+    PyBytes::new(py, &pack(data))
+}
+
+/// 不是压缩过的数据会原样返回
+#[pyfunction]
+pub fn decompress<'py>(py: Python<'py>, data: Vec<u8>) -> PyResult<Bound<'py, PyBytes>> {
+    let data = unpack(data).map_err(PyValueError::new_err)?;
+    Ok(PyBytes::new(py, &data))
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("COMPRESS_MAGIC", COMPRESS_MAGIC)?;
+    m.add_function(wrap_pyfunction!(compress, m)?)?;
+    m.add_function(wrap_pyfunction!(decompress, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(size: i32) -> Vec<u8> {
+        let mut out = COMPRESS_MAGIC.to_be_bytes().to_vec();
+        out.push(ALGORITHM_LZSS);
+        out.extend_from_slice(&size.to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        assert_eq!(pack(Vec::new()), Vec::<u8>::new());
+        assert_eq!(unpack(Vec::new()), Ok(Vec::new()));
+        assert_eq!(lzss_decompress(&lzss_compress(&[]), 0), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn round_trip_compressible() {
+        let data = b"hello hello hello hello hello world ".repeat(200);
+        let packed = pack(data.clone());
+        assert!(is_compressed(&packed));
+        assert!(packed.len() < data.len());
+        assert_eq!(unpack(packed), Ok(data));
+    }
+
+    #[test]
+    fn round_trip_all_same_byte() {
+        // 压缩率最高的情况，不能被大小检查误伤
+        let data = vec![0u8; 100_000];
+        let packed = pack(data.clone());
+        assert!(is_compressed(&packed));
+        assert_eq!(unpack(packed), Ok(data));
+    }
+
+    #[test]
+    fn incompressible_data_is_left_as_is() {
+        let mut state = 0x12345678u32;
+        let data = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect::<Vec<_>>();
+        let packed = pack(data.clone());
+        assert_eq!(packed, data);
+        assert_eq!(unpack(packed), Ok(data));
+    }
+
+    #[test]
+    fn truncated_data_is_rejected() {
+        let data = b"abcabcabcabcabcabcabcabcabcabc".repeat(20);
+        let packed = pack(data);
+        assert!(is_compressed(&packed));
+        for len in [HEADER_SIZE, HEADER_SIZE + 1, packed.len() - 1] {
+            assert!(unpack(packed[..len].to_vec()).is_err(), "len {len}");
+        }
+    }
+
+    #[test]
+    fn bad_back_reference_is_rejected() {
+        let mut data = header(10);
+        // 第一个 token 就是回溯引用，前面什么都没有
+        data.extend_from_slice(&[0b0000_0001, 0x00, 0x10]);
+        assert!(unpack(data).unwrap_err().contains("back reference"));
+    }
+
+    #[test]
+    fn oversized_header_is_rejected_before_allocating() {
+        let mut data = header(i32::MAX);
+        data.extend_from_slice(&[0, b'a']);
+        assert!(unpack(data).unwrap_err().contains("too large"));
+        let mut data = header(-1);
+        data.push(0);
+        assert!(unpack(data).is_err());
+    }
+}
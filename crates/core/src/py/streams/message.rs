@@ -0,0 +1,441 @@
+//! 基于 WriteStream / ReadStream 的消息定义
+//!
+//! 每条消息先写一个 int 作为消息 id，然后按字段顺序写入，
+//! squirrel 那边按同样的顺序读就行：
+//!
+//! | 类型 | squirrel |
+//! | --- | --- |
+//! | int | ReadInt / WriteInt |
+//! | byte | ReadByte / WriteByte |
+//! | float | ReadFloat / WriteFloat |
+//! | bool | PyReadBoolean / PyWriteBoolean |
+//! | sq_string | ReadString / WriteString |
+//! | long | PyReadLong / PyWriteLong |
+//! | string | PyReadString / PyWriteString |
+//! | vector | 三个 float |
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use pyo3::{
+    Bound, IntoPyObjectExt, Py, PyAny, PyResult, Python,
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyCFunction, PyDict, PyDictMethods, PyTupleMethods},
+};
+use tracing::{Level, event};
+
+use crate::{
+    functions::player::PlayerPy,
+    pool::fetch_player,
+    py::{
+        get_traceback,
+        scope::{current_scope, in_target},
+        streams::{ReadStream, WriteStream},
+        types::VectorPy,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    Int,
+    Byte,
+    Float,
+    Bool,
+    SqString,
+    Long,
+    String,
+    Vector,
+}
+
+impl FieldKind {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "int" => Self::Int,
+            "byte" => Self::Byte,
+            "float" => Self::Float,
+            "bool" => Self::Bool,
+            "sq_string" => Self::SqString,
+            "long" => Self::Long,
+            "string" => Self::String,
+            "vector" => Self::Vector,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Int => "int",
+            Self::Byte => "byte",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::SqString => "sq_string",
+            Self::Long => "long",
+            Self::String => "string",
+            Self::Vector => "vector",
+        }
+    }
+
+    fn write(&self, stream: &mut WriteStream, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        match self {
+            Self::Int => stream.write_int(value.extract::<i32>()? as u32),
+            Self::Byte => stream.write_byte(value.extract::<u8>()?),
+            Self::Float => stream.write_float(value.extract::<f32>()?),
+            Self::Bool => stream.write_bool(value.extract::<bool>()?),
+            Self::SqString => {
+                if !stream.write_sq_string(&value.extract::<String>()?)? {
+                    return Err(PyValueError::new_err("sq_string is longer than 4095 bytes"));
+                }
+                Ok(())
+            }
+            Self::Long => stream.write_long(value.extract::<i64>()?),
+            Self::String => stream.write_string(&value.extract::<String>()?),
+            Self::Vector => stream.write_vector(value.extract::<VectorPy>()?),
+        }
+    }
+
+    fn read(&self, py: Python<'_>, stream: &mut ReadStream) -> PyResult<Py<PyAny>> {
+        match self {
+            Self::Int => stream.read_int()?.into_py_any(py),
+            Self::Byte => (stream.read(1)?[0]).into_py_any(py),
+            Self::Float => stream.read_float()?.into_py_any(py),
+            Self::Bool => stream.read_bool()?.into_py_any(py),
+            Self::SqString => stream.read_sq_string()?.into_py_any(py),
+            Self::Long => stream.read_long()?.into_py_any(py),
+            Self::String => stream.read_string()?.into_py_any(py),
+            Self::Vector => stream.read_vector()?.into_py_any(py),
+        }
+    }
+}
+
+#[pyclass]
+#[pyo3(name = "MessageSchema")]
+#[derive(Debug, Clone)]
+pub struct MessageSchemaPy {
+    id: i32,
+    fields: Vec<(String, FieldKind)>,
+}
+
+impl MessageSchemaPy {
+    fn same_as(&self, other: &MessageSchemaPy) -> bool {
+        self.id == other.id && self.fields == other.fields
+    }
+
+    /// 读掉 id 之后剩下的部分
+    fn decode_body<'py>(
+        &self,
+        py: Python<'py>,
+        stream: &mut ReadStream,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for (name, kind) in &self.fields {
+            dict.set_item(name, kind.read(py, stream)?)?;
+        }
+        Ok(dict)
+    }
+}
+
+#[pymethods]
+impl MessageSchemaPy {
+    /// fields: [(名字, 类型), ...]
+    #[new]
+    fn new(id: i32, fields: Vec<(String, String)>) -> PyResult<Self> {
+        let mut parsed: Vec<(String, FieldKind)> = Vec::with_capacity(fields.len());
+        for (name, kind) in fields {
+            if parsed.iter().any(|(n, _)| *n == name) {
+                return Err(PyValueError::new_err(format!("Duplicate field: {name}")));
+            }
+            let kind = FieldKind::from_name(&kind)
+                .ok_or_else(|| PyValueError::new_err(format!("Unknown field type: {kind}")))?;
+            parsed.push((name, kind));
+        }
+        Ok(Self { id, fields: parsed })
+    }
+
+    #[getter]
+    fn get_id(&self) -> i32 {
+        self.id
+    }
+
+    #[getter]
+    fn get_fields(&self) -> Vec<(String, &'static str)> {
+        self.fields
+            .iter()
+            .map(|(name, kind)| (name.clone(), kind.name()))
+            .collect()
+    }
+
+    /// 字段可以用 dict 传，也可以用关键字参数传，少一个多一个都会报错
+    #[pyo3(signature = (values = None, **kwargs))]
+    fn encode(
+        &self,
+        values: Option<&Bound<'_, PyDict>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<WriteStream> {
+        let mut given = 0;
+        let mut stream = WriteStream::default();
+        stream.write_int(self.id as u32)?;
+        for (name, kind) in &self.fields {
+            let value = match values.map(|v| v.get_item(name)).transpose()?.flatten() {
+                Some(value) => Some(value),
+                None => kwargs.map(|k| k.get_item(name)).transpose()?.flatten(),
+            };
+            let value =
+                value.ok_or_else(|| PyValueError::new_err(format!("Missing field: {name}")))?;
+            kind.write(&mut stream, &value)
+                .map_err(|e| PyValueError::new_err(format!("Invalid field {name}: {e}")))?;
+            given += 1;
+        }
+        let total = values.map(|v| v.len()).unwrap_or(0) + kwargs.map(|k| k.len()).unwrap_or(0);
+        if total != given {
+            return Err(PyValueError::new_err(format!(
+                "Unknown or duplicated fields for message {}",
+                self.id
+            )));
+        }
+        Ok(stream)
+    }
+
+    /// 从 stream 的当前位置开始读，id 不对会报错
+    fn decode<'py>(
+        &self,
+        py: Python<'py>,
+        stream: &mut ReadStream,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let id = stream.read_int()?;
+        if id != self.id {
+            return Err(PyValueError::new_err(format!(
+                "Message id mismatch: expected {}, got {id}",
+                self.id
+            )));
+        }
+        self.decode_body(py, stream)
+    }
+
+    #[pyo3(signature = (player, values = None, **kwargs))]
+    fn send(
+        &self,
+        py: Python<'_>,
+        player: &PlayerPy,
+        values: Option<&Bound<'_, PyDict>>,
+        kwargs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let stream = self.encode(values, kwargs)?;
+        player.send_data(py, stream, false);
+        Ok(())
+    }
+
+    /// 注册客户端发来这条消息时的处理函数，func(player, data: dict)
+    ///
+    /// 不传 func 就当装饰器用
+    #[pyo3(signature = (func = None))]
+    fn handler(&self, py: Python<'_>, func: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        if let Some(func) = func {
+            register_handler(self, func.clone_ref(py))?;
+            return Ok(func);
+        }
+        let schema = self.clone();
+        let decorator = PyCFunction::new_closure(
+            py,
+            None,
+            None,
+            move |args, _kwargs| -> PyResult<Py<PyAny>> {
+                let func = args.get_item(0)?.unbind();
+                register_handler(&schema, func.clone_ref(args.py()))?;
+                Ok(func)
+            },
+        )?;
+        Ok(decorator.into_any().unbind())
+    }
+
+    fn __repr__(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, kind)| format!("{name}: {}", kind.name()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("MessageSchema(id={}, fields=[{fields}])", self.id)
+    }
+}
+
+/// 处理函数跟注册它的入口一起存，单独重载一个入口的时候只换这个入口的
+pub(crate) struct MessageRoute {
+    schema: MessageSchemaPy,
+    handlers: Vec<(Option<usize>, Py<PyAny>)>,
+}
+
+static MESSAGE_ROUTES: LazyLock<Mutex<HashMap<i32, MessageRoute>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn register_handler(schema: &MessageSchemaPy, func: Py<PyAny>) -> PyResult<()> {
+    let mut routes = MESSAGE_ROUTES
+        .lock()
+        .expect("Failed to lock message routes");
+    let route = routes.entry(schema.id).or_insert_with(|| MessageRoute {
+        schema: schema.clone(),
+        handlers: Vec::new(),
+    });
+    if !route.schema.same_as(schema) {
+        return Err(PyValueError::new_err(format!(
+            "Message id {} is already used by {}",
+            schema.id,
+            route.schema.__repr__()
+        )));
+    }
+    route.handlers.push((current_scope(), func));
+    Ok(())
+}
+
+/// 加载新脚本之前先把 target 入口的处理函数收起来，None 是全部，失败了再换回去
+pub(crate) fn take_routes(target: Option<usize>) -> HashMap<i32, MessageRoute> {
+    let mut routes = MESSAGE_ROUTES
+        .lock()
+        .expect("Failed to lock message routes");
+    let mut taken = HashMap::new();
+    for (id, route) in routes.iter_mut() {
+        let (matched, rest) = std::mem::take(&mut route.handlers)
+            .into_iter()
+            .partition(|(scope, _)| in_target(*scope, target));
+        route.handlers = rest;
+        taken.insert(
+            *id,
+            MessageRoute {
+                schema: route.schema.clone(),
+                handlers: matched,
+            },
+        );
+    }
+    routes.retain(|_, route| !route.handlers.is_empty());
+    taken.retain(|_, route| !route.handlers.is_empty());
+    taken
+}
+
+/// 丢掉 target 入口新注册的处理函数，把收起来的放回去
+pub(crate) fn restore_routes(taken: HashMap<i32, MessageRoute>, target: Option<usize>) {
+    let mut routes = MESSAGE_ROUTES
+        .lock()
+        .expect("Failed to lock message routes");
+    for route in routes.values_mut() {
+        route
+            .handlers
+            .retain(|(scope, _)| !in_target(*scope, target));
+    }
+    routes.retain(|_, route| !route.handlers.is_empty());
+    for (id, route) in taken {
+        match routes.get_mut(&id) {
+            Some(current) => current.handlers.extend(route.handlers),
+            None => {
+                routes.insert(id, route);
+            }
+        }
+    }
+}
+
+/// 把客户端数据分发给对应消息 id 的处理函数，没有注册的 id 直接忽略
+///
+/// 处理函数要在拿着 GIL 的时候 clone_ref，回调进来的时候 GIL 是释放的
+pub fn dispatch(player_id: i32, data: Vec<u8>) {
+    if data.len() < 4 {
+        return;
+    }
+    let id = i32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    Python::with_gil(|py| {
+        let (schema, handlers) = {
+            let routes = MESSAGE_ROUTES
+                .lock()
+                .expect("Failed to lock message routes");
+            let Some(route) = routes.get(&id) else {
+                return;
+            };
+            let handlers = route
+                .handlers
+                .iter()
+                .map(|(_, handler)| handler.clone_ref(py))
+                .collect::<Vec<_>>();
+            (route.schema.clone(), handlers)
+        };
+        let mut stream = ReadStream::from(data);
+        let message = match schema.decode(py, &mut stream) {
+            Ok(message) => message,
+            Err(e) => {
+                event!(
+                    Level::WARN,
+                    "Failed to decode message {id} from player {player_id}: {}",
+                    get_traceback(&e, Some(py))
+                );
+                return;
+            }
+        };
+        let player = fetch_player(player_id);
+        for handler in handlers {
+            if let Err(e) = handler.call1(py, (player, message.clone())) {
+                event!(
+                    Level::ERROR,
+                    "Failed to handle message {id}: {}",
+                    get_traceback(&e, Some(py))
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::types::{PyList, PyListMethods};
+
+    use super::*;
+
+    #[test]
+    fn dispatch_calls_registered_handler() {
+        pyo3::prepare_freethreaded_python();
+        let (data, received) = Python::with_gil(|py| {
+            let schema = MessageSchemaPy::new(
+                7301,
+                vec![
+                    ("score".to_string(), "int".to_string()),
+                    ("name".to_string(), "string".to_string()),
+                ],
+            )
+            .unwrap();
+            let received = PyList::empty(py);
+            let handler = PyCFunction::new_closure(py, None, None, {
+                let received = received.clone().unbind();
+                move |args, _kwargs| -> PyResult<()> {
+                    received.bind(args.py()).append(args.get_item(1)?)
+                }
+            })
+            .unwrap();
+            register_handler(&schema, handler.into_any().unbind()).unwrap();
+
+            let values = PyDict::new(py);
+            values.set_item("score", 42).unwrap();
+            values.set_item("name", "vice").unwrap();
+            let stream = schema.encode(Some(&values), None).unwrap();
+            (stream.raw_buffer(), received.unbind())
+        });
+
+        // 跟 extern "C" 回调一样，不拿着 GIL 进来
+        dispatch(0, data);
+        dispatch(0, vec![0, 0, 0x1c]);
+
+        Python::with_gil(|py| {
+            let received = received.bind(py);
+            assert_eq!(received.len(), 1);
+            let message = received.get_item(0).unwrap();
+            assert_eq!(
+                message.get_item("score").unwrap().extract::<i32>().unwrap(),
+                42
+            );
+            assert_eq!(
+                message
+                    .get_item("name")
+                    .unwrap()
+                    .extract::<String>()
+                    .unwrap(),
+                "vice"
+            );
+        });
+        take_routes(None);
+    }
+}
@@ -4,8 +4,10 @@ use std::{
 };
 
 use pyo3::{
-    Bound, PyResult, Python, pyclass, pymethods,
-    types::{PyModule, PyModuleMethods},
+    Bound, FromPyObject, PyResult, Python,
+    exceptions::{PyIndexError, PyValueError},
+    pyclass, pymethods,
+    types::{PyAnyMethods, PyIterator, PyModule, PyModuleMethods, PyTuple, PyType},
 };
 
 use vcmp_bindings::{
@@ -24,6 +26,12 @@ use vcmp_bindings::{
 
 use crate::{consts::EntityId, py::events::VcmpEventType};
 
+pub mod enums;
+pub use enums::{
+    BodyPartPy, ExplosionTypePy, PlayerActionPy, PlayerStatePy, SkinPy, VehicleModelPy, WeaponPy,
+    WeatherPy,
+};
+
 #[derive(Clone, Debug, Copy)]
 #[pyclass]
 #[pyo3(name = "ServerSettings")]
@@ -198,6 +206,75 @@ impl WastedSettingsPy {
     }
 }
 
+/// 常用的颜色，名字都是小写，markup 里也用这些名字
+///
+/// `RGB.WHITE` 这些类属性也是用这张表生成的，见 [`module_define`]
+pub const NAMED_COLORS: &[(&str, u32)] = &[
+    ("white", 0xFFFFFF),
+    ("black", 0x000000),
+    ("red", 0xFF0000),
+    ("green", 0x008000),
+    ("lime", 0x00FF00),
+    ("blue", 0x0000FF),
+    ("yellow", 0xFFFF00),
+    ("orange", 0xFFA500),
+    ("gold", 0xFFD700),
+    ("purple", 0x800080),
+    ("pink", 0xFFC0CB),
+    ("cyan", 0x00FFFF),
+    ("magenta", 0xFF00FF),
+    ("brown", 0xA52A2A),
+    ("grey", 0x808080),
+    ("gray", 0x808080),
+];
+
+/// 支持 `#RRGGBB`、`#RRGGBBAA`、`[#RRGGBB]`、`0xRRGGBB` 和颜色名字
+pub fn parse_color(text: &str) -> Option<Color> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or(text);
+    let hex = text
+        .strip_prefix('#')
+        .or_else(|| text.strip_prefix("0x"))
+        .or_else(|| text.strip_prefix("0X"));
+    if let Some(hex) = hex {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        return match hex.len() {
+            6 => Some(Color::from_rgb(value, None)),
+            8 => Some(Color::from_rgba(value)),
+            _ => None,
+        };
+    }
+    let name = text.to_lowercase();
+    NAMED_COLORS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, value)| Color::from_rgb(*value, None))
+}
+
+/// 客户端消息里的内联颜色代码
+pub fn color_code(color: Color) -> String {
+    format!("[#{:06X}]", color.as_rgb())
+}
+
+/// h 是角度，其他都是 0 到 1
+fn hue_to_rgb(h: f32, c: f32, m: f32) -> (u8, u8, u8) {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let to_u8 = |v: f32| ((v + m).clamp(0.0, 1.0) * 255.0).round() as u8;
+    (to_u8(r), to_u8(g), to_u8(b))
+}
+
 #[derive(Clone, Debug, Copy)]
 #[pyclass]
 #[pyo3(name = "RGB")]
@@ -217,6 +294,28 @@ impl From<RGBPy> for Color {
     }
 }
 
+impl RGBPy {
+    /// 返回 (hue, max, min, delta)，颜色分量都是 0 到 1
+    fn hue(&self) -> (f32, f32, f32, f32) {
+        let r = self.inner.r as f32 / 255.0;
+        let g = self.inner.g as f32 / 255.0;
+        let b = self.inner.b as f32 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (h, max, min, delta)
+    }
+}
+
 #[pymethods]
 impl RGBPy {
     #[getter]
@@ -311,6 +410,81 @@ impl RGBPy {
         }
     }
 
+    /// h 是角度，s 和 v 是 0 到 1
+    #[staticmethod]
+    #[pyo3(signature = (h, s, v, a = 255))]
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: u8) -> Self {
+        let c = v * s;
+        let (r, g, b) = hue_to_rgb(h, c, v - c);
+        Self::new(r, g, b, a)
+    }
+
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        let (h, max, _, delta) = self.hue();
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        (h, s, max)
+    }
+
+    /// h 是角度，s 和 l 是 0 到 1
+    #[staticmethod]
+    #[pyo3(signature = (h, s, l, a = 255))]
+    pub fn from_hsl(h: f32, s: f32, l: f32, a: u8) -> Self {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let (r, g, b) = hue_to_rgb(h, c, l - c / 2.0);
+        Self::new(r, g, b, a)
+    }
+
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (h, max, min, delta) = self.hue();
+        let l = (max + min) / 2.0;
+        let s = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        (h, s, l)
+    }
+
+    /// t 为 0 是自己，为 1 是 other
+    #[pyo3(signature = (other, t = 0.5))]
+    pub fn blend(&self, other: RGBPy, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Self::new(
+            mix(self.inner.r, other.inner.r),
+            mix(self.inner.g, other.inner.g),
+            mix(self.inner.b, other.inner.b),
+            mix(self.inner.a, other.inner.a),
+        )
+    }
+
+    /// 解析 `#RRGGBB`、`#RRGGBBAA`、`[#RRGGBB]` 或者颜色名字
+    #[staticmethod]
+    pub fn parse(text: &str) -> PyResult<Self> {
+        parse_color(text)
+            .map(Self::from)
+            .ok_or_else(|| PyValueError::new_err(format!("Invalid color: {text}")))
+    }
+
+    #[staticmethod]
+    pub fn named_colors() -> Vec<&'static str> {
+        NAMED_COLORS.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// 消息里用的内联颜色代码，`[#RRGGBB]`
+    pub fn as_code(&self) -> String {
+        color_code(self.inner)
+    }
+
+    fn __eq__(&self, other: &RGBPy) -> bool {
+        self.as_rgba() == other.as_rgba()
+    }
+
+    /// 跟 __eq__ 一样按 (r, g, b, a) 算
+    fn __hash__(&self) -> u64 {
+        self.as_rgba() as u64
+    }
+
     fn __repr__(&self) -> String {
         let (r, b, g, a) = (self.inner.r, self.inner.b, self.inner.g, self.inner.a);
         format!("RGB(r={r}, b={b}, g={g}, a={a})")
@@ -339,7 +513,7 @@ pub enum EntityVectorType {
 
 #[derive(Clone, Copy)]
 #[pyclass]
-#[pyo3(name = "Vector")]
+#[pyo3(name = "Vector", module = "vcmp.types")]
 pub struct VectorPy {
     pub entity_type: EntityVectorType,
     pub entity_id: EntityId,
@@ -373,6 +547,30 @@ impl From<Vectorf32> for VectorPy {
 }
 
 impl VectorPy {
+    /// 对每个分量做同样的运算，返回独立的值
+    pub fn map(&self, f: impl Fn(f32) -> f32) -> VectorPy {
+        let pos = self.get_entity_pos();
+        VectorPy::from(Vectorf32::new(f(pos.x), f(pos.y), f(pos.z)))
+    }
+
+    /// 两个向量逐分量运算，返回独立的值
+    pub fn zip(&self, other: &VectorPy, f: impl Fn(f32, f32) -> f32) -> VectorPy {
+        let a = self.get_entity_pos();
+        let b = other.get_entity_pos();
+        VectorPy::from(Vectorf32::new(f(a.x, b.x), f(a.y, b.y), f(a.z, b.z)))
+    }
+
+    /// 把 offset 加到实体上，add_position 之类的用
+    pub fn add_to_entity(&mut self, offset: VectorPy) {
+        let sum = *self + offset;
+        self.set_entity_pos(Some(sum.get_x()), Some(sum.get_y()), Some(sum.get_z()));
+    }
+
+    fn length_squared_value(&self) -> f32 {
+        let pos = self.get_entity_pos();
+        pos.x * pos.x + pos.y * pos.y + pos.z * pos.z
+    }
+
     pub fn get_entity_pos(&self) -> Vectorf32 {
         match self.entity_type {
             EntityVectorType::PlayerPosition => {
@@ -490,67 +688,54 @@ impl VectorPy {
     }
 }
 
+/// 向量运算的另一边，可以是向量也可以是数字
+#[derive(Clone, Copy, FromPyObject)]
+pub enum VectorOperand {
+    Vector(VectorPy),
+    Scalar(f32),
+}
+
+impl From<VectorOperand> for Vectorf32 {
+    fn from(val: VectorOperand) -> Self {
+        match val {
+            VectorOperand::Vector(vector) => vector.get_entity_pos(),
+            VectorOperand::Scalar(value) => Vectorf32::new(value, value, value),
+        }
+    }
+}
+
+impl From<VectorOperand> for VectorPy {
+    fn from(val: VectorOperand) -> Self {
+        VectorPy::from(Vectorf32::from(val))
+    }
+}
+
+/// 运算结果都是独立的值，不会写回实体，要写回用 [`VectorPy::add_to_entity`] 或者 setter
 impl Add for VectorPy {
     type Output = Self;
-    fn add(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_pos();
-        let other = rhs.get_entity_pos();
-
-        self.set_entity_pos(
-            Some(origin.x + other.x),
-            Some(origin.y + other.y),
-            Some(origin.z + other.z),
-        );
-
-        self
+    fn add(self, rhs: Self) -> Self {
+        self.zip(&rhs, |a, b| a + b)
     }
 }
 
 impl Sub for VectorPy {
     type Output = Self;
-    fn sub(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_pos();
-        let other = rhs.get_entity_pos();
-
-        self.set_entity_pos(
-            Some(origin.x - other.x),
-            Some(origin.y - other.y),
-            Some(origin.z - other.z),
-        );
-
-        self
+    fn sub(self, rhs: Self) -> Self {
+        self.zip(&rhs, |a, b| a - b)
     }
 }
 
 impl Div for VectorPy {
     type Output = Self;
-    fn div(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_pos();
-        let other = rhs.get_entity_pos();
-
-        self.set_entity_pos(
-            Some(origin.x / other.x),
-            Some(origin.y / other.y),
-            Some(origin.z / other.z),
-        );
-
-        self
+    fn div(self, rhs: Self) -> Self {
+        self.zip(&rhs, |a, b| a / b)
     }
 }
 
 impl Mul for VectorPy {
     type Output = Self;
-    fn mul(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_pos();
-        let other = rhs.get_entity_pos();
-
-        self.set_entity_pos(
-            Some(origin.x * other.x),
-            Some(origin.y * other.y),
-            Some(origin.z * other.z),
-        );
-
-        self
+    fn mul(self, rhs: Self) -> Self {
+        self.zip(&rhs, |a, b| a * b)
     }
 }
 
@@ -642,20 +827,176 @@ impl VectorPy {
     }
 
     // support - + / * for VectorPy
-    fn __add__(&self, py: Python<'_>, other: &VectorPy) -> VectorPy {
-        py.allow_threads(|| *self + *other)
+    // 另一边可以是数字，比如 `pos * 2`，结果都是独立的值
+    fn __add__(&self, py: Python<'_>, other: VectorOperand) -> VectorPy {
+        py.allow_threads(|| *self + VectorPy::from(other))
     }
 
-    fn __sub__(&self, py: Python<'_>, other: &VectorPy) -> VectorPy {
-        py.allow_threads(|| *self - *other)
+    fn __sub__(&self, py: Python<'_>, other: VectorOperand) -> VectorPy {
+        py.allow_threads(|| *self - VectorPy::from(other))
     }
 
-    fn __mul__(&self, py: Python<'_>, other: &VectorPy) -> VectorPy {
-        py.allow_threads(|| *self * *other)
+    fn __mul__(&self, py: Python<'_>, other: VectorOperand) -> VectorPy {
+        py.allow_threads(|| *self * VectorPy::from(other))
     }
 
-    fn __truediv__(&self, py: Python<'_>, other: &VectorPy) -> VectorPy {
-        py.allow_threads(|| *self / *other)
+    fn __truediv__(&self, py: Python<'_>, other: VectorOperand) -> VectorPy {
+        py.allow_threads(|| *self / VectorPy::from(other))
+    }
+
+    // 数字在左边的情况
+    fn __radd__(&self, py: Python<'_>, other: f32) -> VectorPy {
+        py.allow_threads(|| self.map(|v| other + v))
+    }
+
+    fn __rsub__(&self, py: Python<'_>, other: f32) -> VectorPy {
+        py.allow_threads(|| self.map(|v| other - v))
+    }
+
+    fn __rmul__(&self, py: Python<'_>, other: f32) -> VectorPy {
+        py.allow_threads(|| self.map(|v| other * v))
+    }
+
+    fn __rtruediv__(&self, py: Python<'_>, other: f32) -> VectorPy {
+        py.allow_threads(|| self.map(|v| other / v))
+    }
+
+    fn __neg__(&self, py: Python<'_>) -> VectorPy {
+        py.allow_threads(|| self.map(|v| -v))
+    }
+
+    fn __len__(&self) -> usize {
+        3
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<f32> {
+        let pos = self.get_entity_pos();
+        match index {
+            0 | -3 => Ok(pos.x),
+            1 | -2 => Ok(pos.y),
+            2 | -1 => Ok(pos.z),
+            _ => Err(PyIndexError::new_err("Vector index out of range")),
+        }
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let pos = self.get_entity_pos();
+        PyTuple::new(py, [pos.x, pos.y, pos.z])?.try_iter()
+    }
+
+    /// pickle 的时候只保存值，实体向量会变成独立的值
+    fn __reduce__<'py>(slf: &Bound<'py, Self>) -> PyResult<(Bound<'py, PyType>, (f32, f32, f32))> {
+        let pos = slf.borrow().get_entity_pos();
+        Ok((slf.get_type(), (pos.x, pos.y, pos.z)))
+    }
+
+    /// 复制一份当前的值，之后的修改不会再影响实体
+    pub fn detach(&self, py: Python<'_>) -> VectorPy {
+        py.allow_threads(|| VectorPy::from(self.get_entity_pos()))
+    }
+
+    /// 是否是独立的值（不是实体的位置/速度等）
+    #[getter]
+    pub fn get_is_detached(&self) -> bool {
+        self.entity_type == EntityVectorType::Ignore
+    }
+
+    pub fn length(&self, py: Python<'_>) -> f32 {
+        py.allow_threads(|| self.length_squared_value().sqrt())
+    }
+
+    pub fn length_squared(&self, py: Python<'_>) -> f32 {
+        py.allow_threads(|| self.length_squared_value())
+    }
+
+    /// 单位向量，长度为 0 的时候返回零向量
+    pub fn normalize(&self, py: Python<'_>) -> VectorPy {
+        py.allow_threads(|| {
+            let length = self.length_squared_value().sqrt();
+            if length == 0.0 {
+                VectorPy::from(Vectorf32::default())
+            } else {
+                self.map(|v| v / length)
+            }
+        })
+    }
+
+    pub fn dot(&self, py: Python<'_>, other: &VectorPy) -> f32 {
+        py.allow_threads(|| {
+            let a = self.get_entity_pos();
+            let b = other.get_entity_pos();
+            a.x * b.x + a.y * b.y + a.z * b.z
+        })
+    }
+
+    pub fn cross(&self, py: Python<'_>, other: &VectorPy) -> VectorPy {
+        py.allow_threads(|| {
+            let a = self.get_entity_pos();
+            let b = other.get_entity_pos();
+            VectorPy::from(Vectorf32::new(
+                a.y * b.z - a.z * b.y,
+                a.z * b.x - a.x * b.z,
+                a.x * b.y - a.y * b.x,
+            ))
+        })
+    }
+
+    /// 线性插值，t = 0 是自己，t = 1 是 other
+    pub fn lerp(&self, py: Python<'_>, other: &VectorPy, t: f32) -> VectorPy {
+        py.allow_threads(|| {
+            let a = self.get_entity_pos();
+            let b = other.get_entity_pos();
+            VectorPy::from(Vectorf32::new(
+                a.x + (b.x - a.x) * t,
+                a.y + (b.y - a.y) * t,
+                a.z + (b.z - a.z) * t,
+            ))
+        })
+    }
+
+    /// 两个向量之间的夹角（弧度），任意一个长度为 0 时返回 0
+    pub fn angle_to(&self, py: Python<'_>, other: &VectorPy) -> f32 {
+        py.allow_threads(|| {
+            let a = self.get_entity_pos();
+            let b = other.get_entity_pos();
+            let lengths =
+                ((a.x * a.x + a.y * a.y + a.z * a.z) * (b.x * b.x + b.y * b.y + b.z * b.z)).sqrt();
+            if lengths == 0.0 {
+                return 0.0;
+            }
+            let dot = a.x * b.x + a.y * b.y + a.z * b.z;
+            (dot / lengths).clamp(-1.0, 1.0).acos()
+        })
+    }
+
+    /// 在 XY 平面上的朝向（弧度），和游戏里的 angle 一样：+Y 为 0，逆时针为正
+    pub fn heading(&self, py: Python<'_>) -> f32 {
+        py.allow_threads(|| {
+            let pos = self.get_entity_pos();
+            (-pos.x).atan2(pos.y)
+        })
+    }
+
+    /// 绕 Z 轴旋转（弧度），默认绕原点
+    #[pyo3(signature = (angle, origin = None))]
+    pub fn rotate_around_z(
+        &self,
+        py: Python<'_>,
+        angle: f32,
+        origin: Option<VectorPy>,
+    ) -> VectorPy {
+        py.allow_threads(|| {
+            let pos = self.get_entity_pos();
+            let origin = origin.map(|o| o.get_entity_pos()).unwrap_or_default();
+            let (sin, cos) = angle.sin_cos();
+            let x = pos.x - origin.x;
+            let y = pos.y - origin.y;
+            VectorPy::from(Vectorf32::new(
+                origin.x + x * cos - y * sin,
+                origin.y + x * sin + y * cos,
+                pos.z,
+            ))
+        })
     }
 
     pub fn distance(&self, py: Python<'_>, other: &VectorPy) -> f32 {
@@ -692,7 +1033,7 @@ pub enum EntityQuaternionType {
 
 #[derive(Clone, Debug, Copy)]
 #[pyclass]
-#[pyo3(name = "Quaternion")]
+#[pyo3(name = "Quaternion", module = "vcmp.types")]
 pub struct QuaternionPy {
     pub entity_type: EntityQuaternionType,
     pub entity_id: EntityId,
@@ -778,73 +1119,199 @@ impl QuaternionPy {
             }
         };
     }
+
+    /// 逐分量加到实体上，add_rotation 之类的用
+    pub fn add_to_entity(&mut self, offset: QuaternionPy) {
+        let sum = (*self + offset).get_entity_quaternion();
+        self.set_entity_quaternion(Some(sum.x), Some(sum.y), Some(sum.z), Some(sum.w));
+    }
 }
 
+/// 加减只是逐分量运算，不是旋转的组合（组合用乘法），结果都是独立的值
 impl Add for QuaternionPy {
     type Output = Self;
-    fn add(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_quaternion();
-        let other = rhs.get_entity_quaternion();
-
-        self.set_entity_quaternion(
-            Some(origin.x + other.x),
-            Some(origin.y + other.y),
-            Some(origin.z + other.z),
-            Some(origin.w + other.w),
-        );
-
-        self
+    fn add(self, rhs: Self) -> Self {
+        let a = self.get_entity_quaternion();
+        let b = rhs.get_entity_quaternion();
+        QuaternionPy::from(Quaternionf32::new(
+            a.x + b.x,
+            a.y + b.y,
+            a.z + b.z,
+            a.w + b.w,
+        ))
     }
 }
 
 impl Sub for QuaternionPy {
     type Output = Self;
-    fn sub(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_quaternion();
-        let other = rhs.get_entity_quaternion();
-
-        self.set_entity_quaternion(
-            Some(origin.x - other.x),
-            Some(origin.y - other.y),
-            Some(origin.z - other.z),
-            Some(origin.w - other.w),
-        );
+    fn sub(self, rhs: Self) -> Self {
+        let a = self.get_entity_quaternion();
+        let b = rhs.get_entity_quaternion();
+        QuaternionPy::from(Quaternionf32::new(
+            a.x - b.x,
+            a.y - b.y,
+            a.z - b.z,
+            a.w - b.w,
+        ))
+    }
+}
+
+/// 四元数乘法（Hamilton product），先转 b 再转 a
+pub(crate) fn quat_mul(a: Quaternionf32, b: Quaternionf32) -> Quaternionf32 {
+    Quaternionf32::new(
+        a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+        a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+        a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w,
+        a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+    )
+}
+
+fn quat_dot(a: Quaternionf32, b: Quaternionf32) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w
+}
+
+fn quat_conjugate(q: Quaternionf32) -> Quaternionf32 {
+    Quaternionf32::new(-q.x, -q.y, -q.z, q.w)
+}
+
+/// 长度为 0 的时候返回单位四元数
+pub(crate) fn quat_normalize(q: Quaternionf32) -> Quaternionf32 {
+    let length = quat_dot(q, q).sqrt();
+    if length == 0.0 {
+        Quaternionf32::new(0.0, 0.0, 0.0, 1.0)
+    } else {
+        Quaternionf32::new(q.x / length, q.y / length, q.z / length, q.w / length)
+    }
+}
+
+/// 长度为 0 的时候返回单位四元数
+fn quat_inverse(q: Quaternionf32) -> Quaternionf32 {
+    let length_squared = quat_dot(q, q);
+    if length_squared == 0.0 {
+        Quaternionf32::new(0.0, 0.0, 0.0, 1.0)
+    } else {
+        let c = quat_conjugate(q);
+        Quaternionf32::new(
+            c.x / length_squared,
+            c.y / length_squared,
+            c.z / length_squared,
+            c.w / length_squared,
+        )
+    }
+}
+
+pub(crate) fn quat_rotate(q: Quaternionf32, v: Vectorf32) -> Vectorf32 {
+    // v' = v + w * t + q.xyz × t，其中 t = 2 * (q.xyz × v)
+    let tx = 2.0 * (q.y * v.z - q.z * v.y);
+    let ty = 2.0 * (q.z * v.x - q.x * v.z);
+    let tz = 2.0 * (q.x * v.y - q.y * v.x);
+    Vectorf32::new(
+        v.x + q.w * tx + (q.y * tz - q.z * ty),
+        v.y + q.w * ty + (q.z * tx - q.x * tz),
+        v.z + q.w * tz + (q.x * ty - q.y * tx),
+    )
+}
+
+/// 欧拉角（弧度）转四元数，先绕 X 再绕 Y 最后绕 Z（即 Z * Y * X）
+pub(crate) fn quat_from_euler(euler: Vectorf32) -> Quaternionf32 {
+    let (sx, cx) = (euler.x * 0.5).sin_cos();
+    let (sy, cy) = (euler.y * 0.5).sin_cos();
+    let (sz, cz) = (euler.z * 0.5).sin_cos();
+    Quaternionf32::new(
+        sx * cy * cz - cx * sy * sz,
+        cx * sy * cz + sx * cy * sz,
+        cx * cy * sz - sx * sy * cz,
+        cx * cy * cz + sx * sy * sz,
+    )
+}
 
-        self
+/// from_euler 的逆运算
+fn quat_to_euler(q: Quaternionf32) -> Vectorf32 {
+    let x = (2.0 * (q.w * q.x + q.y * q.z)).atan2(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+    let y = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0).asin();
+    let z = (2.0 * (q.w * q.z + q.x * q.y)).atan2(1.0 - 2.0 * (q.y * q.y + q.z * q.z));
+    Vectorf32::new(x, y, z)
+}
+
+/// 球面插值，走最短的那条路
+pub(crate) fn quat_slerp(a: Quaternionf32, b: Quaternionf32, t: f32) -> Quaternionf32 {
+    let mut b = b;
+    let mut cos = quat_dot(a, b);
+    if cos < 0.0 {
+        b = Quaternionf32::new(-b.x, -b.y, -b.z, -b.w);
+        cos = -cos;
+    }
+    // 太接近的时候 sin 接近 0，直接线性插值
+    let (ka, kb) = if cos > 0.9995 {
+        (1.0 - t, t)
+    } else {
+        let angle = cos.acos();
+        let sin = angle.sin();
+        (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+    };
+    quat_normalize(Quaternionf32::new(
+        a.x * ka + b.x * kb,
+        a.y * ka + b.y * kb,
+        a.z * ka + b.z * kb,
+        a.w * ka + b.w * kb,
+    ))
+}
+
+/// 旋转矩阵（按列：X 轴、Y 轴、Z 轴）转四元数
+fn quat_from_axes(x: Vectorf32, y: Vectorf32, z: Vectorf32) -> Quaternionf32 {
+    let trace = x.x + y.y + z.z;
+    let q = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        Quaternionf32::new((y.z - z.y) / s, (z.x - x.z) / s, (x.y - y.x) / s, 0.25 * s)
+    } else if x.x > y.y && x.x > z.z {
+        let s = (1.0 + x.x - y.y - z.z).sqrt() * 2.0;
+        Quaternionf32::new(0.25 * s, (y.x + x.y) / s, (z.x + x.z) / s, (y.z - z.y) / s)
+    } else if y.y > z.z {
+        let s = (1.0 + y.y - x.x - z.z).sqrt() * 2.0;
+        Quaternionf32::new((y.x + x.y) / s, 0.25 * s, (z.y + y.z) / s, (z.x - x.z) / s)
+    } else {
+        let s = (1.0 + z.z - x.x - y.y).sqrt() * 2.0;
+        Quaternionf32::new((z.x + x.z) / s, (z.y + y.z) / s, 0.25 * s, (x.y - y.x) / s)
+    };
+    quat_normalize(q)
+}
+
+fn vec_normalize(v: Vectorf32) -> Vectorf32 {
+    let length = (v.x * v.x + v.y * v.y + v.z * v.z).sqrt();
+    if length == 0.0 {
+        v
+    } else {
+        Vectorf32::new(v.x / length, v.y / length, v.z / length)
     }
 }
 
+fn vec_cross(a: Vectorf32, b: Vectorf32) -> Vectorf32 {
+    Vectorf32::new(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+/// 旋转的组合，`a * b` 是先转 b 再转 a，结果是独立的值
 impl Mul for QuaternionPy {
     type Output = Self;
-    fn mul(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_quaternion();
-        let other = rhs.get_entity_quaternion();
-
-        self.set_entity_quaternion(
-            Some(origin.x * other.x),
-            Some(origin.y * other.y),
-            Some(origin.z * other.z),
-            Some(origin.w * other.w),
-        );
-
-        self
+    fn mul(self, rhs: Self) -> Self {
+        QuaternionPy::from(quat_mul(
+            self.get_entity_quaternion(),
+            rhs.get_entity_quaternion(),
+        ))
     }
 }
 
+/// `a / b` 等于 `a * b.inverse()`
 impl Div for QuaternionPy {
     type Output = Self;
-    fn div(mut self, rhs: Self) -> Self {
-        let origin = self.get_entity_quaternion();
-        let other = rhs.get_entity_quaternion();
-
-        self.set_entity_quaternion(
-            Some(origin.x / other.x),
-            Some(origin.y / other.y),
-            Some(origin.z / other.z),
-            Some(origin.w / other.w),
-        );
-
-        self
+    fn div(self, rhs: Self) -> Self {
+        QuaternionPy::from(quat_mul(
+            self.get_entity_quaternion(),
+            quat_inverse(rhs.get_entity_quaternion()),
+        ))
     }
 }
 
@@ -918,14 +1385,16 @@ impl QuaternionPy {
     #[setter]
     pub fn set_x(&mut self, py: Python<'_>, value: f32) {
         py.allow_threads(|| {
-            self.set_entity_quaternion(None, Some(value), None, None);
+            self.set_entity_quaternion(Some(value), None, None, None);
         });
     }
 
+    /// 逐分量相加，不是旋转的组合
     fn __add__(&self, py: Python<'_>, other: &QuaternionPy) -> QuaternionPy {
         py.allow_threads(|| *self + *other)
     }
 
+    /// 逐分量相减
     fn __sub__(&self, py: Python<'_>, other: &QuaternionPy) -> QuaternionPy {
         py.allow_threads(|| *self - *other)
     }
@@ -934,10 +1403,113 @@ impl QuaternionPy {
         py.allow_threads(|| *self * *other)
     }
 
-    fn __div__(&self, py: Python<'_>, other: &QuaternionPy) -> QuaternionPy {
+    fn __truediv__(&self, py: Python<'_>, other: &QuaternionPy) -> QuaternionPy {
         py.allow_threads(|| *self / *other)
     }
 
+    /// 复制一份当前的值，之后的修改不会再影响实体
+    pub fn detach(&self, py: Python<'_>) -> QuaternionPy {
+        py.allow_threads(|| QuaternionPy::from(self.get_entity_quaternion()))
+    }
+
+    /// 是否是独立的值（不是实体的旋转）
+    #[getter]
+    pub fn get_is_detached(&self) -> bool {
+        matches!(self.entity_type, EntityQuaternionType::Ignore)
+    }
+
+    pub fn length(&self, py: Python<'_>) -> f32 {
+        py.allow_threads(|| {
+            let q = self.get_entity_quaternion();
+            quat_dot(q, q).sqrt()
+        })
+    }
+
+    pub fn dot(&self, py: Python<'_>, other: &QuaternionPy) -> f32 {
+        py.allow_threads(|| quat_dot(self.get_entity_quaternion(), other.get_entity_quaternion()))
+    }
+
+    pub fn conjugate(&self, py: Python<'_>) -> QuaternionPy {
+        py.allow_threads(|| QuaternionPy::from(quat_conjugate(self.get_entity_quaternion())))
+    }
+
+    pub fn inverse(&self, py: Python<'_>) -> QuaternionPy {
+        py.allow_threads(|| QuaternionPy::from(quat_inverse(self.get_entity_quaternion())))
+    }
+
+    pub fn normalize(&self, py: Python<'_>) -> QuaternionPy {
+        py.allow_threads(|| QuaternionPy::from(quat_normalize(self.get_entity_quaternion())))
+    }
+
+    /// 转成欧拉角（弧度），顺序和 from_euler 一样
+    pub fn to_euler(&self, py: Python<'_>) -> VectorPy {
+        py.allow_threads(|| VectorPy::from(quat_to_euler(self.get_entity_quaternion())))
+    }
+
+    /// 用这个旋转去转一个向量
+    pub fn rotate(&self, py: Python<'_>, vector: &VectorPy) -> VectorPy {
+        py.allow_threads(|| {
+            VectorPy::from(quat_rotate(
+                quat_normalize(self.get_entity_quaternion()),
+                vector.get_entity_pos(),
+            ))
+        })
+    }
+
+    /// 球面插值，t = 0 是自己，t = 1 是 other
+    pub fn slerp(&self, py: Python<'_>, other: &QuaternionPy, t: f32) -> QuaternionPy {
+        py.allow_threads(|| {
+            QuaternionPy::from(quat_slerp(
+                quat_normalize(self.get_entity_quaternion()),
+                quat_normalize(other.get_entity_quaternion()),
+                t,
+            ))
+        })
+    }
+
+    /// 欧拉角（弧度）转四元数，先绕 X 再绕 Y 最后绕 Z
+    #[staticmethod]
+    pub fn from_euler(py: Python<'_>, euler: &VectorPy) -> QuaternionPy {
+        py.allow_threads(|| QuaternionPy::from(quat_from_euler(euler.get_entity_pos())))
+    }
+
+    /// 绕 axis 旋转 angle（弧度）
+    #[staticmethod]
+    pub fn from_axis_angle(py: Python<'_>, axis: &VectorPy, angle: f32) -> QuaternionPy {
+        py.allow_threads(|| {
+            let axis = vec_normalize(axis.get_entity_pos());
+            let (sin, cos) = (angle * 0.5).sin_cos();
+            QuaternionPy::from(Quaternionf32::new(
+                axis.x * sin,
+                axis.y * sin,
+                axis.z * sin,
+                cos,
+            ))
+        })
+    }
+
+    /// 让 +Y（游戏里的正前方）朝向 forward，up 默认是 +Z
+    #[staticmethod]
+    #[pyo3(signature = (forward, up = None))]
+    pub fn look_rotation(py: Python<'_>, forward: &VectorPy, up: Option<VectorPy>) -> QuaternionPy {
+        py.allow_threads(|| {
+            let forward = vec_normalize(forward.get_entity_pos());
+            let up = up
+                .map(|up| up.get_entity_pos())
+                .unwrap_or(Vectorf32::new(0.0, 0.0, 1.0));
+            let mut right = vec_normalize(vec_cross(forward, up));
+            if right == Vectorf32::default() {
+                // forward 和 up 平行，随便找一个垂直的
+                right = vec_normalize(vec_cross(forward, Vectorf32::new(1.0, 0.0, 0.0)));
+                if right == Vectorf32::default() {
+                    right = vec_normalize(vec_cross(forward, Vectorf32::new(0.0, 1.0, 0.0)));
+                }
+            }
+            let up = vec_cross(right, forward);
+            QuaternionPy::from(quat_from_axes(right, forward, up))
+        })
+    }
+
     fn __repr__(&self) -> String {
         let pos = self.get_entity_quaternion();
         format!(
@@ -1457,13 +2029,202 @@ impl VcmpEntityPoolPy {
     }
 }
 
-pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ServerSettingsPy>()?;
     m.add_class::<RGBPy>()?;
+    let rgb = py.get_type::<RGBPy>();
+    for (name, value) in NAMED_COLORS {
+        rgb.setattr(name.to_uppercase(), RGBPy::from_rgb(*value, None))?;
+    }
     m.add_class::<VectorPy>()?;
     m.add_class::<QuaternionPy>()?;
     m.add_class::<Version>()?;
     m.add_class::<KeyCode>()?;
     m.add_class::<VcmpEventType>()?;
+    enums::module_define(py, m)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    fn vector(x: f32, y: f32, z: f32) -> VectorPy {
+        VectorPy::from(Vectorf32::new(x, y, z))
+    }
+
+    fn assert_vector(actual: Vectorf32, expected: (f32, f32, f32)) {
+        let (x, y, z) = expected;
+        assert!(
+            (actual.x - x).abs() < EPSILON
+                && (actual.y - y).abs() < EPSILON
+                && (actual.z - z).abs() < EPSILON,
+            "expected ({x}, {y}, {z}), got ({}, {}, {})",
+            actual.x,
+            actual.y,
+            actual.z
+        );
+    }
+
+    #[test]
+    fn vector_operators_are_component_wise() {
+        let a = vector(1.0, 2.0, 3.0);
+        let b = vector(4.0, -2.0, 0.5);
+        assert_vector((a + b).get_entity_pos(), (5.0, 0.0, 3.5));
+        assert_vector((a - b).get_entity_pos(), (-3.0, 4.0, 2.5));
+        assert_vector((a * b).get_entity_pos(), (4.0, -4.0, 1.5));
+        assert_vector((a / b).get_entity_pos(), (0.25, -1.0, 6.0));
+        assert_vector(a.map(|v| v * 2.0).get_entity_pos(), (2.0, 4.0, 6.0));
+        assert_vector(a.zip(&b, f32::max).get_entity_pos(), (4.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn vector_math() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let a = vector(3.0, 0.0, 4.0);
+            assert!((a.length(py) - 5.0).abs() < EPSILON);
+            assert_vector(a.normalize(py).get_entity_pos(), (0.6, 0.0, 0.8));
+            assert_vector(
+                vector(0.0, 0.0, 0.0).normalize(py).get_entity_pos(),
+                (0.0, 0.0, 0.0),
+            );
+
+            let x = vector(1.0, 0.0, 0.0);
+            let y = vector(0.0, 1.0, 0.0);
+            assert!(x.dot(py, &y).abs() < EPSILON);
+            assert_vector(x.cross(py, &y).get_entity_pos(), (0.0, 0.0, 1.0));
+            assert_vector(y.cross(py, &x).get_entity_pos(), (0.0, 0.0, -1.0));
+            assert_vector(x.lerp(py, &y, 0.5).get_entity_pos(), (0.5, 0.5, 0.0));
+            assert!((x.angle_to(py, &y) - std::f32::consts::FRAC_PI_2).abs() < EPSILON);
+            assert!(x.angle_to(py, &vector(0.0, 0.0, 0.0)).abs() < EPSILON);
+        });
+    }
+
+    #[test]
+    fn vector_helpers() {
+        assert_vector(
+            vec_cross(Vectorf32::new(0.0, 1.0, 0.0), Vectorf32::new(0.0, 0.0, 1.0)),
+            (1.0, 0.0, 0.0),
+        );
+        assert_vector(
+            vec_normalize(Vectorf32::new(0.0, -2.0, 0.0)),
+            (0.0, -1.0, 0.0),
+        );
+        assert_vector(
+            vec_normalize(Vectorf32::new(0.0, 0.0, 0.0)),
+            (0.0, 0.0, 0.0),
+        );
+    }
+
+    fn assert_quaternion(actual: Quaternionf32, expected: Quaternionf32) {
+        assert!(
+            (actual.x - expected.x).abs() < EPSILON
+                && (actual.y - expected.y).abs() < EPSILON
+                && (actual.z - expected.z).abs() < EPSILON
+                && (actual.w - expected.w).abs() < EPSILON,
+            "expected ({}, {}, {}, {}), got ({}, {}, {}, {})",
+            expected.x,
+            expected.y,
+            expected.z,
+            expected.w,
+            actual.x,
+            actual.y,
+            actual.z,
+            actual.w
+        );
+    }
+
+    #[test]
+    fn euler_round_trip() {
+        for euler in [
+            Vectorf32::new(0.0, 0.0, 0.0),
+            Vectorf32::new(0.3, -0.7, 1.2),
+            Vectorf32::new(-1.0, 0.5, -2.5),
+        ] {
+            let back = quat_to_euler(quat_from_euler(euler));
+            assert_vector(back, (euler.x, euler.y, euler.z));
+        }
+    }
+
+    #[test]
+    fn rotate_quarter_turn_around_z() {
+        let q = quat_from_euler(Vectorf32::new(0.0, 0.0, std::f32::consts::FRAC_PI_2));
+        assert_vector(
+            quat_rotate(q, Vectorf32::new(1.0, 0.0, 0.0)),
+            (0.0, 1.0, 0.0),
+        );
+        assert_vector(
+            quat_rotate(q, Vectorf32::new(0.0, 0.0, 1.0)),
+            (0.0, 0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn multiply_by_inverse_is_identity() {
+        let identity = Quaternionf32::new(0.0, 0.0, 0.0, 1.0);
+        let q = quat_from_euler(Vectorf32::new(0.4, 1.1, -0.8));
+        assert_quaternion(quat_mul(q, quat_inverse(q)), identity);
+        assert_quaternion(quat_mul(quat_inverse(q), q), identity);
+        assert_quaternion(
+            quat_normalize(Quaternionf32::new(0.0, 0.0, 0.0, 0.0)),
+            identity,
+        );
+    }
+
+    #[test]
+    fn slerp_endpoints_and_midpoint() {
+        let a = Quaternionf32::new(0.0, 0.0, 0.0, 1.0);
+        let b = quat_from_euler(Vectorf32::new(0.0, 0.0, std::f32::consts::FRAC_PI_2));
+        assert_quaternion(quat_slerp(a, b, 0.0), a);
+        assert_quaternion(quat_slerp(a, b, 1.0), b);
+        let half = quat_from_euler(Vectorf32::new(0.0, 0.0, std::f32::consts::FRAC_PI_4));
+        assert_quaternion(quat_slerp(a, b, 0.5), half);
+    }
+
+    #[test]
+    fn slerp_takes_shortest_path() {
+        let a = Quaternionf32::new(0.0, 0.0, 0.0, 1.0);
+        let b = quat_from_euler(Vectorf32::new(0.0, 0.0, std::f32::consts::FRAC_PI_2));
+        // -b 和 b 是同一个旋转，中点应该还是转 45 度
+        let negated = Quaternionf32::new(-b.x, -b.y, -b.z, -b.w);
+        let half = quat_from_euler(Vectorf32::new(0.0, 0.0, std::f32::consts::FRAC_PI_4));
+        assert_quaternion(quat_slerp(a, negated, 0.5), half);
+    }
+
+    #[test]
+    fn parse_color_forms() {
+        let rgb = |text: &str| parse_color(text).map(|color| color.as_rgb());
+        assert_eq!(rgb("#FF8000"), Some(0xFF8000));
+        assert_eq!(rgb("[#00ff00]"), Some(0x00FF00));
+        assert_eq!(rgb("0x0000FF"), Some(0x0000FF));
+        assert_eq!(rgb(" Red "), Some(0xFF0000));
+        assert_eq!(rgb("gray"), rgb("grey"));
+        assert_eq!(
+            parse_color("#11223344").map(|color| color.as_rgba()),
+            Some(0x11223344)
+        );
+    }
+
+    #[test]
+    fn parse_color_rejects_invalid() {
+        for text in [
+            "",
+            "#",
+            "#12345",
+            "#GGGGGG",
+            "0x1234567890",
+            "[#FF0000",
+            "notacolor",
+        ] {
+            assert!(parse_color(text).is_none(), "{text:?} should be invalid");
+        }
+    }
+
+    #[test]
+    fn color_code_format() {
+        assert_eq!(color_code(Color::from_rgb(0x1A2B3C, None)), "[#1A2B3C]");
+    }
+}
@@ -0,0 +1,617 @@
+//! 代替魔法数字的枚举
+//!
+//! 都实现了 `__index__`，所以原来收 int 的方法也可以直接传枚举进去
+//!
+//! 只用来传参，事件和 getter 返回的还是 int，脚本里拿到的值可能不在枚举里（比如自定义武器）
+
+use pyo3::{
+    Bound, PyResult, Python,
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyModule, PyModuleMethods},
+};
+
+use crate::py::util::{SKINS, VEHICLE_NAMES, WEAPON_NAMES};
+
+macro_rules! int_enum {
+    ($name:ident, $py_name:literal, $names:expr, { $($variant:ident = $value:literal,)* }) => {
+        #[pyclass(eq, eq_int, hash, frozen, module = "vcmp.types")]
+        #[pyo3(name = $py_name)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(non_camel_case_types)]
+        pub enum $name {
+            $($variant = $value,)*
+        }
+
+        impl TryFrom<i32> for $name {
+            type Error = i32;
+
+            fn try_from(value: i32) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok(Self::$variant),)*
+                    _ => Err(value),
+                }
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(value: $name) -> Self {
+                value as i32
+            }
+        }
+
+        #[pymethods]
+        impl $name {
+            #[staticmethod]
+            pub fn from_id(value: i32) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
+
+            /// 枚举名和显示名都可以，不区分大小写
+            #[staticmethod]
+            pub fn from_name(name: &str) -> PyResult<Self> {
+                let name = name.trim();
+                $(
+                    if name.eq_ignore_ascii_case(stringify!($variant)) {
+                        return Ok(Self::$variant);
+                    }
+                )*
+                let names: &[(i32, &'static str)] = &$names;
+                names
+                    .iter()
+                    .find(|(_, n)| n.eq_ignore_ascii_case(name))
+                    .and_then(|(id, _)| Self::try_from(*id).ok())
+                    .ok_or_else(|| {
+                        PyValueError::new_err(format!("Unknown {}: {name}", $py_name))
+                    })
+            }
+
+            #[getter]
+            pub fn get_name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => stringify!($variant),)*
+                }
+            }
+
+            /// 游戏里的名字
+            #[getter]
+            pub fn get_display_name(&self) -> &'static str {
+                let names: &[(i32, &'static str)] = &$names;
+                names
+                    .iter()
+                    .find(|(id, _)| *id == *self as i32)
+                    .map(|(_, n)| *n)
+                    .unwrap_or(self.get_name())
+            }
+
+            #[getter]
+            pub fn get_value(&self) -> i32 {
+                *self as i32
+            }
+
+            fn __index__(&self) -> i32 {
+                *self as i32
+            }
+        }
+    };
+}
+
+/// 武器 id
+int_enum! {
+    WeaponPy, "Weapon", WEAPON_NAMES,
+    {
+        Fists = 0,
+        BrassKnuckles = 1,
+        Screwdriver = 2,
+        Golfclub = 3,
+        Nitestick = 4,
+        Knife = 5,
+        BaseballBat = 6,
+        Hammer = 7,
+        MeatCleaver = 8,
+        Machete = 9,
+        Katana = 10,
+        Chainsaw = 11,
+        Grenades = 12,
+        RemoteGrenades = 13,
+        Teargas = 14,
+        MolotovCocktails = 15,
+        Missile = 16,
+        Colt45 = 17,
+        Python = 18,
+        Shotgun = 19,
+        SpazShotgun = 20,
+        StubbyShotgun = 21,
+        Tec9 = 22,
+        Uzi = 23,
+        Ingram = 24,
+        MP5 = 25,
+        M4 = 26,
+        Ruger = 27,
+        SniperRifle = 28,
+        LaserSniper = 29,
+        RocketLauncher = 30,
+        FlameThrower = 31,
+        M60 = 32,
+        Minigun = 33,
+        Bomb = 34,
+        Helicannon = 35,
+        Camera = 36,
+        Vehicle = 39,
+        HeliBlades = 40,
+        Explosion = 41,
+        DriveBy = 42,
+        Drown = 43,
+        Fall = 44,
+        Explosion_51 = 51,
+        HeliBlades_60 = 60,
+        Suicide = 70,
+    }
+}
+
+/// 载具模型
+int_enum! {
+    VehicleModelPy, "VehicleModel", VEHICLE_NAMES,
+    {
+        Landstalker = 130,
+        Idaho = 131,
+        Stinger = 132,
+        Linerunner = 133,
+        Perennial = 134,
+        Sentinel = 135,
+        Rio = 136,
+        Firetruck = 137,
+        Trashmaster = 138,
+        Stretch = 139,
+        Manana = 140,
+        Infernus = 141,
+        Voodoo = 142,
+        Pony = 143,
+        Mule = 144,
+        Cheetah1 = 145,
+        Ambulance = 146,
+        FBIWashington = 147,
+        Moonbeam = 148,
+        Esperanto = 149,
+        Taxi = 150,
+        Washington = 151,
+        Bobcat = 152,
+        MrWhoopee = 153,
+        BFInjection = 154,
+        Hunter = 155,
+        Police = 156,
+        Enforcer = 157,
+        Securicar = 158,
+        Banshee = 159,
+        Predator = 160,
+        Bus = 161,
+        Rhino = 162,
+        BarracksOL = 163,
+        CubanHermes = 164,
+        Angel = 166,
+        Coach = 167,
+        Cabbie = 168,
+        Stallion = 169,
+        Rumpo = 170,
+        RCBandit = 171,
+        RomeroSHearse = 172,
+        Packer = 173,
+        SentinelXS = 174,
+        Admiral = 175,
+        Squalo = 176,
+        SeaSparrow = 177,
+        PizzaBoy = 178,
+        GangBurrito = 179,
+        Speeder = 182,
+        Reefer = 183,
+        Tropic = 184,
+        Flatbed = 185,
+        Yankee = 186,
+        Caddy = 187,
+        ZebraCab = 188,
+        TopFun = 189,
+        Skimmer = 190,
+        PCJ600 = 191,
+        Faggio = 192,
+        Freeway = 193,
+        RCBaron = 194,
+        RCRaider = 195,
+        Glendale = 196,
+        Oceanic = 197,
+        Sanchez = 198,
+        Sparrow = 199,
+        Patriot = 200,
+        LoveFist = 201,
+        CoastGuard = 202,
+        Dinghy = 203,
+        Hermes = 204,
+        Sabre = 205,
+        SabreTurbo = 206,
+        Phoenix = 207,
+        Walton = 208,
+        Regina = 209,
+        Comet = 210,
+        Deluxo = 211,
+        Burrito = 212,
+        SpandExpress = 213,
+        Marquis = 214,
+        BaggageHandler = 215,
+        KaufmanCab = 216,
+        Maverick = 217,
+        VCNMaverick = 218,
+        Rancher = 219,
+        FBIRancher = 220,
+        Virgo = 221,
+        Greenwood = 222,
+        CubanJetmax = 223,
+        HotringRacer1 = 224,
+        Sandking = 225,
+        BlistaCompact = 226,
+        PoliceMaverick = 227,
+        Boxville = 228,
+        Benson = 229,
+        MesaGrande = 230,
+        RCGoblin = 231,
+        HotringRacer2 = 232,
+        HotringRacer3 = 233,
+        BloodringBanger1 = 234,
+        BloodringBanger2 = 235,
+        Cheetah2 = 236,
+    }
+}
+
+/// 皮肤，重名的后面加上 id
+int_enum! {
+    SkinPy, "Skin", SKINS,
+    {
+        TommyVercetti = 0,
+        Cop = 1,
+        SWAT = 2,
+        FBI = 3,
+        Army = 4,
+        Paramedic = 5,
+        Firefighter = 6,
+        GolfGuy1 = 7,
+        BumLady1 = 9,
+        BumLady2 = 10,
+        Punk1 = 11,
+        Lawyer = 12,
+        SpanishLady1 = 13,
+        SpanishLady2 = 14,
+        CoolGuy1 = 15,
+        ArabicGuy = 16,
+        BeachLady1 = 17,
+        BeachLady2 = 18,
+        BeachGuy1 = 19,
+        BeachGuy2 = 20,
+        OfficeLady1 = 21,
+        Waitress1 = 22,
+        FoodLady = 23,
+        Prostitute1 = 24,
+        BumLady3 = 25,
+        BumGuy1 = 26,
+        Garbageman1 = 27,
+        TaxiDriver1 = 28,
+        Haitian1 = 29,
+        Criminal1 = 30,
+        HoodLady = 31,
+        Granny1 = 32,
+        Businessman1 = 33,
+        ChurchGuy = 34,
+        ClubLady = 35,
+        ChurchLady = 36,
+        Pimp = 37,
+        BeachLady3 = 38,
+        BeachGuy3 = 39,
+        BeachLady4 = 40,
+        BeachGuy4 = 41,
+        Businessman2 = 42,
+        Prostitute2 = 43,
+        BumLady4 = 44,
+        BumGuy2 = 45,
+        Haitian2 = 46,
+        ConstructionWorker1 = 47,
+        Punk2 = 48,
+        Prostitute3 = 49,
+        Granny2 = 50,
+        Punk3 = 51,
+        Businessman3 = 52,
+        SpanishLady3 = 53,
+        SpanishLady4 = 54,
+        CoolGuy2 = 55,
+        Businessman4 = 56,
+        BeachLady5 = 57,
+        BeachGuy5 = 58,
+        BeachLady6 = 59,
+        BeachGuy6 = 60,
+        ConstructionWorker2 = 61,
+        GolfGuy2 = 62,
+        GolfLady = 63,
+        GolfGuy3 = 64,
+        BeachLady7 = 65,
+        BeachGuy7 = 66,
+        OfficeLady2 = 67,
+        Businessman5 = 68,
+        Businessman6 = 69,
+        Prostitute2_70 = 70,
+        BumLady4_71 = 71,
+        BumGuy3 = 72,
+        SpanishGuy = 73,
+        TaxiDriver2 = 74,
+        GymLady = 75,
+        GymGuy = 76,
+        SkateLady = 77,
+        SkateGuy = 78,
+        Shopper1 = 79,
+        Shopper2 = 80,
+        Tourist1 = 81,
+        Tourist2 = 82,
+        Cuban1 = 83,
+        Cuban2 = 84,
+        Haitian3 = 85,
+        Haitian4 = 86,
+        Shark1 = 87,
+        Shark2 = 88,
+        DiazGuy1 = 89,
+        DiazGuy2 = 90,
+        DBPSecurity1 = 91,
+        DBPSecurity2 = 92,
+        Biker1 = 93,
+        Biker2 = 94,
+        VercettiGuy1 = 95,
+        VercettiGuy2 = 96,
+        UndercoverCop1 = 97,
+        UndercoverCop2 = 98,
+        UndercoverCop3 = 99,
+        UndercoverCop4 = 100,
+        UndercoverCop5 = 101,
+        UndercoverCop6 = 102,
+        RichGuy = 103,
+        CoolGuy3 = 104,
+        Prostitute3_105 = 105,
+        Prostitute4 = 106,
+        LoveFist1 = 107,
+        KenRosenburg = 108,
+        CandySuxx = 109,
+        Hilary = 110,
+        LoveFist2 = 111,
+        Phil = 112,
+        RockstarGuy = 113,
+        Sonny = 114,
+        Lance = 115,
+        Mercedes = 116,
+        LoveFist3 = 117,
+        AlexShrub = 118,
+        LanceCop = 119,
+        Lance_120 = 120,
+        Cortez = 121,
+        LoveFist4 = 122,
+        ColumbianGuy1 = 123,
+        HilaryRobber = 124,
+        Mercedes_125 = 125,
+        Cam = 126,
+        CamRobber = 127,
+        PhilOneArm = 128,
+        PhilRobber = 129,
+        CoolGuy4 = 130,
+        PizzaMan = 131,
+        TaxiDriver1_132 = 132,
+        TaxiDriver2_133 = 133,
+        Sailor1 = 134,
+        Sailor2 = 135,
+        Sailor3 = 136,
+        Chef = 137,
+        Criminal2 = 138,
+        FrenchGuy = 139,
+        Garbageman2 = 140,
+        Haitian5 = 141,
+        Waitress2 = 142,
+        SonnyGuy1 = 143,
+        SonnyGuy2 = 144,
+        SonnyGuy3 = 145,
+        ColumbianGuy2 = 146,
+        Haitian6 = 147,
+        BeachGuy8 = 148,
+        Garbageman3 = 149,
+        Garbageman4 = 150,
+        Garbageman5 = 151,
+        Tranny = 152,
+        Thug5 = 153,
+        SpandExGuy1 = 154,
+        SpandExGuy2 = 155,
+        Stripper1 = 156,
+        Stripper2 = 157,
+        Stripper3 = 158,
+        StoreClerk = 159,
+        TommyWithSuit = 161,
+        WorkerTommy = 162,
+        GolferTommy = 163,
+        CubanTommy = 164,
+        VCPDTommy = 165,
+        BankRobberTommy = 166,
+        StreetTommy = 167,
+        MafiaTommy = 168,
+        JoggerTommy1 = 169,
+        JoggerTommy2 = 170,
+        GuyWithSuit1 = 171,
+        GuyWithSuit3 = 172,
+        Prostitute5 = 173,
+        Rico = 174,
+        Prostitute3_175 = 175,
+        ClubLady_176 = 176,
+        Prostitute2_177 = 177,
+        SkullTShirtGuy = 178,
+        EasterEggTommy = 179,
+        DiazGangster1 = 180,
+        DiazGangster2 = 181,
+        HoodLady_182 = 182,
+        Punk1_183 = 183,
+        TrayLady = 184,
+        KentPaul = 185,
+        TaxiDriver1_186 = 186,
+        DeformedKenRosenberg = 187,
+        DeformedWoman = 188,
+        DeformedMan = 189,
+        DeformedCortez = 190,
+        DeformedLanceVance = 191,
+        Thief1 = 192,
+        Thief2 = 193,
+        Thief3 = 194,
+    }
+}
+
+pub const BODY_PART_NAMES: [(i32, &str); 7] = [
+    (0, "Body"),
+    (1, "Torso"),
+    (2, "Left Arm"),
+    (3, "Right Arm"),
+    (4, "Left Leg"),
+    (5, "Right Leg"),
+    (6, "Head"),
+];
+
+/// 被打中的身体部位
+int_enum! {
+    BodyPartPy, "BodyPart", BODY_PART_NAMES,
+    {
+        Body = 0,
+        Torso = 1,
+        LeftArm = 2,
+        RightArm = 3,
+        LeftLeg = 4,
+        RightLeg = 5,
+        Head = 6,
+    }
+}
+
+pub const PLAYER_STATE_NAMES: [(i32, &str); 9] = [
+    (0, "None"),
+    (1, "Normal"),
+    (2, "Aim"),
+    (3, "Driver"),
+    (4, "Passenger"),
+    (5, "Enter Driver"),
+    (6, "Enter Passenger"),
+    (7, "Exit"),
+    (8, "Unspawned"),
+];
+
+/// 玩家状态
+int_enum! {
+    PlayerStatePy, "PlayerState", PLAYER_STATE_NAMES,
+    {
+        NoState = 0,
+        Normal = 1,
+        Aim = 2,
+        Driver = 3,
+        Passenger = 4,
+        EnterDriver = 5,
+        EnterPassenger = 6,
+        Exit = 7,
+        Unspawned = 8,
+    }
+}
+
+pub const PLAYER_ACTION_NAMES: [(i32, &str); 13] = [
+    (0, "None"),
+    (1, "Normal"),
+    (12, "Aiming"),
+    (16, "Shooting"),
+    (41, "Jumping"),
+    (42, "Lie Down"),
+    (43, "Getting Up"),
+    (44, "Jump From Vehicle"),
+    (50, "Driving"),
+    (54, "Dying"),
+    (55, "Wasted"),
+    (58, "Entering Vehicle"),
+    (60, "Exiting Vehicle"),
+];
+
+/// 玩家动作
+int_enum! {
+    PlayerActionPy, "PlayerAction", PLAYER_ACTION_NAMES,
+    {
+        NoAction = 0,
+        Normal = 1,
+        Aiming = 12,
+        Shooting = 16,
+        Jumping = 41,
+        LieDown = 42,
+        GettingUp = 43,
+        JumpFromVehicle = 44,
+        Driving = 50,
+        Dying = 54,
+        Wasted = 55,
+        EnteringVehicle = 58,
+        ExitingVehicle = 60,
+    }
+}
+
+pub const EXPLOSION_TYPE_NAMES: [(i32, &str); 11] = [
+    (0, "Grenade"),
+    (1, "Molotov"),
+    (2, "Rocket"),
+    (3, "Car"),
+    (4, "Car Quick"),
+    (5, "Boat"),
+    (6, "Heli"),
+    (7, "Mine"),
+    (8, "Barrel"),
+    (9, "Tank Grenade"),
+    (10, "Heli Bomb"),
+];
+
+/// 爆炸类型
+int_enum! {
+    ExplosionTypePy, "ExplosionType", EXPLOSION_TYPE_NAMES,
+    {
+        Grenade = 0,
+        Molotov = 1,
+        Rocket = 2,
+        Car = 3,
+        CarQuick = 4,
+        Boat = 5,
+        Heli = 6,
+        Mine = 7,
+        Barrel = 8,
+        TankGrenade = 9,
+        HeliBomb = 10,
+    }
+}
+
+pub const WEATHER_NAMES: [(i32, &str); 7] = [
+    (0, "Sunny"),
+    (1, "Cloudy"),
+    (2, "Rainy"),
+    (3, "Foggy"),
+    (4, "Extra Sunny"),
+    (5, "Hurricane"),
+    (6, "Extra Colours"),
+];
+
+/// 天气
+int_enum! {
+    WeatherPy, "Weather", WEATHER_NAMES,
+    {
+        Sunny = 0,
+        Cloudy = 1,
+        Rainy = 2,
+        Foggy = 3,
+        ExtraSunny = 4,
+        Hurricane = 5,
+        ExtraColours = 6,
+    }
+}
+
+pub fn module_define(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<WeaponPy>()?;
+    m.add_class::<VehicleModelPy>()?;
+    m.add_class::<SkinPy>()?;
+    m.add_class::<BodyPartPy>()?;
+    m.add_class::<PlayerStatePy>()?;
+    m.add_class::<PlayerActionPy>()?;
+    m.add_class::<ExplosionTypePy>()?;
+    m.add_class::<WeatherPy>()?;
+    Ok(())
+}
@@ -1,9 +1,14 @@
+use pyo3::exceptions::PyValueError;
 use pyo3::types::{PyDict, PyDictMethods, PyModuleMethods};
-use pyo3::{Bound, PyResult, Python, pyfunction, types::PyModule, wrap_pyfunction};
+use pyo3::{
+    Bound, PyRefMut, PyResult, Python, pyclass, pyfunction, pymethods, types::PyModule,
+    wrap_pyfunction,
+};
+use vcmp_bindings::utils::Color;
 
 use crate::functions::player::PlayerPy;
-use crate::pool::ENTITY_POOL;
-use crate::py::types::RGBPy;
+use crate::pool::read_pool;
+use crate::py::types::{RGBPy, color_code, parse_color};
 
 pub const SKINS: [(i32, &str); 193] = [
     (0, "Tommy Vercetti"),
@@ -476,31 +481,150 @@ pub fn get_district_name(x: f64, y: f64) -> &'static str {
 
 #[pyfunction]
 pub fn get_players() -> Vec<PlayerPy> {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
+    let pool = read_pool();
     pool.get_players()
 }
 
 #[pyfunction]
 pub fn announce_all(announce_type: i32, message: String) {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-    for player in pool.get_players() {
-        player.announce(announce_type, &message.clone());
+    let pool = read_pool();
+    for player in pool.players() {
+        player.announce(announce_type, &message);
     }
 }
 
 #[pyfunction]
 pub fn message_all(message: String) {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-    for player in pool.get_players() {
-        player.message(&message.clone());
+    let pool = read_pool();
+    for player in pool.players() {
+        player.message(&message);
     }
 }
 
 #[pyfunction]
 pub fn raw_message_all(color: RGBPy, message: String) {
-    let pool = ENTITY_POOL.lock().expect("Failed to lock entity pool");
-    for player in pool.get_players() {
-        player.raw_message(color, &message.clone());
+    let pool = read_pool();
+    for player in pool.players() {
+        player.raw_message(color, &message);
+    }
+}
+
+/// 把 `{red}Warning{/}` 这样的 markup 转成客户端的内联颜色代码
+///
+/// `{颜色}` 开始一段颜色，可以是名字或者 `#RRGGBB`，`{/}` 回到上一层颜色，
+/// `{{` 和 `}}` 是字面上的大括号
+pub fn render_markup(markup: &str, base: Color) -> Result<String, String> {
+    let mut stack = vec![base];
+    let mut output = String::with_capacity(markup.len());
+    let mut chars = markup.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                output.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                output.push('}');
+            }
+            '{' => {
+                let mut tag = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(format!("Unclosed markup tag: {{{tag}")),
+                    }
+                }
+                if tag == "/" {
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                } else {
+                    let color = parse_color(&tag).ok_or(format!("Invalid color: {tag}"))?;
+                    stack.push(color);
+                }
+                output.push_str(&color_code(*stack.last().unwrap_or(&base)));
+            }
+            c => output.push(c),
+        }
+    }
+    Ok(output)
+}
+
+#[pyfunction]
+#[pyo3(signature = (markup, base = None))]
+pub fn format_markup(markup: &str, base: Option<RGBPy>) -> PyResult<String> {
+    let base = base.unwrap_or(RGBPy::from_rgb(0xFFFFFF, None));
+    render_markup(markup, base.into()).map_err(PyValueError::new_err)
+}
+
+#[pyfunction]
+pub fn markup_message_all(markup: &str) -> PyResult<String> {
+    let message = format_markup(markup, None)?;
+    message_all(message.clone());
+    Ok(message)
+}
+
+/// 拼一条带颜色的消息
+#[pyclass]
+#[pyo3(name = "MessageBuilder")]
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    base: RGBPy,
+    message: String,
+}
+
+#[pymethods]
+impl MessageBuilder {
+    #[new]
+    #[pyo3(signature = (base = None))]
+    fn new(base: Option<RGBPy>) -> Self {
+        Self {
+            base: base.unwrap_or(RGBPy::from_rgb(0xFFFFFF, None)),
+            message: String::new(),
+        }
+    }
+
+    /// 原样追加，不解析 markup
+    fn text<'a>(mut slf: PyRefMut<'a, Self>, text: &str) -> PyRefMut<'a, Self> {
+        slf.message.push_str(text);
+        slf
+    }
+
+    /// 追加一段指定颜色的文字，之后回到基础颜色
+    fn colored<'a>(mut slf: PyRefMut<'a, Self>, color: RGBPy, text: &str) -> PyRefMut<'a, Self> {
+        let reset = slf.base.as_code();
+        slf.message.push_str(&color.as_code());
+        slf.message.push_str(text);
+        slf.message.push_str(&reset);
+        slf
+    }
+
+    fn markup<'a>(mut slf: PyRefMut<'a, Self>, markup: &str) -> PyResult<PyRefMut<'a, Self>> {
+        let rendered = render_markup(markup, slf.base.into()).map_err(PyValueError::new_err)?;
+        slf.message.push_str(&rendered);
+        Ok(slf)
+    }
+
+    fn build(&self) -> String {
+        self.message.clone()
+    }
+
+    fn send(&self, player: PlayerPy) {
+        player.raw_message(self.base, &self.message);
+    }
+
+    fn send_all(&self) {
+        raw_message_all(self.base, self.message.clone());
+    }
+
+    fn __str__(&self) -> String {
+        self.build()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MessageBuilder({:?})", self.message)
     }
 }
 
@@ -511,6 +635,9 @@ pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(announce_all, m)?)?;
     m.add_function(wrap_pyfunction!(message_all, m)?)?;
     m.add_function(wrap_pyfunction!(raw_message_all, m)?)?;
+    m.add_function(wrap_pyfunction!(format_markup, m)?)?;
+    m.add_function(wrap_pyfunction!(markup_message_all, m)?)?;
+    m.add_class::<MessageBuilder>()?;
 
     let skin_dict = PyDict::new(py);
     SKINS.iter().for_each(|(k, v)| {
@@ -557,3 +684,38 @@ pub fn module_define(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(markup: &str) -> Result<String, String> {
+        render_markup(markup, Color::from_rgb(0xFFFFFF, None))
+    }
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        assert_eq!(render("hello world").as_deref(), Ok("hello world"));
+        assert_eq!(render("{{literal}}").as_deref(), Ok("{literal}"));
+    }
+
+    #[test]
+    fn nested_colors_fall_back() {
+        assert_eq!(
+            render("{red}Warning{/} ok").as_deref(),
+            Ok("[#FF0000]Warning[#FFFFFF] ok")
+        );
+        assert_eq!(
+            render("{red}a{#0000FF}b{/}c{/}d").as_deref(),
+            Ok("[#FF0000]a[#0000FF]b[#FF0000]c[#FFFFFF]d")
+        );
+        // 多出来的 {/} 停在基础颜色
+        assert_eq!(render("{/}{/}x").as_deref(), Ok("[#FFFFFF][#FFFFFF]x"));
+    }
+
+    #[test]
+    fn invalid_markup_is_an_error() {
+        assert_eq!(render("{red"), Err("Unclosed markup tag: {red".to_string()));
+        assert_eq!(render("{nope}x"), Err("Invalid color: nope".to_string()));
+    }
+}
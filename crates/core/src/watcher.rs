@@ -0,0 +1,433 @@
+//! 脚本目录变动自动重载
+//!
+//! 没有用系统的文件通知，就是定时扫一遍脚本目录比较修改时间，
+//! 改动停下来超过 debounce 之后才设置 `need_reload`，
+//! 然后下一个 server frame 会走原来的重载流程。
+//!
+//! 只看脚本自己的 .py 和插件会加载的 toml（`class_file`）：
+//! 文件入口所在的目录只扫最上面一层和里面的 Python 包（有 `__init__.py` 的目录），
+//! 不会把整个服务器目录都扫一遍；包入口扫整个包。
+//! 隐藏目录、`__pycache__` 和虚拟环境（有 `pyvenv.cfg` 的目录）都跳过。
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use tracing::{Level, event};
+
+use crate::{
+    cfg::{get_class_file, get_watch_debounce},
+    py::{
+        GLOBAL_VAR,
+        loader::{ScriptEntry, script_entries},
+    },
+};
+
+/// 多久扫一次目录
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 目录里会触发重载的文件后缀，toml 只看插件自己加载的那几个
+const WATCH_EXTENSION: &str = "py";
+
+/// 要扫的目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchRoot {
+    pub path: PathBuf,
+    /// 只进 Python 包（有 `__init__.py` 的目录），文件入口所在的目录是这样
+    pub packages_only: bool,
+}
+
+pub struct ScriptWatcher {
+    roots: Vec<WatchRoot>,
+    files: Vec<PathBuf>,
+    debounce: Duration,
+    snapshot: HashMap<PathBuf, SystemTime>,
+    changed_at: Option<Instant>,
+}
+
+impl ScriptWatcher {
+    /// files 是目录之外也要看的文件，比如 class 文件
+    pub fn new(roots: Vec<WatchRoot>, files: Vec<PathBuf>, debounce: Duration) -> Self {
+        let snapshot = scan(&roots, &files);
+        Self {
+            roots,
+            files,
+            debounce,
+            snapshot,
+            changed_at: None,
+        }
+    }
+
+    pub fn roots(&self) -> &[WatchRoot] {
+        &self.roots
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// 扫一遍目录，改动停下来超过 debounce 之后返回 true（只返回一次）
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let current = scan(&self.roots, &self.files);
+        if current != self.snapshot {
+            self.snapshot = current;
+            self.changed_at = Some(now);
+            return false;
+        }
+        match self.changed_at {
+            Some(changed_at) if now.duration_since(changed_at) >= self.debounce => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 要不要进这个子目录，虚拟环境不一定叫 .venv，所以看有没有 pyvenv.cfg
+fn should_descend(dir: &Path, packages_only: bool) -> bool {
+    let name = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if name.starts_with('.') || name == "__pycache__" || dir.join("pyvenv.cfg").exists() {
+        return false;
+    }
+    !packages_only || dir.join("__init__.py").is_file()
+}
+
+/// 收集需要监视的文件和修改时间
+fn scan(roots: &[WatchRoot], extra_files: &[PathBuf]) -> HashMap<PathBuf, SystemTime> {
+    let mut files = HashMap::new();
+    let mut dirs = roots
+        .iter()
+        .map(|root| (root.path.clone(), root.packages_only))
+        .collect::<Vec<_>>();
+    while let Some((dir, packages_only)) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if should_descend(&path, packages_only) {
+                    dirs.push((path, packages_only));
+                }
+                continue;
+            }
+            let watched = path.extension().is_some_and(|ext| ext == WATCH_EXTENSION);
+            if watched && let Ok(modified) = metadata.modified() {
+                files.insert(path, modified);
+            }
+        }
+    }
+    for path in extra_files {
+        if let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) {
+            files.insert(path.clone(), modified);
+        }
+    }
+    files
+}
+
+/// 所有脚本入口所在的目录
+pub fn script_dirs() -> Vec<WatchRoot> {
+    watch_roots(&script_entries())
+}
+
+/// 插件自己会加载的文件，现在只有 class 文件
+pub fn config_files() -> Vec<PathBuf> {
+    let class_file = get_class_file();
+    if class_file.is_empty() {
+        return Vec::new();
+    }
+    let path = PathBuf::from(class_file);
+    vec![std::path::absolute(&path).unwrap_or(path)]
+}
+
+/// 文件和 `module:function` 入口看加到 sys.path 的目录，只进里面的 Python 包；
+/// 包入口扫整个包目录。已经被整个扫到的目录不重复扫
+fn watch_roots(entries: &[ScriptEntry]) -> Vec<WatchRoot> {
+    let mut roots: Vec<WatchRoot> = Vec::new();
+    for entry in entries {
+        let (dir, packages_only) = match entry {
+            ScriptEntry::Package { path, .. } => (path.clone(), false),
+            _ => (entry.search_path(), true),
+        };
+        let path = std::path::absolute(&dir).unwrap_or(dir);
+        let covered = roots.iter().any(|root| {
+            (path == root.path && (packages_only || !root.packages_only))
+                || (!root.packages_only && path.starts_with(&root.path))
+        });
+        if covered {
+            continue;
+        }
+        roots.retain(|root| !(root.path == path || !packages_only && root.path.starts_with(&path)));
+        roots.push(WatchRoot {
+            path,
+            packages_only,
+        });
+    }
+    roots
+}
+
+fn request_reload() {
+    let mut var = GLOBAL_VAR.lock().expect("Failed to lock global var");
+    if var.need_reload {
+        return;
+    }
+    event!(Level::INFO, "Script changed, reloading");
+    var.need_reload = true;
+}
+
+pub fn init() {
+    let mut watcher = ScriptWatcher::new(script_dirs(), config_files(), get_watch_debounce());
+    event!(
+        Level::INFO,
+        "Watching {:?} and {:?} for script changes",
+        watcher.roots(),
+        watcher.files()
+    );
+    let _ = thread::spawn(move || {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if watcher.poll(Instant::now()) {
+                request_reload();
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    const DEBOUNCE: Duration = Duration::from_millis(200);
+
+    /// 测试用的临时目录，drop 的时候删掉
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            static COUNTER: AtomicUsize = AtomicUsize::new(0);
+            let path = std::env::temp_dir().join(format!(
+                "vcmp-watcher-test-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn write(&self, name: &str, content: &str) -> PathBuf {
+            let path = self.0.join(name);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(&path, content).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// 有的文件系统修改时间精度不够，直接把时间往后拨
+    fn touch(path: &Path, seconds: u64) {
+        let time = SystemTime::now() + Duration::from_secs(seconds);
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    fn whole(dir: &TempDir) -> WatchRoot {
+        WatchRoot {
+            path: dir.0.clone(),
+            packages_only: false,
+        }
+    }
+
+    /// 发现改动之后要等 debounce 才返回 true，而且只返回一次
+    fn assert_reload(watcher: &mut ScriptWatcher, now: Instant) {
+        assert!(!watcher.poll(now), "change should be debounced");
+        assert!(!watcher.poll(now + DEBOUNCE / 2));
+        assert!(watcher.poll(now + DEBOUNCE));
+        assert!(!watcher.poll(now + DEBOUNCE * 2));
+    }
+
+    #[test]
+    fn create_modify_delete() {
+        let dir = TempDir::new();
+        dir.write("main.py", "print('hi')");
+        let mut watcher = ScriptWatcher::new(vec![whole(&dir)], Vec::new(), DEBOUNCE);
+        let now = Instant::now();
+        assert!(!watcher.poll(now));
+
+        let module = dir.write("game/module.py", "x = 1");
+        assert_reload(&mut watcher, now);
+
+        touch(&module, 10);
+        assert_reload(&mut watcher, now + DEBOUNCE * 3);
+
+        fs::remove_file(&module).unwrap();
+        assert_reload(&mut watcher, now + DEBOUNCE * 6);
+    }
+
+    #[test]
+    fn burst_is_collapsed_into_one_reload() {
+        let dir = TempDir::new();
+        let main = dir.write("main.py", "");
+        let mut watcher = ScriptWatcher::new(vec![whole(&dir)], Vec::new(), DEBOUNCE);
+        let start = Instant::now();
+        let mut reloads = 0;
+        for step in 0..5u32 {
+            touch(&main, step as u64 + 1);
+            let now = start + DEBOUNCE / 4 * step;
+            reloads += watcher.poll(now) as usize;
+        }
+        let last_change = start + DEBOUNCE / 4 * 4;
+        for step in 0..=8u32 {
+            reloads += watcher.poll(last_change + DEBOUNCE / 4 * step) as usize;
+        }
+        assert_eq!(reloads, 1);
+    }
+
+    #[test]
+    fn ignored_files_do_not_trigger_reload() {
+        let dir = TempDir::new();
+        dir.write("main.py", "");
+        let mut watcher = ScriptWatcher::new(vec![whole(&dir)], Vec::new(), DEBOUNCE);
+        let now = Instant::now();
+
+        dir.write("__pycache__/main.cpython-312.py", "");
+        dir.write(".venv/lib/site.py", "");
+        dir.write("env/pyvenv.cfg", "");
+        dir.write("env/lib/site.py", "");
+        dir.write("game/.hidden/module.py", "");
+        dir.write("game/config.toml", "");
+        dir.write("notes.txt", "");
+        dir.write("main.pyc", "");
+        dir.write("server.log", "");
+        assert!(!watcher.poll(now));
+        assert!(!watcher.poll(now + DEBOUNCE * 2));
+
+        dir.write("game/module.py", "");
+        assert_reload(&mut watcher, now + DEBOUNCE * 3);
+    }
+
+    #[test]
+    fn script_dir_only_watches_packages() {
+        let dir = TempDir::new();
+        dir.write("main.py", "");
+        dir.write("game/__init__.py", "");
+        let root = WatchRoot {
+            path: dir.0.clone(),
+            packages_only: true,
+        };
+        let mut watcher = ScriptWatcher::new(vec![root], Vec::new(), DEBOUNCE);
+        let now = Instant::now();
+
+        // 服务器目录下别的东西不管
+        dir.write("plugins/tool.py", "");
+        dir.write("store/script/main.py", "");
+        assert!(!watcher.poll(now));
+        assert!(!watcher.poll(now + DEBOUNCE * 2));
+
+        dir.write("game/jobs/__init__.py", "");
+        assert_reload(&mut watcher, now + DEBOUNCE * 3);
+
+        dir.write("admin.py", "");
+        assert_reload(&mut watcher, now + DEBOUNCE * 6);
+    }
+
+    #[test]
+    fn only_loaded_toml_files_are_watched() {
+        let dir = TempDir::new();
+        dir.write("main.py", "");
+        let classes = dir.write("classes.toml", "");
+        dir.write("python_cfg.toml", "");
+        let mut watcher = ScriptWatcher::new(vec![whole(&dir)], vec![classes.clone()], DEBOUNCE);
+        let now = Instant::now();
+
+        dir.write("python_cfg.toml", "[script]");
+        dir.write("presets/weapons.toml", "");
+        assert!(!watcher.poll(now));
+        assert!(!watcher.poll(now + DEBOUNCE * 2));
+
+        touch(&classes, 10);
+        assert_reload(&mut watcher, now + DEBOUNCE * 3);
+    }
+
+    #[test]
+    fn every_root_is_watched() {
+        let first = TempDir::new();
+        let second = TempDir::new();
+        first.write("main.py", "");
+        let mut watcher =
+            ScriptWatcher::new(vec![whole(&first), whole(&second)], Vec::new(), DEBOUNCE);
+        let now = Instant::now();
+        assert!(!watcher.poll(now));
+
+        second.write("admin.py", "");
+        assert_reload(&mut watcher, now);
+    }
+
+    #[test]
+    fn roots_follow_script_entries() {
+        let server = TempDir::new();
+        let other = TempDir::new();
+        let main = server.write("main.py", "");
+        let nested = server.write("scripts/admin.py", "");
+        other.write("game/__init__.py", "");
+        let package = other.0.join("game");
+
+        let entries = [
+            ScriptEntry::parse(&main.to_string_lossy()),
+            ScriptEntry::parse(&nested.to_string_lossy()),
+            ScriptEntry::parse(&package.to_string_lossy()),
+        ];
+        let scripts = WatchRoot {
+            path: server.0.join("scripts"),
+            packages_only: true,
+        };
+        let game = WatchRoot {
+            path: package.clone(),
+            packages_only: false,
+        };
+        let server_root = WatchRoot {
+            path: server.0.clone(),
+            packages_only: true,
+        };
+        assert_eq!(
+            watch_roots(&entries),
+            vec![server_root, scripts, game.clone()]
+        );
+
+        // 文件入口所在的目录只扫里面的包，包目录还是要单独整个扫
+        let entries = [
+            ScriptEntry::parse(&package.to_string_lossy()),
+            ScriptEntry::parse(&other.write("main.py", "").to_string_lossy()),
+            ScriptEntry::parse(&other.write("admin.py", "").to_string_lossy()),
+        ];
+        let other_root = WatchRoot {
+            path: other.0.clone(),
+            packages_only: true,
+        };
+        assert_eq!(watch_roots(&entries), vec![game, other_root]);
+    }
+}
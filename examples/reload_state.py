@@ -0,0 +1,26 @@
+from vcmp import reload_state
+from vcmp.callback import callbacks
+from vcmp.events.player import PlayerDeathEvent
+
+# 重载之后模块会重新执行，这里的值会被清空
+kills: dict[str, int] = {}
+
+
+@callbacks.on_player_death()
+def _(event: PlayerDeathEvent):
+    if event.killer is None:
+        return
+    kills[event.killer.name] = kills.get(event.killer.name, 0) + 1
+
+
+@reload_state.on_before_reload(version=2)
+def _():
+    return {"kills": kills}
+
+
+@reload_state.on_after_reload
+def _(state: dict, version: int):
+    if version < 2:
+        # 旧版本直接存的就是 kills
+        state = {"kills": state}
+    kills.update(state["kills"])
@@ -0,0 +1,17 @@
+include("stream.nut")
+
+function Server::ServerData(data) {
+    local id = data.ReadInt()
+    if (id == 1) {
+        local text = data.ReadString() // sq_string
+        local count = data.ReadInt() // int
+        local pos = Vector(data.ReadFloat(), data.ReadFloat(), data.ReadFloat()) // vector
+        ::Console.Print("Greeting: " + text + ", " + count)
+
+        local reply = ::Stream()
+        reply.WriteInt(2) // message id
+        reply.WriteString("got it") // sq_string
+        reply.PyWriteBoolean(true) // bool
+        Server.SendData(reply)
+    }
+}
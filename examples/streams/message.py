@@ -0,0 +1,15 @@
+from vcmp.functions.player import Player
+from vcmp.streams import MessageSchema
+
+# 客户端对应 message.nut
+Greeting = MessageSchema(1, [("text", "sq_string"), ("count", "int"), ("pos", "vector")])
+Report = MessageSchema(2, [("text", "sq_string"), ("ok", "bool")])
+
+
+def send_greeting(player: Player):
+    Greeting.send(player, text="Hello World!", count=123, pos=player.position)
+
+
+@Report.handler()
+def on_report(player: Player, data: dict):
+    print(f"{player.name} reported: {data['text']} ({data['ok']})")
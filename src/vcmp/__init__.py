@@ -5,6 +5,7 @@ from . import functions
 from . import callbacks
 from . import events
 from . import instance
+from . import reload_state
 
 
 def reload(**kwargs):
@@ -16,11 +17,34 @@ def reload(**kwargs):
     pass
 
 
+def reload_entry(entry: str, **kwargs):
+    """
+    Reloads one script entry.
+
+    Only the callbacks of this entry receive the disconnect / shutdown / init events,
+    and only the modules imported while loading this entry are unloaded.
+    Other entries keep their modules, callbacks and reload state.
+
+    Parameters
+    ----------
+    entry : str
+        The entry as written in `script_path` / `scripts`, or its index.
+
+    Raises
+    ------
+    ValueError
+        If the entry is not configured.
+    """
+    pass
+
+
 def set_error_handler(handler: Optional[Callable[[BaseException], Any]] = None):
     """
     Sets the error handler.
 
-    The error handler is called when an error occurs in the module.
+    The error handler is called when an error occurs in a callback.
+    Each script entry has its own handler; an entry without one falls back
+    to the handler set outside of any entry.
 
     Parameters
     ----------
@@ -32,7 +56,7 @@ def set_error_handler(handler: Optional[Callable[[BaseException], Any]] = None):
 
 def get_error_handler() -> Optional[Callable[[BaseException], Any]]:
     """
-    Gets the error handler.
+    Gets the error handler of the current script entry.
 
     Returns
     -------
@@ -50,6 +74,178 @@ __all__ = [
     "events",
     "instance",
     "reload",
+    "reload_entry",
     "set_error_handler",
     "get_error_handler",
 ]
+
+
+class Settings:
+    """
+    服务器和环境设置
+
+    通过这个对象修改设置会触发 SettingChangedEvent
+    """
+
+    @property
+    def server_name(self) -> str: ...
+    @server_name.setter
+    def server_name(self, value: str) -> None: ...
+    @property
+    def password(self) -> str: ...
+    @password.setter
+    def password(self, value: str) -> None: ...
+    @property
+    def gamemode(self) -> str: ...
+    @gamemode.setter
+    def gamemode(self, value: str) -> None: ...
+    @property
+    def max_players(self) -> int: ...
+    @max_players.setter
+    def max_players(self, value: int) -> None: ...
+    @property
+    def port(self) -> int: ...
+    @property
+    def flags(self) -> int: ...
+    @property
+    def hour(self) -> int: ...
+    @hour.setter
+    def hour(self, value: int) -> None: ...
+    @property
+    def minute(self) -> int: ...
+    @minute.setter
+    def minute(self, value: int) -> None: ...
+    @property
+    def time(self) -> int: ...
+    @time.setter
+    def time(self, value: int) -> None: ...
+    @property
+    def time_rate(self) -> int: ...
+    @time_rate.setter
+    def time_rate(self, value: int) -> None: ...
+    @property
+    def weather(self) -> int: ...
+    @weather.setter
+    def weather(self, value: int) -> None: ...
+    @property
+    def gravity(self) -> float: ...
+    @gravity.setter
+    def gravity(self, value: float) -> None: ...
+    @property
+    def gamespeed(self) -> float: ...
+    @gamespeed.setter
+    def gamespeed(self, value: float) -> None: ...
+    @property
+    def water_level(self) -> float: ...
+    @water_level.setter
+    def water_level(self, value: float) -> None: ...
+    @property
+    def max_flight_altitude(self) -> float: ...
+    @max_flight_altitude.setter
+    def max_flight_altitude(self, value: float) -> None: ...
+    @property
+    def kill_command_delay(self) -> int: ...
+    @kill_command_delay.setter
+    def kill_command_delay(self, value: int) -> None: ...
+    @property
+    def vehicles_forced_respawn_height(self) -> float: ...
+    @vehicles_forced_respawn_height.setter
+    def vehicles_forced_respawn_height(self, value: float) -> None: ...
+    @property
+    def fall_timer(self) -> int: ...
+    @fall_timer.setter
+    def fall_timer(self, value: int) -> None: ...
+    @property
+    def taxi_boost_jump(self) -> bool: ...
+    @taxi_boost_jump.setter
+    def taxi_boost_jump(self, value: bool) -> None: ...
+    @property
+    def drive_on_water(self) -> bool: ...
+    @drive_on_water.setter
+    def drive_on_water(self, value: bool) -> None: ...
+    @property
+    def fast_switch(self) -> bool: ...
+    @fast_switch.setter
+    def fast_switch(self, value: bool) -> None: ...
+    @property
+    def friendly_fire(self) -> bool: ...
+    @friendly_fire.setter
+    def friendly_fire(self, value: bool) -> None: ...
+    @property
+    def disable_drive_by(self) -> bool: ...
+    @disable_drive_by.setter
+    def disable_drive_by(self, value: bool) -> None: ...
+    @property
+    def perfect_handling(self) -> bool: ...
+    @perfect_handling.setter
+    def perfect_handling(self, value: bool) -> None: ...
+    @property
+    def flying_cars(self) -> bool: ...
+    @flying_cars.setter
+    def flying_cars(self, value: bool) -> None: ...
+    @property
+    def jump_switch(self) -> bool: ...
+    @jump_switch.setter
+    def jump_switch(self, value: bool) -> None: ...
+    @property
+    def show_markers(self) -> bool: ...
+    @show_markers.setter
+    def show_markers(self, value: bool) -> None: ...
+    @property
+    def only_show_team_markers(self) -> bool: ...
+    @only_show_team_markers.setter
+    def only_show_team_markers(self, value: bool) -> None: ...
+    @property
+    def stunt_bike(self) -> bool: ...
+    @stunt_bike.setter
+    def stunt_bike(self, value: bool) -> None: ...
+    @property
+    def shoot_in_air(self) -> bool: ...
+    @shoot_in_air.setter
+    def shoot_in_air(self, value: bool) -> None: ...
+    @property
+    def show_name_tags(self) -> bool: ...
+    @show_name_tags.setter
+    def show_name_tags(self, value: bool) -> None: ...
+    @property
+    def join_messages(self) -> bool: ...
+    @join_messages.setter
+    def join_messages(self, value: bool) -> None: ...
+    @property
+    def death_messages(self) -> bool: ...
+    @death_messages.setter
+    def death_messages(self, value: bool) -> None: ...
+    @property
+    def chat_tags_enabled(self) -> bool: ...
+    @chat_tags_enabled.setter
+    def chat_tags_enabled(self, value: bool) -> None: ...
+    @property
+    def use_classes(self) -> bool: ...
+    @use_classes.setter
+    def use_classes(self, value: bool) -> None: ...
+    @property
+    def wall_glitch(self) -> bool: ...
+    @wall_glitch.setter
+    def wall_glitch(self, value: bool) -> None: ...
+    @property
+    def disable_backface_culling(self) -> bool: ...
+    @disable_backface_culling.setter
+    def disable_backface_culling(self, value: bool) -> None: ...
+    @property
+    def disable_heli_blade_damage(self) -> bool: ...
+    @disable_heli_blade_damage.setter
+    def disable_heli_blade_damage(self, value: bool) -> None: ...
+    def keys(self) -> list[str]: ...
+    def is_read_only(self, name: str) -> bool: ...
+    def snapshot(self, include_secrets: bool = False) -> dict[str, Any]:
+        """当前所有设置（包括只读的），password 要传 include_secrets=True 才会导出"""
+        ...
+    def restore(self, snapshot: dict[str, Any]) -> None:
+        """还原 snapshot 的结果，只读的设置会被跳过
+
+        先检查所有的名字和值，有一个不对就抛异常，一个都不改
+        """
+        ...
+
+
+settings: Settings
@@ -42,6 +42,18 @@ class CallbackManager:
         func: Optional[EVENT_FUNCTION] = None,
         tag: Optional[str] = None,
     ) -> EVENT_FUNCTION: ...
+    def on_server_reload_failed(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_setting_changed(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
     def on_incoming_connection(
         self,
         priority: int = DEFAULT_PRIORITY,
@@ -54,6 +66,12 @@ class CallbackManager:
         func: Optional[EVENT_FUNCTION] = None,
         tag: Optional[str] = None,
     ) -> EVENT_FUNCTION: ...
+    def on_client_chunked_data(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
     def on_player_connect(
         self,
         priority: int = DEFAULT_PRIORITY,
@@ -246,6 +264,48 @@ class CallbackManager:
         func: Optional[EVENT_FUNCTION] = None,
         tag: Optional[str] = None,
     ) -> EVENT_FUNCTION: ...
+    def on_player_skin_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_team_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_score_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_world_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_cash_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_wanted_level_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_player_damage(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
     def on_pickup_pick_attempt(
         self,
         priority: int = DEFAULT_PRIORITY,
@@ -288,6 +348,12 @@ class CallbackManager:
         func: Optional[EVENT_FUNCTION] = None,
         tag: Optional[str] = None,
     ) -> EVENT_FUNCTION: ...
+    def on_object_animation_finished(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
     def on_vehicle_explode(
         self,
         priority: int = DEFAULT_PRIORITY,
@@ -318,6 +384,48 @@ class CallbackManager:
         func: Optional[EVENT_FUNCTION] = None,
         tag: Optional[str] = None,
     ) -> EVENT_FUNCTION: ...
+    def on_vehicle_damage_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_tyre_pop(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_part_status_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_lights_data_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_siren_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_doors_locked_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
+    def on_vehicle_handling_change(
+        self,
+        priority: int = DEFAULT_PRIORITY,
+        func: Optional[EVENT_FUNCTION] = None,
+        tag: Optional[str] = None,
+    ) -> EVENT_FUNCTION: ...
     def on_custom(
         self,
         priority: int = DEFAULT_PRIORITY,
@@ -330,6 +438,16 @@ class CallbackManager:
         tag: Optional[str] = None,
     ) -> list[EVENT_FUNCTION]: ...
     def remove_callback(self, func: EVENT_FUNCTION) -> None: ...
+    def set_filter(
+        self, func: Optional[Callable[[Any], bool]]
+    ) -> Optional[Callable[[Any], bool]]:
+        """
+        Set an event filter for the script entry that is currently running.
+
+        Events the filter returns False for never reach this entry's callbacks.
+        Pass None to remove it. Can be used as a decorator.
+        """
+        ...
 
 
 class CallbackFunction:
@@ -32,6 +32,10 @@ class EventBuilder:
     ) -> "EventBuilder": ...
     @staticmethod
     def server_reloaded(elapsed_time: float) -> "EventBuilder": ...
+    @staticmethod
+    def server_reload_failed(traceback: str) -> "EventBuilder": ...
+    @staticmethod
+    def setting_changed(name: str, old_value: Any, new_value: Any) -> "EventBuilder": ...
 
     # Checkpoint events
     @staticmethod
@@ -51,6 +55,10 @@ class EventBuilder:
     ) -> "EventBuilder": ...
     @staticmethod
     def object_touched(object: Object, player: Player) -> "EventBuilder": ...  # noqa: F811
+    @staticmethod
+    def object_animation_finished(
+        object: Object, tag: Optional[str]
+    ) -> "EventBuilder": ...  # noqa: F811
 
     # Pickup events
     @staticmethod
@@ -68,6 +76,10 @@ class EventBuilder:
     @staticmethod
     def client_script_data(player: Player, data: bytes) -> "EventBuilder": ...  # noqa: F811
     @staticmethod
+    def client_chunked_data(
+        player: Player, transfer_id: int, data: bytes
+    ) -> "EventBuilder": ...  # noqa: F811
+    @staticmethod
     def player_connect(player: Player) -> "EventBuilder": ...  # noqa: F811
     @staticmethod
     def player_disconnect(player: Player, reason: int) -> "EventBuilder": ...  # noqa: F811
@@ -186,6 +198,49 @@ class EventBuilder:
         old_position: Vector,
         new_position: Vector,  # noqa: F811
     ) -> "EventBuilder": ...
+    @staticmethod
+    def player_skin_change(
+        player: Player,
+        old_skin: int,
+        new_skin: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_team_change(
+        player: Player,
+        old_team: int,
+        new_team: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_score_change(
+        player: Player,
+        old_score: int,
+        new_score: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_world_change(
+        player: Player,
+        old_world: int,
+        new_world: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_cash_change(
+        player: Player,
+        old_cash: int,
+        new_cash: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_wanted_level_change(
+        player: Player,
+        old_wanted_level: int,
+        new_wanted_level: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def player_damage(
+        player: Player,
+        attacker: Optional[Player],
+        weapon: int,
+        amount: float,  # noqa: F811
+    ) -> "EventBuilder": ...
 
     # Vehicle events
     @staticmethod
@@ -209,6 +264,55 @@ class EventBuilder:
         new_health: float,  # noqa: F811
     ) -> "EventBuilder": ...
     @staticmethod
+    def vehicle_damage_change(
+        vehicle: Vehicle,
+        old_damage: int,
+        new_damage: int,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_tyre_pop(
+        vehicle: Vehicle,
+        tyre: int,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_part_status_change(
+        vehicle: Vehicle,
+        part: int,
+        old_status: bool,
+        new_status: bool,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_lights_data_change(
+        vehicle: Vehicle,
+        old_lights_data: int,
+        new_lights_data: int,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_siren_change(
+        vehicle: Vehicle,
+        old_siren: bool,
+        new_siren: bool,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_doors_locked_change(
+        vehicle: Vehicle,
+        old_doors_locked: bool,
+        new_doors_locked: bool,
+        update_type: int,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
+    def vehicle_handling_change(
+        vehicle: Vehicle,
+        rule_index: int,
+        old_value: float,
+        new_value: float,  # noqa: F811
+    ) -> "EventBuilder": ...
+    @staticmethod
     def custom(**kwargs) -> "EventBuilder": ...  # noqa: F811
     @staticmethod
     def traceback(traceback: TracebackException) -> "EventBuilder": ...
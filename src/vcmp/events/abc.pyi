@@ -1,7 +1,12 @@
 from typing import Any
 
 class Event:
-    ...
+    """所有事件的基类
+
+    事件里的实体（`event.player`、`event.vehicle` 等）是取的时候才从实体池里拿的，
+    实体已经没了（比如玩家已经断开、车辆已经删除）会抛 `ValueError`，
+    不会再返回一个只有 id 的空实体。
+    """
 
     @property
     def kwargs(self) -> dict[str, Any]: ...
@@ -1,3 +1,5 @@
+from typing import Optional
+
 from .abc import Event
 
 from vcmp.functions.object import Object
@@ -23,3 +25,11 @@ class ObjectTouchedEvent(ObjectEvent):
     @property
     def player(self) -> Player: ...
     def __repr__(self) -> str: ...
+
+
+class ObjectAnimationFinishedEvent(ObjectEvent):
+    @property
+    def object(self) -> Object: ...
+    @property
+    def tag(self) -> Optional[str]: ...
+    def __repr__(self) -> str: ...
@@ -1,7 +1,10 @@
+from typing import Optional
+
 from .abc import Event
 
 from vcmp.functions.player import Player
 from vcmp.functions.vehicle import Vehicle
+from vcmp.functions.environment import PlayerClass
 from vcmp.functions.keybind import KeyBind
 from vcmp.streams import ReadStream
 from vcmp.types import Vector
@@ -47,6 +50,30 @@ class ClientScriptDataEvent(PlayerEvent):
         """返回事件的字符串表示"""
         ...
 
+class ClientChunkedDataEvent(PlayerEvent):
+    """分片传输的数据全部收到之后触发"""
+
+    @property
+    def player(self) -> Player:
+        """获取相关玩家对象"""
+        ...
+
+    @property
+    def transfer_id(self) -> int: ...
+    @property
+    def data(self) -> bytes:
+        """拼好的完整数据"""
+        ...
+
+    @property
+    def stream(self) -> ReadStream:
+        """用完整数据创建的数据流对象"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
 class PlayerConnectEvent(PlayerEvent):
     """玩家连接事件"""
 
@@ -89,6 +116,11 @@ class PlayerRequestClassEvent(PlayerEvent):
         """获取请求的角色 ID"""
         ...
 
+    @property
+    def player_class(self) -> Optional[PlayerClass]:
+        """获取请求的角色，不是通过脚本添加的则为 None"""
+        ...
+
     def __repr__(self) -> str:
         """返回事件的字符串表示"""
         ...
@@ -132,12 +164,21 @@ class PlayerDeathEvent(PlayerEvent):
 
     @property
     def reason(self) -> int:
-        """获取死亡原因"""
+        """获取死亡原因（武器 id），是 int，要枚举用 `Weapon.from_id`"""
         ...
 
     @property
     def body(self) -> int:
-        """获取死亡部位信息"""
+        """获取死亡部位信息，是 int，要枚举用 `BodyPart.from_id`"""
+        ...
+
+    @property
+    def damage_ledger(self) -> list["DamageRecord"]:
+        """获取这条命受到的所有伤害记录"""
+        ...
+
+    def get_assists(self, min_damage: float = 0.0) -> list[Player]:
+        """获取助攻者，按伤害总量从高到低排列，不包括杀手"""
         ...
 
     def __repr__(self) -> str:
@@ -695,3 +736,271 @@ class PlayerMoveEvent(PlayerEvent):
     def __repr__(self) -> str:
         """返回事件的字符串表示"""
         ...
+
+class PlayerSkinChangeEvent(PlayerEvent):
+    """玩家皮肤变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取皮肤变更的玩家对象"""
+        ...
+
+    @property
+    def old_skin(self) -> int:
+        """获取旧皮肤"""
+        ...
+
+    @property
+    def new_skin(self) -> int:
+        """获取新皮肤"""
+        ...
+
+    @property
+    def current_skin(self) -> int:
+        """获取当前皮肤"""
+        ...
+
+    @current_skin.setter
+    def current_skin(self, value: int) -> None:
+        """设置当前皮肤"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class PlayerTeamChangeEvent(PlayerEvent):
+    """玩家队伍变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取队伍变更的玩家对象"""
+        ...
+
+    @property
+    def old_team(self) -> int:
+        """获取旧队伍"""
+        ...
+
+    @property
+    def new_team(self) -> int:
+        """获取新队伍"""
+        ...
+
+    @property
+    def current_team(self) -> int:
+        """获取当前队伍"""
+        ...
+
+    @current_team.setter
+    def current_team(self, value: int) -> None:
+        """设置当前队伍"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class PlayerScoreChangeEvent(PlayerEvent):
+    """玩家分数变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取分数变更的玩家对象"""
+        ...
+
+    @property
+    def old_score(self) -> int:
+        """获取旧分数"""
+        ...
+
+    @property
+    def new_score(self) -> int:
+        """获取新分数"""
+        ...
+
+    @property
+    def current_score(self) -> int:
+        """获取当前分数"""
+        ...
+
+    @current_score.setter
+    def current_score(self, value: int) -> None:
+        """设置当前分数"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class PlayerWorldChangeEvent(PlayerEvent):
+    """玩家世界变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取世界变更的玩家对象"""
+        ...
+
+    @property
+    def old_world(self) -> int:
+        """获取旧世界"""
+        ...
+
+    @property
+    def new_world(self) -> int:
+        """获取新世界"""
+        ...
+
+    @property
+    def current_world(self) -> int:
+        """获取当前世界"""
+        ...
+
+    @current_world.setter
+    def current_world(self, value: int) -> None:
+        """设置当前世界"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class PlayerCashChangeEvent(PlayerEvent):
+    """玩家金钱变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取金钱变更的玩家对象"""
+        ...
+
+    @property
+    def old_cash(self) -> int:
+        """获取旧金钱"""
+        ...
+
+    @property
+    def new_cash(self) -> int:
+        """获取新金钱"""
+        ...
+
+    @property
+    def current_cash(self) -> int:
+        """获取当前金钱"""
+        ...
+
+    @current_cash.setter
+    def current_cash(self, value: int) -> None:
+        """设置当前金钱"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class PlayerWantedLevelChangeEvent(PlayerEvent):
+    """玩家通缉等级变更事件
+
+    只有注册了这个事件的回调才会追踪
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取通缉等级变更的玩家对象"""
+        ...
+
+    @property
+    def old_wanted_level(self) -> int:
+        """获取旧通缉等级"""
+        ...
+
+    @property
+    def new_wanted_level(self) -> int:
+        """获取新通缉等级"""
+        ...
+
+    @property
+    def current_wanted_level(self) -> int:
+        """获取当前通缉等级"""
+        ...
+
+    @current_wanted_level.setter
+    def current_wanted_level(self, value: int) -> None:
+        """设置当前通缉等级"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
+
+class DamageRecord:
+    """伤害记录"""
+
+    @property
+    def attacker(self) -> Player | None:
+        """获取推测出来的攻击者（可能为 None）"""
+        ...
+
+    @property
+    def weapon(self) -> int:
+        """获取武器 id，无法推测时为 -1"""
+        ...
+
+    @property
+    def amount(self) -> float:
+        """获取伤害量"""
+        ...
+
+    @property
+    def time(self) -> float:
+        """获取受到伤害的 unix 时间戳"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回记录的字符串表示"""
+        ...
+
+class PlayerDamageEvent(PlayerEvent):
+    """玩家受到伤害事件
+
+    根据附近的开枪、车辆和近战推测攻击者，结果不一定准确
+    空手只有刚出过拳（PlayerAction.Shooting）才算近战
+    """
+
+    @property
+    def player(self) -> Player:
+        """获取受到伤害的玩家对象"""
+        ...
+
+    @property
+    def attacker(self) -> Player | None:
+        """获取推测出来的攻击者（可能为 None）"""
+        ...
+
+    @property
+    def weapon(self) -> int:
+        """获取武器 id，无法推测时为 -1"""
+        ...
+
+    @property
+    def amount(self) -> float:
+        """获取伤害量"""
+        ...
+
+    def __repr__(self) -> str:
+        """返回事件的字符串表示"""
+        ...
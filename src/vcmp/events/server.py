@@ -1,3 +1,5 @@
+from typing import Any
+
 from .abc import Event
 
 
@@ -27,3 +29,29 @@ class ServerPerformanceReportEvent(ServerEvent):
 class ServerReloadedEvent(ServerEvent):
     @property
     def elapsed_time(self) -> float: ...
+
+
+class ServerReloadFailedEvent(ServerEvent):
+    """重载失败时触发，旧脚本继续运行
+
+    重载前检查失败的话旧脚本什么都没收到；
+    检查通过但加载新脚本失败的话，旧脚本的模块和回调会换回来，
+    这时旧脚本已经收到过每个玩家的 PlayerDisconnect 和 ServerShutdown，
+    会跟重载成功一样再收到 server init 和玩家加入；
+    收起旧脚本的时候就失败的话，旧脚本同样已经收到过 disconnect / shutdown，
+    但不会再收到 server init 和玩家加入
+    """
+
+    @property
+    def traceback(self) -> str: ...
+
+
+class SettingChangedEvent(ServerEvent):
+    """通过 vcmp.settings 修改设置时触发"""
+
+    @property
+    def name(self) -> str: ...
+    @property
+    def old_value(self) -> Any: ...
+    @property
+    def new_value(self) -> Any: ...
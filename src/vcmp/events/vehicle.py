@@ -108,3 +108,228 @@ class VehicleHealthChangeEvent(VehicleEvent):
         ...
 
     def __repr__(self) -> str: ...
+
+
+class VehicleDamageChangeEvent(VehicleEvent):
+    """车辆损坏数据变化事件"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def old_damage(self) -> int:
+        """获取变化前的损坏数据"""
+        ...
+
+    @property
+    def new_damage(self) -> int:
+        """获取变化后的损坏数据"""
+        ...
+
+    @property
+    def current_damage(self) -> int:
+        """获取当前损坏数据（可修改）"""
+        ...
+
+    @current_damage.setter
+    def current_damage(self, damage: int) -> None:
+        """设置当前损坏数据"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehicleTyrePopEvent(VehicleEvent):
+    """车辆爆胎事件
+
+    拦截后会把轮胎恢复
+    """
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def tyre(self) -> int:
+        """获取爆掉的轮胎编号"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehiclePartStatusChangeEvent(VehicleEvent):
+    """车辆部件状态变化事件"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def part(self) -> int:
+        """获取部件编号"""
+        ...
+
+    @property
+    def old_status(self) -> bool:
+        """获取变化前的部件状态"""
+        ...
+
+    @property
+    def new_status(self) -> bool:
+        """获取变化后的部件状态"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehicleLightsDataChangeEvent(VehicleEvent):
+    """车辆灯光数据变化事件"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def old_lights_data(self) -> int:
+        """获取变化前的灯光数据"""
+        ...
+
+    @property
+    def new_lights_data(self) -> int:
+        """获取变化后的灯光数据"""
+        ...
+
+    @property
+    def current_lights_data(self) -> int:
+        """获取当前灯光数据（可修改）"""
+        ...
+
+    @current_lights_data.setter
+    def current_lights_data(self, lights_data: int) -> None:
+        """设置当前灯光数据"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehicleSirenChangeEvent(VehicleEvent):
+    """车辆警笛开关事件"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def old_siren(self) -> bool:
+        """获取变化前的警笛状态"""
+        ...
+
+    @property
+    def new_siren(self) -> bool:
+        """获取变化后的警笛状态"""
+        ...
+
+    @property
+    def current_siren(self) -> bool:
+        """获取当前警笛状态（可修改），拒绝事件的话恢复成变化前的"""
+        ...
+
+    @current_siren.setter
+    def current_siren(self, siren: bool) -> None:
+        """设置当前警笛状态"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehicleDoorsLockedChangeEvent(VehicleEvent):
+    """车辆车门锁定变化事件"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def old_doors_locked(self) -> bool:
+        """获取变化前的车门锁定状态"""
+        ...
+
+    @property
+    def new_doors_locked(self) -> bool:
+        """获取变化后的车门锁定状态"""
+        ...
+
+    @property
+    def current_doors_locked(self) -> bool:
+        """获取当前车门锁定状态（可修改），拒绝事件的话恢复成变化前的"""
+        ...
+
+    @current_doors_locked.setter
+    def current_doors_locked(self, doors_locked: bool) -> None:
+        """设置当前车门锁定状态"""
+        ...
+
+    @property
+    def update_type(self) -> int:
+        """获取更新类型（整数标识）"""
+        ...
+
+    def __repr__(self) -> str: ...
+
+
+class VehicleHandlingChangeEvent(VehicleEvent):
+    """车辆操控规则变化事件，由 set_handling_rule / reset_handling_rule / reset_handling 触发"""
+
+    @property
+    def vehicle(self) -> Vehicle:
+        """获取事件关联的车辆"""
+        ...
+
+    @property
+    def rule_index(self) -> int:
+        """获取变化的操控规则编号"""
+        ...
+
+    @property
+    def old_value(self) -> float:
+        """获取变化前的值"""
+        ...
+
+    @property
+    def new_value(self) -> float:
+        """获取变化后的值"""
+        ...
+
+    def __repr__(self) -> str: ...
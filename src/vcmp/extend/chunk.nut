@@ -0,0 +1,99 @@
+// 分片传输，和 python 的 vcmp.streams.send_chunked / Player.send_chunked_data 配套
+//
+// 每一片: int magic, int transfer id, int 序号, int 总数, int 长度, byte * 长度
+//
+// 用法:
+// function Server::ServerData(data) {
+//     PyReceiveChunk(data, function(transferId, payload) {
+//         // payload 是拼好的字符串
+//     }, function(id, data) {
+//         // 不是分片，id 是已经读掉的第一个 int
+//     })
+// }
+
+const PY_CHUNK_MAGIC = 0x43484E4B;
+const PY_CHUNK_SIZE = 4000;
+// 毫秒
+const PY_CHUNK_TIMEOUT = 30000;
+
+PyChunkTransfers <- {};
+PyChunkNextId <- 0;
+
+function PyDropStaleChunks() {
+    local now = Script.GetTicks()
+    local stale = []
+    foreach (id, transfer in PyChunkTransfers) {
+        if (now - transfer.updated > PY_CHUNK_TIMEOUT) {
+            stale.push(id)
+        }
+    }
+    foreach (id in stale) {
+        delete PyChunkTransfers[id]
+    }
+}
+
+function PyReceiveChunk(data, onComplete, onOther = null) {
+    local first = data.ReadInt()
+    if (first != PY_CHUNK_MAGIC) {
+        if (onOther != null) {
+            onOther(first, data)
+        }
+        return false
+    }
+    PyDropStaleChunks()
+
+    local transferId = data.ReadInt()
+    local index = data.ReadInt()
+    local count = data.ReadInt()
+    local length = data.ReadInt()
+    local part = ""
+    for (local i = 0; i < length; i++) {
+        part += data.ReadByte().tochar()
+    }
+
+    if (!(transferId in PyChunkTransfers)) {
+        local parts = array(count, null)
+        PyChunkTransfers[transferId] <- { parts = parts, received = 0, updated = 0 }
+    }
+    local transfer = PyChunkTransfers[transferId]
+    transfer.updated = Script.GetTicks()
+    if (transfer.parts[index] == null) {
+        transfer.parts[index] = part
+        transfer.received++
+    }
+    if (transfer.received == count) {
+        delete PyChunkTransfers[transferId]
+        local payload = ""
+        foreach (p in transfer.parts) {
+            payload += p
+        }
+        onComplete(transferId, payload)
+    }
+    return true
+}
+
+function PySendChunked(payload, chunkSize = PY_CHUNK_SIZE) {
+    local transferId = PyChunkNextId++
+    local count = (payload.len() + chunkSize - 1) / chunkSize
+    if (count == 0) {
+        count = 1
+    }
+    for (local index = 0; index < count; index++) {
+        local start = index * chunkSize
+        local end = start + chunkSize
+        if (end > payload.len()) {
+            end = payload.len()
+        }
+        local stream = ::Stream()
+        stream.WriteInt(PY_CHUNK_MAGIC)
+        stream.WriteInt(transferId)
+        stream.WriteInt(index)
+        stream.WriteInt(count)
+        stream.WriteInt(end - start)
+        for (local i = start; i < end; i++) {
+            stream.WriteByte(payload[i])
+        }
+        Server.SendData(stream)
+    }
+    return transferId
+}
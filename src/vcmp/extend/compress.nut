@@ -0,0 +1,50 @@
+// 解压 python 那边 send_data(stream, compress=True) 发过来的数据
+//
+// 头部: int magic, byte 算法, int 原始长度，之后是 LZSS 数据
+// 每 8 个 token 前面一个标记字节，低位在前，0 是原样字节，
+// 1 是两个字节的回溯引用：距离 - 1 占高 12 位，长度 - 3 占低 4 位
+//
+// 用法:
+// function Server::ServerData(data) {
+//     local id = data.ReadInt()
+//     if (id == PY_COMPRESS_MAGIC) {
+//         local payload = PyDecompress(data) // 解压后的字符串
+//         return
+//     }
+//     // 没压缩的数据，id 是已经读掉的第一个 int
+// }
+
+const PY_COMPRESS_MAGIC = 0x435A4C31;
+const PY_COMPRESS_LZSS = 1;
+
+// magic 已经读掉了，返回解压后的字符串，算法不认识的话返回 null
+function PyDecompress(data) {
+    local algorithm = data.ReadByte() & 0xFF
+    local size = data.ReadInt()
+    if (algorithm != PY_COMPRESS_LZSS) {
+        return null
+    }
+    local out = array(size, 0)
+    local pos = 0
+    while (pos < size) {
+        local flags = data.ReadByte() & 0xFF
+        for (local bit = 0; bit < 8 && pos < size; bit++) {
+            if ((flags & (1 << bit)) == 0) {
+                out[pos++] = data.ReadByte() & 0xFF
+                continue
+            }
+            local value = ((data.ReadByte() & 0xFF) << 8) | (data.ReadByte() & 0xFF)
+            local distance = (value >> 4) + 1
+            local length = (value & 0xF) + 3
+            for (local i = 0; i < length && pos < size; i++) {
+                out[pos] = out[pos - distance]
+                pos++
+            }
+        }
+    }
+    local result = ""
+    foreach (b in out) {
+        result += b.tochar()
+    }
+    return result
+}
@@ -1,5 +1,7 @@
+from . import animation
 from . import checkpoint
 from . import environment
+from . import handling
 from . import keybind
 from . import marker
 from . import misc
@@ -8,10 +10,13 @@ from . import pickup
 from . import player
 from . import server
 from . import vehicle
+from . import weapon
 
 __all__ = [
+    "animation",
     "checkpoint",
     "environment",
+    "handling",
     "keybind",
     "marker",
     "misc",
@@ -20,4 +25,5 @@ __all__ = [
     "player",
     "server",
     "vehicle",
+    "weapon",
 ]
@@ -0,0 +1,64 @@
+from typing import Optional
+from vcmp.types import Quaternion, Vector
+
+
+class Easing:
+    Linear = ...
+    QuadIn = ...
+    QuadOut = ...
+    QuadInOut = ...
+    CubicIn = ...
+    CubicOut = ...
+    CubicInOut = ...
+    SineIn = ...
+    SineOut = ...
+    SineInOut = ...
+    BackOut = ...
+    BounceOut = ...
+    ElasticOut = ...
+    def apply(self, t: float) -> float: ...
+
+
+class ObjectAnimation:
+    def __init__(
+        self, loops: int = 1, ping_pong: bool = False, tag: Optional[str] = None
+    ) -> None:
+        """
+        loops 为 0 表示无限循环，ping_pong 播完之后倒着播回去
+        """
+        ...
+    @property
+    def loops(self) -> int: ...
+    @property
+    def ping_pong(self) -> bool: ...
+    @property
+    def tag(self) -> Optional[str]: ...
+    @property
+    def duration(self) -> int: ...
+    def __len__(self) -> int: ...
+    def move_to(
+        self, position: Vector, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def move_by(
+        self, offset: Vector, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def rotate_to(
+        self, rotation: Quaternion, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def rotate_by(
+        self, rotation: Quaternion, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def rotate_to_euler(
+        self, euler: Vector, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def rotate_by_euler(
+        self, euler: Vector, duration: int, easing: Easing = Easing.Linear
+    ) -> "ObjectAnimation": ...
+    def keyframe(
+        self,
+        duration: int,
+        position: Optional[Vector] = None,
+        rotation: Optional[Quaternion] = None,
+        easing: Easing = Easing.Linear,
+    ) -> "ObjectAnimation": ...
+    def wait(self, duration: int) -> "ObjectAnimation": ...
@@ -66,6 +66,28 @@ def disable_kill_command() -> None: ...
 def enable_kill_command() -> None: ...
 def get_vehicles_forced_respawn_height() -> float: ...
 def set_vehicles_forced_respawn_height(height: float) -> None: ...
+class PlayerClass:
+    """出生职业"""
+
+    @property
+    def id(self) -> int: ...
+    @property
+    def team(self) -> int: ...
+    @property
+    def color(self) -> RGB: ...
+    @property
+    def skin(self) -> int: ...
+    @property
+    def position(self) -> Vector: ...
+    @property
+    def angle(self) -> float: ...
+    @property
+    def weapons(self) -> list[tuple[int, int]]:
+        """(武器, 弹药) 列表，没给武器的槽位不在里面"""
+        ...
+    @property
+    def name(self) -> Optional[str]: ...
+
 def add_player_class(
     team: int,
     color: RGB,
@@ -78,7 +100,22 @@ def add_player_class(
     ammo1: Optional[int] = None,
     weapon2: Optional[int] = None,
     ammo2: Optional[int] = None,
-) -> None: ...
+    name: Optional[str] = None,
+) -> PlayerClass:
+    """每次调用都会添加一个新的 class，返回注册好的 class（id 是服务器给的）
+
+    没给的武器槽位不会传给服务器，ammo 不给的话是 0
+    """
+    ...
+def get_player_class(class_id: int) -> Optional[PlayerClass]: ...
+def get_player_classes() -> list[PlayerClass]: ...
+def get_team_classes(team: int) -> list[PlayerClass]: ...
+def load_player_classes(path: str, dedup: bool = False) -> list[PlayerClass]:
+    """从 toml 文件加载 class，返回加载到的 class
+
+    重载的时候会再调用一次的话传 dedup=True，一模一样的 class 复用原来的 id，不会越加越多
+    """
+    ...
 def set_spawn_player_position(position: Vector) -> None: ...
 def set_spawn_camera_position(position: Vector) -> None: ...
 def set_spawn_camera_look_at(position: Vector) -> None: ...
@@ -0,0 +1,87 @@
+from enum import IntEnum
+from typing import Optional
+
+class HandlingRule(IntEnum):
+    Mass = 1
+    TurnMass = 2
+    DragMultiplier = 3
+    CentreOfMassX = 4
+    CentreOfMassY = 5
+    CentreOfMassZ = 6
+    PercentSubmerged = 7
+    TractionMultiplier = 8
+    TractionLoss = 9
+    TractionBias = 10
+    NumberOfGears = 11
+    MaxSpeed = 12
+    Acceleration = 13
+    DriveType = 14
+    EngineType = 15
+    BrakeDeceleration = 16
+    BrakeBias = 17
+    SteeringLock = 18
+    SuspensionForceLevel = 19
+    SuspensionDampening = 20
+    SeatOffset = 21
+    DamageMultiplier = 22
+    SuspensionUpperLimit = 23
+    SuspensionLowerLimit = 24
+    SuspensionBias = 25
+    SuspensionAntiDive = 26
+    Flags = 27
+    FrontLights = 28
+    RearLights = 29
+
+    @property
+    def key(self) -> str:
+        """导入导出时用的名字"""
+        ...
+
+def exists_model_handling_rule(model: int, rule: int) -> bool: ...
+
+def get_model_handling_rule(model: int, rule: int) -> float: ...
+
+def set_model_handling_rule(model: int, rule: int, value: float) -> None: ...
+
+def reset_model_handling_rule(model: int, rule: int) -> None: ...
+
+def reset_model_handling(model: int) -> None: ...
+
+def reset_all_handling() -> None: ...
+
+def get_model_handling(
+    model: int, overridden_only: bool = False
+) -> dict[str, float]: ...
+
+def set_model_handling(
+    model: int, table: dict[str, float], reset: bool = False
+) -> None: ...
+
+def export_handling_preset(
+    models: Optional[list[int]] = None,
+    path: Optional[str] = None,
+    format: str = "toml",
+    overridden_only: bool = True,
+) -> str:
+    """
+    导出车型的操控预设，不传 models 的话导出所有被改过的车型
+
+    ```toml
+    [models.Infernus]
+    max_speed = 260.0
+    acceleration = 40.0
+    ```
+    """
+    ...
+
+def load_handling_preset(
+    content: str, format: str = "toml", reset: bool = False
+) -> int:
+    """从字符串导入车型的操控预设，返回改了多少条规则"""
+    ...
+
+def import_handling_preset(
+    path: str, format: Optional[str] = None, reset: bool = False
+) -> int:
+    """从文件导入，format 不传的话按后缀名判断"""
+    ...
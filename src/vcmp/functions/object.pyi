@@ -1,5 +1,7 @@
-from typing import Optional
+from typing import Optional, Union
 from .player import Player
+from .vehicle import Vehicle
+from .animation import ObjectAnimation
 from vcmp.types import Quaternion, Vector
 
 class Object:
@@ -35,6 +37,19 @@ class Object:
     def rotate_to_euler(
         self, vector: Vector, duration: Optional[int] = None
     ) -> None: ...
+    def animate(self, animation: ObjectAnimation, queue: bool = False) -> None: ...
+    def stop_animation(self) -> bool: ...
+    @property
+    def is_animating(self) -> bool: ...
+    def attach_to(
+        self,
+        entity: Union[Player, Vehicle],
+        offset: Optional[Vector] = None,
+        rotation: Optional[Quaternion] = None,
+    ) -> None: ...
+    def detach(self) -> bool: ...
+    @property
+    def attached_to(self) -> Optional[Union[Player, Vehicle]]: ...
     @property
     def shot_report(self) -> bool: ...
     @shot_report.setter
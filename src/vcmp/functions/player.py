@@ -219,9 +219,19 @@ class Player:
     def request_module_list(self) -> None: ...
     def restore_camera(self) -> None: ...
     def select(self) -> None: ...
-    def send_data(self, data: WriteStream) -> None: ...
+    def send_data(self, data: WriteStream, compress: bool = False) -> None:
+        """compress 为 True 时压缩后发送，客户端要用 extend/compress.nut 解压"""
+        ...
+    def send_chunked_data(
+        self, data: bytes | bytearray | str | WriteStream, chunk_size: Optional[int] = None
+    ) -> int:
+        """数据太大的时候分片发送，客户端要用 extend/chunk.nut 拼起来，返回 transfer id"""
+        ...
     def send_raw_message(self, color: RGB, message: str) -> None: ...
     def send_message(self, message: str) -> None: ...
+    def send_markup_message(self, markup: str) -> None:
+        """支持 `{red}Warning{/}` 这样的颜色 markup"""
+        ...
     def send_announce(self, announce_type: int, message: str) -> None: ...
     def set_camera_position(self, position: Vector, look_at: Vector) -> None: ...
     def set_camera(
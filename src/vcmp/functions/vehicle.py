@@ -147,6 +147,18 @@ class Vehicle:
     def set_position(self, position: Vector, remove_occupants: bool) -> None: ...
     def reset_handling(self) -> None: ...
     def reset_handling_rule(self, rule_index: int) -> None: ...
+    def get_handling(self, overridden_only: bool = False) -> dict[str, float]:
+        """这辆车的整张操控表，overridden_only 的话只要被改过的规则"""
+        ...
+    def set_handling(self, table: dict[str, float], reset: bool = False) -> None: ...
+    def export_handling(
+        self, format: str = "toml", overridden_only: bool = True
+    ) -> str:
+        """导出成 toml 或者 json"""
+        ...
+    def import_handling(
+        self, content: str, format: str = "toml", reset: bool = False
+    ) -> None: ...
     def respawn(self) -> None: ...
     def set_handling_rule(self, rule_index: int, rule_value: float) -> None: ...
     def set_part_status(self, part_index: int, status: int) -> None: ...
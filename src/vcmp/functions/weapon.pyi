@@ -0,0 +1,123 @@
+from enum import IntEnum
+from typing import Optional
+from vcmp.types import Weapon
+
+class WeaponField(IntEnum):
+    FireType = 1
+    Range = 2
+    FiringRate = 3
+    Reload = 4
+    ClipSize = 5
+    Damage = 6
+    Speed = 7
+    Radius = 8
+    LifeSpan = 9
+    Spread = 10
+    FireOffsetX = 11
+    FireOffsetY = 12
+    FireOffsetZ = 13
+    AnimGroup = 14
+    AnimLoopStart = 15
+    AnimLoopEnd = 16
+    AnimFirePos = 17
+    AnimTwoLoopStart = 18
+    AnimTwoLoopEnd = 19
+    AnimTwoFirePos = 20
+    AnimBreakoutPos = 21
+    ModelId = 22
+    Model2Id = 23
+    Flags = 24
+    Slot = 25
+
+    @property
+    def key(self) -> str:
+        """toml 预设里用的名字"""
+        ...
+
+class WeaponData:
+    @property
+    def weapon_id(self) -> int: ...
+    @property
+    def weapon(self) -> Optional[Weapon]: ...
+    def get(self, field: WeaponField) -> float: ...
+    def set(self, field: WeaponField, value: float) -> None: ...
+    def reset(self, field: Optional[WeaponField] = None) -> None:
+        """不传 field 的话整把武器都恢复默认"""
+        ...
+    def is_modified(self, field: Optional[WeaponField] = None) -> bool:
+        """不传 field 的话看是否有任意一个字段被改过"""
+        ...
+    def to_dict(self, modified_only: bool = False) -> dict[str, float]: ...
+    @property
+    def damage(self) -> float: ...
+    @damage.setter
+    def damage(self, value: float) -> None: ...
+    @property
+    def range(self) -> float: ...
+    @range.setter
+    def range(self, value: float) -> None: ...
+    @property
+    def firing_rate(self) -> float: ...
+    @firing_rate.setter
+    def firing_rate(self, value: float) -> None: ...
+    @property
+    def reload(self) -> float: ...
+    @reload.setter
+    def reload(self, value: float) -> None: ...
+    @property
+    def clip_size(self) -> float: ...
+    @clip_size.setter
+    def clip_size(self, value: float) -> None: ...
+    @property
+    def speed(self) -> float: ...
+    @speed.setter
+    def speed(self, value: float) -> None: ...
+    @property
+    def radius(self) -> float: ...
+    @radius.setter
+    def radius(self, value: float) -> None: ...
+    @property
+    def life_span(self) -> float: ...
+    @life_span.setter
+    def life_span(self, value: float) -> None: ...
+    @property
+    def spread(self) -> float: ...
+    @spread.setter
+    def spread(self, value: float) -> None: ...
+    @property
+    def slot(self) -> float: ...
+    @slot.setter
+    def slot(self, value: float) -> None: ...
+    def __repr__(self) -> str: ...
+
+def get_weapon_data(weapon: int) -> WeaponData: ...
+
+def reset_weapon_data(weapon: int) -> None: ...
+
+def reset_all_weapon_data() -> None: ...
+
+def export_weapon_preset(
+    path: Optional[str] = None, modified_only: bool = True, format: str = "toml"
+) -> str:
+    """
+    导出武器预设，传了 path 就顺便写到文件里，format 是 "toml" 或者 "json"
+
+    key 是武器名字，导入的时候也可以用武器 id，只认有武器数据的武器（0 ~ 36）
+
+    ```toml
+    [weapons.M4]
+    damage = 40.0
+    range = 90.0
+    ```
+    """
+    ...
+
+def load_weapon_preset(content: str, reset: bool = False, format: str = "toml") -> int:
+    """从字符串导入，返回改了多少个字段"""
+    ...
+
+def import_weapon_preset(
+    path: str, reset: bool = False, format: Optional[str] = None
+) -> int:
+    """从文件导入，返回改了多少个字段，format 不传的话按后缀名判断"""
+    ...
@@ -28,3 +28,25 @@ def clear_markers() -> int: ...
 def clear_objects() -> int: ...
 def clear_all() -> int: ...
 def find_player(value: str | int) -> Optional[Player]: ...
+
+def get_desync_count() -> int:
+    """
+    Get how many times the plugin found an entity missing from its pool during a callback.
+
+    The missing entity is inserted lazily and dispatching continues, so a non-zero value
+    means an entity pool change was missed somewhere.
+
+    Event getters such as `event.player` never insert: they raise `ValueError` once the
+    entity is gone, and a still connected player missing from the pool only counts here.
+
+    :return: The desync count since start (or since the last reset).
+    :rtype: int
+    """
+
+def reset_desync_count() -> int:
+    """
+    Reset the desync counter.
+
+    :return: The count before the reset.
+    :rtype: int
+    """
@@ -0,0 +1,44 @@
+from typing import Any, Callable, Optional
+
+
+def on_before_reload(
+    func: Optional[Callable[[], Optional[dict[str, Any]]]] = None,
+    name: Optional[str] = None,
+    version: int = 1,
+) -> Any:
+    """
+    Registers a function called before the script is unloaded on reload.
+
+    The function returns a picklable dict (or None to save nothing).
+    The pickled state is kept by the plugin and handed to the
+    `on_after_reload` functions with the same name.
+    Can be used as a decorator, with or without arguments.
+
+    Parameters
+    ----------
+    name : Optional[str]
+        The state name. Defaults to the function's module name, or its file
+        path for script entries, which all run as ``__main__``.
+    version : int
+        Passed to `on_after_reload` so newer scripts can migrate old states.
+    """
+    ...
+
+
+def on_after_reload(
+    func: Optional[Callable[[dict[str, Any], int], Any]] = None,
+    name: Optional[str] = None,
+) -> Any:
+    """
+    Registers a function called with `(state, version)` after the new script is loaded.
+
+    Only called when a state with the same name was saved before the reload.
+    Can be used as a decorator, with or without arguments.
+
+    Parameters
+    ----------
+    name : Optional[str]
+        The state name. Defaults to the function's module name, or its file
+        path for script entries, which all run as ``__main__``.
+    """
+    ...
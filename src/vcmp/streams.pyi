@@ -1,28 +1,143 @@
-from typing import Optional
+from typing import Any, Callable, Literal, Optional, TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from vcmp.functions.player import Player
+    from vcmp.types import Vector
+
+ByteOrder = Literal["big", "little"]
+
+FieldType = Literal["int", "byte", "float", "bool", "sq_string", "long", "string", "vector"]
 
 class WriteStream:
-    def __init__(self): ...
+    def __init__(self, byte_order: ByteOrder = "big"): ...
     def __repr__(self): ...
+    def __len__(self) -> int: ...
+    @property
+    def byte_order(self) -> ByteOrder:
+        """数字的字节序，sq_string 的长度前缀固定是大端"""
+        ...
+    @byte_order.setter
+    def byte_order(self, value: ByteOrder) -> None: ...
     def write_bytes(self, data: bytes | bytearray | int) -> None: ...
     def write_byte(self, value: int) -> None: ...
     def write_int(self, value: int) -> None: ...
+    def write_i8(self, value: int) -> None: ...
+    def write_u8(self, value: int) -> None: ...
+    def write_i16(self, value: int) -> None: ...
+    def write_u16(self, value: int) -> None: ...
+    def write_i32(self, value: int) -> None: ...
+    def write_u32(self, value: int) -> None: ...
+    def write_i64(self, value: int) -> None: ...
     def write_long(self, value: int) -> None: ...
     def write_sq_string(self, value: str) -> None: ...
     def write_string(self, value: str) -> None: ...
     def write_boolean(self, value: bool) -> None: ...
     def write_float(self, value: float) -> None: ...
-    def get_raw_buffer(self) -> bytes: ...
+    def write_f32(self, value: float) -> None: ...
+    def write_f64(self, value: float) -> None: ...
+    def write_vector(self, value: "Vector") -> None: ...
+    def get_raw_buffer(self, compress: bool = False) -> bytes:
+        """compress 为 True 时用 LZSS 压缩，压缩之后没变小就还是原始数据"""
+        ...
 
 class ReadStream:
-    def __init__(self, data: Optional[bytes]): ...
+    def __init__(self, data: Optional[bytes] = None, byte_order: ByteOrder = "big"): ...
     def __repr__(self): ...
+    @property
+    def byte_order(self) -> ByteOrder:
+        """数字的字节序，sq_string 的长度前缀固定是大端"""
+        ...
+    @byte_order.setter
+    def byte_order(self, value: ByteOrder) -> None: ...
+    def seek(self, offset: int, whence: int = 0) -> int:
+        """和 io 的 seek 一样，whence: 0 开头，1 当前位置，2 末尾"""
+        ...
+    def tell(self) -> int: ...
+    def remaining(self) -> int: ...
+    def peek(self, length: int) -> bytes:
+        """读但不移动位置"""
+        ...
     def read(self, length: int) -> bytes: ...
     def read_byte(self) -> int: ...
     def read_bytes(self, length: int) -> bytes: ...
     def read_int(self) -> int: ...
+    def read_i8(self) -> int: ...
+    def read_u8(self) -> int: ...
+    def read_i16(self) -> int: ...
+    def read_u16(self) -> int: ...
+    def read_i32(self) -> int: ...
+    def read_u32(self) -> int: ...
+    def read_i64(self) -> int: ...
     def read_long(self) -> int: ...
     def read_sq_string(self) -> str: ...
     def read_string(self) -> str: ...
     def read_boolean(self) -> bool: ...
     def read_float(self) -> float: ...
+    def read_f32(self) -> float: ...
+    def read_f64(self) -> float: ...
+    def read_vector(self) -> "Vector": ...
     def get_raw_buffer(self) -> bytes: ...
+
+class MessageSchema:
+    """
+    消息定义
+
+    先写入一个 int 作为消息 id，然后按字段顺序写入
+    """
+
+    def __init__(self, id: int, fields: list[tuple[str, FieldType]]): ...
+    @property
+    def id(self) -> int: ...
+    @property
+    def fields(self) -> list[tuple[str, FieldType]]: ...
+    def encode(self, values: Optional[dict[str, Any]] = None, **kwargs: Any) -> WriteStream: ...
+    def decode(self, stream: ReadStream) -> dict[str, Any]:
+        """从 stream 的当前位置开始读，消息 id 不对会抛出 ValueError"""
+        ...
+    def send(
+        self, player: "Player", values: Optional[dict[str, Any]] = None, **kwargs: Any
+    ) -> None: ...
+    def handler(
+        self, func: Optional[Callable[["Player", dict[str, Any]], Any]] = None
+    ) -> Callable[..., Any]:
+        """注册客户端发来这条消息时的处理函数，不传 func 就当装饰器用"""
+        ...
+
+CHUNK_MAGIC: int
+DEFAULT_CHUNK_SIZE: int
+
+def send_chunked(
+    player: "Player",
+    data: bytes | bytearray | str | WriteStream,
+    chunk_size: Optional[int] = None,
+) -> int:
+    """分片发送给玩家，客户端要用 extend/chunk.nut 拼起来，返回 transfer id"""
+    ...
+
+COMPRESS_MAGIC: int
+
+def compress(data: bytes) -> bytes:
+    """LZSS 压缩并加上头部，压缩之后没变小就原样返回"""
+    ...
+
+def decompress(data: bytes) -> bytes:
+    """不是压缩过的数据会原样返回"""
+    ...
+
+def broadcast(
+    stream: WriteStream,
+    players: Optional[list["Player"]] = None,
+    world: Optional[int] = None,
+    team: Optional[int] = None,
+    exclude: list["Player"] = [],
+    compress: bool = False,
+) -> int:
+    """
+    编码一次发给多个玩家，返回发送成功的人数
+
+    players 为 None 时是所有玩家，再按 world / team 过滤，exclude 里的玩家不发
+
+    compress 跟 `Player.send_data` 的一样，为 True 时只压缩一次再发给所有人，
+    客户端要用 extend/compress.nut 解压
+    """
+    ...
@@ -1,4 +1,5 @@
 from enum import Enum, IntEnum
+from typing import Iterator, Optional
 
 class ServerSettings:
     @property
@@ -25,10 +26,44 @@ class Vector:
     z: float
 
     def __init__(self, x: float, y: float, z: float): ...
-    def __add__(self, other: "Vector") -> "Vector": ...
-    def __sub__(self, other: "Vector") -> "Vector": ...
-    def __mul__(self, other: "Vector") -> "Vector": ...
-    def __div__(self, other: "Vector") -> "Vector": ...
+    def __add__(self, other: "Vector | float") -> "Vector":
+        """运算的结果都是独立的值，不会写回实体，写回要用 setter 或者 add_position 之类的方法"""
+        ...
+    def __sub__(self, other: "Vector | float") -> "Vector": ...
+    def __mul__(self, other: "Vector | float") -> "Vector": ...
+    def __truediv__(self, other: "Vector | float") -> "Vector": ...
+    def __radd__(self, other: float) -> "Vector": ...
+    def __rsub__(self, other: float) -> "Vector": ...
+    def __rmul__(self, other: float) -> "Vector": ...
+    def __rtruediv__(self, other: float) -> "Vector": ...
+    def __neg__(self) -> "Vector": ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> float: ...
+    def __iter__(self) -> Iterator[float]: ...
+    @property
+    def is_detached(self) -> bool:
+        """是否是独立的值（不是实体的位置/速度等）"""
+        ...
+    def detach(self) -> "Vector":
+        """复制一份当前的值，之后的修改不会再影响实体"""
+        ...
+    def distance(self, other: "Vector") -> float: ...
+    def distance_with_z(self, other: "Vector") -> float: ...
+    def length(self) -> float: ...
+    def length_squared(self) -> float: ...
+    def normalize(self) -> "Vector": ...
+    def dot(self, other: "Vector") -> float: ...
+    def cross(self, other: "Vector") -> "Vector": ...
+    def lerp(self, other: "Vector", t: float) -> "Vector": ...
+    def angle_to(self, other: "Vector") -> float:
+        """两个向量之间的夹角（弧度）"""
+        ...
+    def heading(self) -> float:
+        """XY 平面上的朝向（弧度），+Y 为 0，逆时针为正"""
+        ...
+    def rotate_around_z(
+        self, angle: float, origin: "Vector | None" = None
+    ) -> "Vector": ...
 
 class Quaternion:
     x: float
@@ -37,16 +72,73 @@ class Quaternion:
     w: float
 
     def __init__(self, x: float, y: float, z: float, w: float): ...
-    def __mul__(self, other: "Quaternion") -> "Quaternion": ...
-    def __div__(self, other: "Quaternion") -> "Quaternion": ...
-    def __add__(self, other: "Quaternion") -> "Quaternion": ...
-    def __sub__(self, other: "Quaternion") -> "Quaternion": ...
+    def __mul__(self, other: "Quaternion") -> "Quaternion":
+        """旋转的组合（Hamilton product），`a * b` 是先转 b 再转 a，结果是独立的值"""
+        ...
+    def __truediv__(self, other: "Quaternion") -> "Quaternion":
+        """等于 `self * other.inverse()`"""
+        ...
+    def __add__(self, other: "Quaternion") -> "Quaternion":
+        """逐分量相加，不是旋转的组合，结果是独立的值"""
+        ...
+    def __sub__(self, other: "Quaternion") -> "Quaternion":
+        """逐分量相减，结果是独立的值"""
+        ...
+    @property
+    def is_detached(self) -> bool:
+        """是否是独立的值（不是实体的旋转）"""
+        ...
+    def detach(self) -> "Quaternion":
+        """复制一份当前的值，之后的修改不会再影响实体"""
+        ...
+    def length(self) -> float: ...
+    def dot(self, other: "Quaternion") -> float: ...
+    def conjugate(self) -> "Quaternion": ...
+    def inverse(self) -> "Quaternion": ...
+    def normalize(self) -> "Quaternion": ...
+    def to_euler(self) -> Vector:
+        """转成欧拉角（弧度），顺序和 from_euler 一样"""
+        ...
+    def rotate(self, vector: Vector) -> Vector:
+        """用这个旋转去转一个向量"""
+        ...
+    def slerp(self, other: "Quaternion", t: float) -> "Quaternion":
+        """球面插值，t = 0 是自己，t = 1 是 other"""
+        ...
+    @staticmethod
+    def from_euler(euler: Vector) -> "Quaternion":
+        """欧拉角（弧度）转四元数，先绕 X 再绕 Y 最后绕 Z"""
+        ...
+    @staticmethod
+    def from_axis_angle(axis: Vector, angle: float) -> "Quaternion":
+        """绕 axis 旋转 angle（弧度）"""
+        ...
+    @staticmethod
+    def look_rotation(forward: Vector, up: Vector | None = None) -> "Quaternion":
+        """让 +Y（游戏里的正前方）朝向 forward，up 默认是 +Z"""
+        ...
 
 class RGB:
     r: int
     g: int
     b: int
     a: int
+    WHITE: "RGB"
+    BLACK: "RGB"
+    RED: "RGB"
+    GREEN: "RGB"
+    LIME: "RGB"
+    BLUE: "RGB"
+    YELLOW: "RGB"
+    ORANGE: "RGB"
+    GOLD: "RGB"
+    PURPLE: "RGB"
+    PINK: "RGB"
+    CYAN: "RGB"
+    MAGENTA: "RGB"
+    BROWN: "RGB"
+    GREY: "RGB"
+    GRAY: "RGB"
 
     def __init__(self, r: int, g: int, b: int, a: int = 255): ...
     def as_rgba(self) -> int: ...
@@ -61,6 +153,30 @@ class RGB:
     def from_rgba(value: int) -> "RGB": ...
     @staticmethod
     def from_argb(value: int) -> "RGB": ...
+    @staticmethod
+    def from_hsv(h: float, s: float, v: float, a: int = 255) -> "RGB":
+        """h 是角度，s 和 v 是 0 到 1"""
+        ...
+    def to_hsv(self) -> tuple[float, float, float]: ...
+    @staticmethod
+    def from_hsl(h: float, s: float, l: float, a: int = 255) -> "RGB":  # noqa: E741
+        """h 是角度，s 和 l 是 0 到 1"""
+        ...
+    def to_hsl(self) -> tuple[float, float, float]: ...
+    def blend(self, other: "RGB", t: float = 0.5) -> "RGB":
+        """t 为 0 是自己，为 1 是 other"""
+        ...
+    @staticmethod
+    def parse(text: str) -> "RGB":
+        """解析 `#RRGGBB`、`#RRGGBBAA`、`[#RRGGBB]` 或者颜色名字"""
+        ...
+    @staticmethod
+    def named_colors() -> list[str]: ...
+    def as_code(self) -> str:
+        """消息里用的内联颜色代码，`[#RRGGBB]`"""
+        ...
+    def __eq__(self, other: "RGB") -> bool: ...
+    def __hash__(self) -> int: ...
 
 class Version(IntEnum):
     """Version enum."""
@@ -273,10 +389,13 @@ class EventType(Enum):
 
     # Server Extra
     ServerReloaded = ...
+    ServerReloadFailed = ...
+    SettingChanged = ...
 
     # Player
     IncomingConnection = ...
     ClientScriptData = ...
+    ClientChunkedData = ...
     PlayerConnect = ...
     PlayerDisconnect = ...
     PlayerRequestClass = ...
@@ -311,6 +430,13 @@ class EventType(Enum):
     PlayerWeaponChange = ...
     PlayerAmmoChange = ...
     PlayerMove = ...
+    PlayerSkinChange = ...
+    PlayerTeamChange = ...
+    PlayerScoreChange = ...
+    PlayerWorldChange = ...
+    PlayerCashChange = ...
+    PlayerWantedLevelChange = ...
+    PlayerDamage = ...
 
     # Pickup
     PickupPickAttempt = ...
@@ -324,6 +450,7 @@ class EventType(Enum):
     # Object
     ObjectShot = ...
     ObjectTouched = ...
+    ObjectAnimationFinished = ...
 
     # Vehicle
     VehicleExplode = ...
@@ -333,6 +460,525 @@ class EventType(Enum):
     # Vehicle Extra
     VehicleMove = ...
     VehicleHealthChange = ...
+    VehicleDamageChange = ...
+    VehicleTyrePop = ...
+    VehiclePartStatusChange = ...
+    VehicleLightsDataChange = ...
+    VehicleSirenChange = ...
+    VehicleDoorsLockedChange = ...
+    VehicleHandlingChange = ...
 
     # Custom
     Custom = ...
+
+# 下面这些枚举只用来传参（实现了 __index__，收 int 的地方都能传），
+# 事件字段和 getter 返回的还是 int，需要的话用 `Weapon.from_id(event.reason)` 转一下
+class Weapon(IntEnum):
+    """武器 id"""
+
+    Fists = 0
+    BrassKnuckles = 1
+    Screwdriver = 2
+    Golfclub = 3
+    Nitestick = 4
+    Knife = 5
+    BaseballBat = 6
+    Hammer = 7
+    MeatCleaver = 8
+    Machete = 9
+    Katana = 10
+    Chainsaw = 11
+    Grenades = 12
+    RemoteGrenades = 13
+    Teargas = 14
+    MolotovCocktails = 15
+    Missile = 16
+    Colt45 = 17
+    Python = 18
+    Shotgun = 19
+    SpazShotgun = 20
+    StubbyShotgun = 21
+    Tec9 = 22
+    Uzi = 23
+    Ingram = 24
+    MP5 = 25
+    M4 = 26
+    Ruger = 27
+    SniperRifle = 28
+    LaserSniper = 29
+    RocketLauncher = 30
+    FlameThrower = 31
+    M60 = 32
+    Minigun = 33
+    Bomb = 34
+    Helicannon = 35
+    Camera = 36
+    Vehicle = 39
+    HeliBlades = 40
+    Explosion = 41
+    DriveBy = 42
+    Drown = 43
+    Fall = 44
+    Explosion_51 = 51
+    HeliBlades_60 = 60
+    Suicide = 70
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["Weapon"]: ...
+    @staticmethod
+    def from_name(name: str) -> "Weapon":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class VehicleModel(IntEnum):
+    """载具模型"""
+
+    Landstalker = 130
+    Idaho = 131
+    Stinger = 132
+    Linerunner = 133
+    Perennial = 134
+    Sentinel = 135
+    Rio = 136
+    Firetruck = 137
+    Trashmaster = 138
+    Stretch = 139
+    Manana = 140
+    Infernus = 141
+    Voodoo = 142
+    Pony = 143
+    Mule = 144
+    Cheetah1 = 145
+    Ambulance = 146
+    FBIWashington = 147
+    Moonbeam = 148
+    Esperanto = 149
+    Taxi = 150
+    Washington = 151
+    Bobcat = 152
+    MrWhoopee = 153
+    BFInjection = 154
+    Hunter = 155
+    Police = 156
+    Enforcer = 157
+    Securicar = 158
+    Banshee = 159
+    Predator = 160
+    Bus = 161
+    Rhino = 162
+    BarracksOL = 163
+    CubanHermes = 164
+    Angel = 166
+    Coach = 167
+    Cabbie = 168
+    Stallion = 169
+    Rumpo = 170
+    RCBandit = 171
+    RomeroSHearse = 172
+    Packer = 173
+    SentinelXS = 174
+    Admiral = 175
+    Squalo = 176
+    SeaSparrow = 177
+    PizzaBoy = 178
+    GangBurrito = 179
+    Speeder = 182
+    Reefer = 183
+    Tropic = 184
+    Flatbed = 185
+    Yankee = 186
+    Caddy = 187
+    ZebraCab = 188
+    TopFun = 189
+    Skimmer = 190
+    PCJ600 = 191
+    Faggio = 192
+    Freeway = 193
+    RCBaron = 194
+    RCRaider = 195
+    Glendale = 196
+    Oceanic = 197
+    Sanchez = 198
+    Sparrow = 199
+    Patriot = 200
+    LoveFist = 201
+    CoastGuard = 202
+    Dinghy = 203
+    Hermes = 204
+    Sabre = 205
+    SabreTurbo = 206
+    Phoenix = 207
+    Walton = 208
+    Regina = 209
+    Comet = 210
+    Deluxo = 211
+    Burrito = 212
+    SpandExpress = 213
+    Marquis = 214
+    BaggageHandler = 215
+    KaufmanCab = 216
+    Maverick = 217
+    VCNMaverick = 218
+    Rancher = 219
+    FBIRancher = 220
+    Virgo = 221
+    Greenwood = 222
+    CubanJetmax = 223
+    HotringRacer1 = 224
+    Sandking = 225
+    BlistaCompact = 226
+    PoliceMaverick = 227
+    Boxville = 228
+    Benson = 229
+    MesaGrande = 230
+    RCGoblin = 231
+    HotringRacer2 = 232
+    HotringRacer3 = 233
+    BloodringBanger1 = 234
+    BloodringBanger2 = 235
+    Cheetah2 = 236
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["VehicleModel"]: ...
+    @staticmethod
+    def from_name(name: str) -> "VehicleModel":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class Skin(IntEnum):
+    """皮肤，重名的后面加上 id"""
+
+    TommyVercetti = 0
+    Cop = 1
+    SWAT = 2
+    FBI = 3
+    Army = 4
+    Paramedic = 5
+    Firefighter = 6
+    GolfGuy1 = 7
+    BumLady1 = 9
+    BumLady2 = 10
+    Punk1 = 11
+    Lawyer = 12
+    SpanishLady1 = 13
+    SpanishLady2 = 14
+    CoolGuy1 = 15
+    ArabicGuy = 16
+    BeachLady1 = 17
+    BeachLady2 = 18
+    BeachGuy1 = 19
+    BeachGuy2 = 20
+    OfficeLady1 = 21
+    Waitress1 = 22
+    FoodLady = 23
+    Prostitute1 = 24
+    BumLady3 = 25
+    BumGuy1 = 26
+    Garbageman1 = 27
+    TaxiDriver1 = 28
+    Haitian1 = 29
+    Criminal1 = 30
+    HoodLady = 31
+    Granny1 = 32
+    Businessman1 = 33
+    ChurchGuy = 34
+    ClubLady = 35
+    ChurchLady = 36
+    Pimp = 37
+    BeachLady3 = 38
+    BeachGuy3 = 39
+    BeachLady4 = 40
+    BeachGuy4 = 41
+    Businessman2 = 42
+    Prostitute2 = 43
+    BumLady4 = 44
+    BumGuy2 = 45
+    Haitian2 = 46
+    ConstructionWorker1 = 47
+    Punk2 = 48
+    Prostitute3 = 49
+    Granny2 = 50
+    Punk3 = 51
+    Businessman3 = 52
+    SpanishLady3 = 53
+    SpanishLady4 = 54
+    CoolGuy2 = 55
+    Businessman4 = 56
+    BeachLady5 = 57
+    BeachGuy5 = 58
+    BeachLady6 = 59
+    BeachGuy6 = 60
+    ConstructionWorker2 = 61
+    GolfGuy2 = 62
+    GolfLady = 63
+    GolfGuy3 = 64
+    BeachLady7 = 65
+    BeachGuy7 = 66
+    OfficeLady2 = 67
+    Businessman5 = 68
+    Businessman6 = 69
+    Prostitute2_70 = 70
+    BumLady4_71 = 71
+    BumGuy3 = 72
+    SpanishGuy = 73
+    TaxiDriver2 = 74
+    GymLady = 75
+    GymGuy = 76
+    SkateLady = 77
+    SkateGuy = 78
+    Shopper1 = 79
+    Shopper2 = 80
+    Tourist1 = 81
+    Tourist2 = 82
+    Cuban1 = 83
+    Cuban2 = 84
+    Haitian3 = 85
+    Haitian4 = 86
+    Shark1 = 87
+    Shark2 = 88
+    DiazGuy1 = 89
+    DiazGuy2 = 90
+    DBPSecurity1 = 91
+    DBPSecurity2 = 92
+    Biker1 = 93
+    Biker2 = 94
+    VercettiGuy1 = 95
+    VercettiGuy2 = 96
+    UndercoverCop1 = 97
+    UndercoverCop2 = 98
+    UndercoverCop3 = 99
+    UndercoverCop4 = 100
+    UndercoverCop5 = 101
+    UndercoverCop6 = 102
+    RichGuy = 103
+    CoolGuy3 = 104
+    Prostitute3_105 = 105
+    Prostitute4 = 106
+    LoveFist1 = 107
+    KenRosenburg = 108
+    CandySuxx = 109
+    Hilary = 110
+    LoveFist2 = 111
+    Phil = 112
+    RockstarGuy = 113
+    Sonny = 114
+    Lance = 115
+    Mercedes = 116
+    LoveFist3 = 117
+    AlexShrub = 118
+    LanceCop = 119
+    Lance_120 = 120
+    Cortez = 121
+    LoveFist4 = 122
+    ColumbianGuy1 = 123
+    HilaryRobber = 124
+    Mercedes_125 = 125
+    Cam = 126
+    CamRobber = 127
+    PhilOneArm = 128
+    PhilRobber = 129
+    CoolGuy4 = 130
+    PizzaMan = 131
+    TaxiDriver1_132 = 132
+    TaxiDriver2_133 = 133
+    Sailor1 = 134
+    Sailor2 = 135
+    Sailor3 = 136
+    Chef = 137
+    Criminal2 = 138
+    FrenchGuy = 139
+    Garbageman2 = 140
+    Haitian5 = 141
+    Waitress2 = 142
+    SonnyGuy1 = 143
+    SonnyGuy2 = 144
+    SonnyGuy3 = 145
+    ColumbianGuy2 = 146
+    Haitian6 = 147
+    BeachGuy8 = 148
+    Garbageman3 = 149
+    Garbageman4 = 150
+    Garbageman5 = 151
+    Tranny = 152
+    Thug5 = 153
+    SpandExGuy1 = 154
+    SpandExGuy2 = 155
+    Stripper1 = 156
+    Stripper2 = 157
+    Stripper3 = 158
+    StoreClerk = 159
+    TommyWithSuit = 161
+    WorkerTommy = 162
+    GolferTommy = 163
+    CubanTommy = 164
+    VCPDTommy = 165
+    BankRobberTommy = 166
+    StreetTommy = 167
+    MafiaTommy = 168
+    JoggerTommy1 = 169
+    JoggerTommy2 = 170
+    GuyWithSuit1 = 171
+    GuyWithSuit3 = 172
+    Prostitute5 = 173
+    Rico = 174
+    Prostitute3_175 = 175
+    ClubLady_176 = 176
+    Prostitute2_177 = 177
+    SkullTShirtGuy = 178
+    EasterEggTommy = 179
+    DiazGangster1 = 180
+    DiazGangster2 = 181
+    HoodLady_182 = 182
+    Punk1_183 = 183
+    TrayLady = 184
+    KentPaul = 185
+    TaxiDriver1_186 = 186
+    DeformedKenRosenberg = 187
+    DeformedWoman = 188
+    DeformedMan = 189
+    DeformedCortez = 190
+    DeformedLanceVance = 191
+    Thief1 = 192
+    Thief2 = 193
+    Thief3 = 194
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["Skin"]: ...
+    @staticmethod
+    def from_name(name: str) -> "Skin":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class BodyPart(IntEnum):
+    """被打中的身体部位"""
+
+    Body = 0
+    Torso = 1
+    LeftArm = 2
+    RightArm = 3
+    LeftLeg = 4
+    RightLeg = 5
+    Head = 6
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["BodyPart"]: ...
+    @staticmethod
+    def from_name(name: str) -> "BodyPart":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class PlayerState(IntEnum):
+    """玩家状态"""
+
+    NoState = 0
+    Normal = 1
+    Aim = 2
+    Driver = 3
+    Passenger = 4
+    EnterDriver = 5
+    EnterPassenger = 6
+    Exit = 7
+    Unspawned = 8
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["PlayerState"]: ...
+    @staticmethod
+    def from_name(name: str) -> "PlayerState":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class PlayerAction(IntEnum):
+    """玩家动作"""
+
+    NoAction = 0
+    Normal = 1
+    Aiming = 12
+    Shooting = 16
+    Jumping = 41
+    LieDown = 42
+    GettingUp = 43
+    JumpFromVehicle = 44
+    Driving = 50
+    Dying = 54
+    Wasted = 55
+    EnteringVehicle = 58
+    ExitingVehicle = 60
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["PlayerAction"]: ...
+    @staticmethod
+    def from_name(name: str) -> "PlayerAction":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class ExplosionType(IntEnum):
+    """爆炸类型"""
+
+    Grenade = 0
+    Molotov = 1
+    Rocket = 2
+    Car = 3
+    CarQuick = 4
+    Boat = 5
+    Heli = 6
+    Mine = 7
+    Barrel = 8
+    TankGrenade = 9
+    HeliBomb = 10
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["ExplosionType"]: ...
+    @staticmethod
+    def from_name(name: str) -> "ExplosionType":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
+
+class Weather(IntEnum):
+    """天气"""
+
+    Sunny = 0
+    Cloudy = 1
+    Rainy = 2
+    Foggy = 3
+    ExtraSunny = 4
+    Hurricane = 5
+    ExtraColours = 6
+
+    @property
+    def display_name(self) -> str:
+        """游戏里的名字"""
+        ...
+    @staticmethod
+    def from_id(value: int) -> Optional["Weather"]: ...
+    @staticmethod
+    def from_name(name: str) -> "Weather":
+        """枚举名和显示名都可以，不区分大小写"""
+        ...
@@ -1,3 +1,6 @@
+from typing import Optional
+
+from vcmp.functions.player import Player
 from vcmp.types import RGB
 
 WEAPON_NAMES: dict[int, str] = ...
@@ -39,3 +42,29 @@ def in_poly(
 def announce_all(announce_type: int, message: str): ...
 def message_all(message: str): ...
 def raw_message_all(color: RGB, message: str): ...
+
+def format_markup(markup: str, base: Optional[RGB] = None) -> str:
+    """
+    把 `{red}Warning{/}` 这样的 markup 转成客户端的内联颜色代码
+
+    `{颜色}` 开始一段颜色，可以是名字或者 `#RRGGBB`，`{/}` 回到上一层颜色，
+    `{{` 和 `}}` 是字面上的大括号
+    """
+    ...
+
+def markup_message_all(markup: str) -> str: ...
+
+class MessageBuilder:
+    def __init__(self, base: Optional[RGB] = None) -> None: ...
+    def text(self, text: str) -> "MessageBuilder":
+        """原样追加，不解析 markup"""
+        ...
+    def colored(self, color: RGB, text: str) -> "MessageBuilder":
+        """追加一段指定颜色的文字，之后回到基础颜色"""
+        ...
+    def markup(self, markup: str) -> "MessageBuilder": ...
+    def build(self) -> str: ...
+    def send(self, player: Player) -> None: ...
+    def send_all(self) -> None: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...